@@ -0,0 +1,140 @@
+use super::provider::{AiConfig, ProviderKind};
+use color_eyre::eyre::Result;
+use std::env;
+use std::fmt;
+use std::io::{self, BufRead, IsTerminal};
+
+/// Keyring service the API keys are stored under, one entry per provider
+const SERVICE: &str = "spring-init";
+
+/// Where an API key was found
+pub enum KeySource {
+    Keyring,
+    Env(&'static str),
+    Config,
+}
+
+impl fmt::Display for KeySource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KeySource::Keyring => write!(f, "the OS keyring"),
+            KeySource::Env(var) => write!(f, "{}", var),
+            KeySource::Config => write!(f, "ai.api_key in the config"),
+        }
+    }
+}
+
+/// Environment variable holding the provider's API key, if it needs one
+pub fn env_var(provider: ProviderKind) -> Option<&'static str> {
+    match provider {
+        ProviderKind::Anthropic => Some("ANTHROPIC_API_KEY"),
+        ProviderKind::OpenAi => Some("OPENAI_API_KEY"),
+        ProviderKind::Ollama => None,
+    }
+}
+
+fn provider_name(provider: ProviderKind) -> &'static str {
+    match provider {
+        ProviderKind::Anthropic => "anthropic",
+        ProviderKind::OpenAi => "openai",
+        ProviderKind::Ollama => "ollama",
+    }
+}
+
+fn entry(provider: ProviderKind) -> Result<keyring::Entry> {
+    keyring::Entry::new(SERVICE, provider_name(provider))
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to open the OS keyring: {}", e))
+}
+
+/// Look up the configured provider's API key in the keyring, then the
+/// environment, then the config file
+pub fn lookup(config: &AiConfig) -> Option<(String, KeySource)> {
+    let var = env_var(config.provider)?;
+    // An unavailable keyring (e.g. no secret service on a CI runner) falls through to the environment
+    if let Some(key) = entry(config.provider)
+        .ok()
+        .and_then(|entry| entry.get_password().ok())
+    {
+        return Some((key, KeySource::Keyring));
+    }
+    if let Ok(key) = env::var(var) {
+        if !key.trim().is_empty() {
+            return Some((key, KeySource::Env(var)));
+        }
+    }
+    config
+        .api_key
+        .clone()
+        .filter(|key| !key.trim().is_empty())
+        .map(|key| (key, KeySource::Config))
+}
+
+/// API key for the configured provider, failing with a hint on how to set one
+pub fn api_key(config: &AiConfig) -> Result<String> {
+    lookup(config).map(|(key, _)| key).ok_or_else(|| {
+        let name = provider_name(config.provider);
+        color_eyre::eyre::eyre!(
+            "No API key for {}, run `spring-init auth login {}` or set {}",
+            name,
+            name,
+            env_var(config.provider).unwrap_or_default()
+        )
+    })
+}
+
+/// Read an API key without echoing it, or from stdin when it isn't a terminal
+fn read_key(provider: ProviderKind) -> Result<String> {
+    let key = if io::stdin().is_terminal() {
+        inquire::Password::new(&format!("{} API key:", provider_name(provider)))
+            .without_confirmation()
+            .prompt()?
+    } else {
+        let mut line = String::new();
+        io::stdin().lock().read_line(&mut line)?;
+        line
+    };
+    let key = key.trim().to_string();
+    if key.is_empty() {
+        return Err(color_eyre::eyre::eyre!("No API key given"));
+    }
+    Ok(key)
+}
+
+/// Store the provider's API key in the OS keyring
+pub fn login(provider: ProviderKind) -> Result<()> {
+    if env_var(provider).is_none() {
+        return Err(color_eyre::eyre::eyre!(
+            "{} doesn't use an API key",
+            provider_name(provider)
+        ));
+    }
+    let key = read_key(provider)?;
+    entry(provider)?
+        .set_password(&key)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to store the API key: {}", e))?;
+    println!(
+        "Stored the {} API key in the OS keyring",
+        provider_name(provider)
+    );
+    Ok(())
+}
+
+/// Remove the provider's API key from the OS keyring
+pub fn logout(provider: ProviderKind) -> Result<()> {
+    match entry(provider)?.delete_credential() {
+        Ok(()) => println!(
+            "Removed the {} API key from the OS keyring",
+            provider_name(provider)
+        ),
+        Err(keyring::Error::NoEntry) => {
+            println!("No {} API key in the OS keyring", provider_name(provider))
+        }
+        Err(e) => {
+            return Err(color_eyre::eyre::eyre!(
+                "Failed to remove the API key: {}",
+                e
+            ))
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use super::provider::{LlmProvider, Usage};
+use crate::http::Http;
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
+
+const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1/messages";
+pub const DEFAULT_MODEL: &str = "claude-3-5-sonnet-20241022";
+
+#[derive(Debug, Serialize)]
+pub struct Message {
+    pub role: String,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClaudeRequest {
+    pub model: String,
+    pub messages: Vec<Message>,
+    pub max_tokens: u32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<Tool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ToolChoice>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Tool {
+    pub name: String,
+    pub input_schema: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ToolChoice {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClaudeResponse {
+    pub content: Vec<ContentBlock>,
+    pub usage: Usage,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentBlock {
+    ToolUse {
+        input: serde_json::Value,
+    },
+    #[serde(other)]
+    Other,
+}
+
+pub struct ClaudeClient {
+    http: Http,
+    api_key: String,
+    model: String,
+}
+
+impl ClaudeClient {
+    pub fn new(http: Http, api_key: String, model: Option<String>) -> Self {
+        Self {
+            http,
+            api_key,
+            model: model.unwrap_or_else(|| DEFAULT_MODEL.to_string()),
+        }
+    }
+
+    fn headers(&self) -> Result<HeaderMap, color_eyre::eyre::Error> {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        headers.insert("x-api-key", HeaderValue::from_str(&self.api_key)?);
+        headers.insert(
+            "anthropic-version",
+            HeaderValue::from_static("2023-06-01"),
+        );
+        Ok(headers)
+    }
+}
+
+#[async_trait]
+impl LlmProvider for ClaudeClient {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn send_structured(
+        &self,
+        system_prompt: &str,
+        user_message: &str,
+        name: &str,
+        schema: &serde_json::Value,
+    ) -> Result<(serde_json::Value, Usage), color_eyre::eyre::Error> {
+        // Forcing a tool call makes Claude answer with input matching the schema
+        let request = ClaudeRequest {
+            model: self.model.clone(),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: format!("{}\n\n{}", system_prompt, user_message),
+            }],
+            max_tokens: 4096,
+            tools: vec![Tool {
+                name: name.to_string(),
+                input_schema: schema.clone(),
+            }],
+            tool_choice: Some(ToolChoice {
+                kind: "tool".to_string(),
+                name: name.to_string(),
+            }),
+        };
+
+        let headers = self.headers()?;
+        let response = self
+            .http
+            .send(|client| {
+                client
+                    .post(ANTHROPIC_API_URL)
+                    .headers(headers.clone())
+                    .json(&request)
+            })
+            .await?
+            .error_for_status()?;
+
+        let claude_response: ClaudeResponse = response.json().await?;
+        let input = claude_response
+            .content
+            .into_iter()
+            .find_map(|block| match block {
+                ContentBlock::ToolUse { input } => Some(input),
+                ContentBlock::Other => None,
+            })
+            .ok_or_else(|| color_eyre::eyre::eyre!("Claude did not return structured output"))?;
+        Ok((input, claude_response.usage))
+    }
+}
@@ -0,0 +1,8 @@
+pub mod auth;
+pub mod claude;
+pub mod ollama;
+pub mod openai;
+pub mod provider;
+pub mod suggest;
+
+pub use suggest::Suggester;
@@ -0,0 +1,135 @@
+use super::provider::{LlmProvider, Usage};
+use crate::http::Http;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_BASE_URL: &str = "http://localhost:11434";
+pub const DEFAULT_MODEL: &str = "llama3.1";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Message {
+    pub role: String,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChatRequest {
+    pub model: String,
+    pub messages: Vec<Message>,
+    pub stream: bool,
+    /// JSON schema the response must match
+    pub format: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChatResponse {
+    pub message: Message,
+    #[serde(default)]
+    pub prompt_eval_count: u32,
+    #[serde(default)]
+    pub eval_count: u32,
+}
+
+pub struct OllamaClient {
+    http: Http,
+    base_url: String,
+    model: String,
+}
+
+impl OllamaClient {
+    pub fn new(http: Http, base_url: Option<String>, model: Option<String>) -> Self {
+        Self {
+            http,
+            base_url: base_url
+                .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            model: model.unwrap_or_else(|| DEFAULT_MODEL.to_string()),
+        }
+    }
+
+    async fn chat(
+        &self,
+        system_prompt: &str,
+        user_message: &str,
+        format: serde_json::Value,
+    ) -> Result<(String, Usage), color_eyre::eyre::Error> {
+        let request = ChatRequest {
+            model: self.model.clone(),
+            messages: vec![
+                Message {
+                    role: "system".to_string(),
+                    content: system_prompt.to_string(),
+                },
+                Message {
+                    role: "user".to_string(),
+                    content: user_message.to_string(),
+                },
+            ],
+            stream: false,
+            format,
+        };
+
+        let url = format!("{}/api/chat", self.base_url);
+        let response = self
+            .http
+            .send(|client| client.post(&url).json(&request))
+            .await
+            .map_err(|e| {
+                if e.is_connect() {
+                    color_eyre::eyre::eyre!(
+                        "Could not reach Ollama at {}, is `ollama serve` running?",
+                        self.base_url
+                    )
+                } else {
+                    e.into()
+                }
+            })?;
+
+        if !response.status().is_success() {
+            // Ollama reports problems such as unknown models as {"error": "..."}
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let message = serde_json::from_str::<serde_json::Value>(&body)
+                .ok()
+                .and_then(|json| json["error"].as_str().map(String::from))
+                .unwrap_or(body);
+            return Err(color_eyre::eyre::eyre!(
+                "Ollama request failed ({}): {}",
+                status,
+                message.trim()
+            ));
+        }
+
+        let chat_response: ChatResponse = response.json().await?;
+        Ok((
+            chat_response.message.content,
+            Usage {
+                input_tokens: chat_response.prompt_eval_count,
+                output_tokens: chat_response.eval_count,
+            },
+        ))
+    }
+}
+
+#[async_trait]
+impl LlmProvider for OllamaClient {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn send_structured(
+        &self,
+        system_prompt: &str,
+        user_message: &str,
+        _name: &str,
+        schema: &serde_json::Value,
+    ) -> Result<(serde_json::Value, Usage), color_eyre::eyre::Error> {
+        let (text, usage) = self
+            .chat(system_prompt, user_message, schema.clone())
+            .await?;
+        let value = serde_json::from_str(&text)
+            .map_err(|e| color_eyre::eyre::eyre!("Ollama returned invalid JSON: {}", e))?;
+        Ok((value, usage))
+    }
+}
@@ -0,0 +1,136 @@
+use super::provider::{LlmProvider, Usage};
+use crate::http::Http;
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
+
+const OPENAI_API_URL: &str = "https://api.openai.com/v1/chat/completions";
+pub const DEFAULT_MODEL: &str = "gpt-4o";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Message {
+    pub role: String,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChatRequest {
+    pub model: String,
+    pub messages: Vec<Message>,
+    pub response_format: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChatResponse {
+    pub choices: Vec<Choice>,
+    pub usage: ChatUsage,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Choice {
+    pub message: Message,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChatUsage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+}
+
+pub struct OpenAiClient {
+    http: Http,
+    api_key: String,
+    model: String,
+}
+
+impl OpenAiClient {
+    pub fn new(http: Http, api_key: String, model: Option<String>) -> Self {
+        Self {
+            http,
+            api_key,
+            model: model.unwrap_or_else(|| DEFAULT_MODEL.to_string()),
+        }
+    }
+
+    async fn chat(
+        &self,
+        system_prompt: &str,
+        user_message: &str,
+        response_format: serde_json::Value,
+    ) -> Result<(String, Usage), color_eyre::eyre::Error> {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", self.api_key))?,
+        );
+
+        let request = ChatRequest {
+            model: self.model.clone(),
+            messages: vec![
+                Message {
+                    role: "system".to_string(),
+                    content: system_prompt.to_string(),
+                },
+                Message {
+                    role: "user".to_string(),
+                    content: user_message.to_string(),
+                },
+            ],
+            response_format,
+        };
+
+        let response = self
+            .http
+            .send(|client| {
+                client
+                    .post(OPENAI_API_URL)
+                    .headers(headers.clone())
+                    .json(&request)
+            })
+            .await?
+            .error_for_status()?;
+
+        let chat_response: ChatResponse = response.json().await?;
+        let text = chat_response
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message.content)
+            .ok_or_else(|| color_eyre::eyre::eyre!("OpenAI returned no choices"))?;
+
+        Ok((
+            text,
+            Usage {
+                input_tokens: chat_response.usage.prompt_tokens,
+                output_tokens: chat_response.usage.completion_tokens,
+            },
+        ))
+    }
+}
+
+#[async_trait]
+impl LlmProvider for OpenAiClient {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn send_structured(
+        &self,
+        system_prompt: &str,
+        user_message: &str,
+        name: &str,
+        schema: &serde_json::Value,
+    ) -> Result<(serde_json::Value, Usage), color_eyre::eyre::Error> {
+        let response_format = serde_json::json!({
+            "type": "json_schema",
+            "json_schema": {"name": name, "schema": schema, "strict": true},
+        });
+        let (text, usage) = self
+            .chat(system_prompt, user_message, response_format)
+            .await?;
+        let value = serde_json::from_str(&text)
+            .map_err(|e| color_eyre::eyre::eyre!("OpenAI returned invalid JSON: {}", e))?;
+        Ok((value, usage))
+    }
+}
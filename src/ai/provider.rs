@@ -0,0 +1,81 @@
+use super::auth;
+use super::claude::ClaudeClient;
+use super::ollama::OllamaClient;
+use super::openai::OpenAiClient;
+use crate::http::Http;
+use crate::ProjectConfig;
+use async_trait::async_trait;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Usage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+}
+
+#[async_trait]
+pub trait LlmProvider: Send + Sync {
+    /// Model used for requests, also used to look up token prices
+    fn model(&self) -> &str;
+
+    /// Ask for a response matching the JSON `schema`, `name` identifies the
+    /// kind of output for providers that need one
+    async fn send_structured(
+        &self,
+        system_prompt: &str,
+        user_message: &str,
+        name: &str,
+        schema: &serde_json::Value,
+    ) -> Result<(serde_json::Value, Usage), color_eyre::eyre::Error>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    #[default]
+    Anthropic,
+    #[value(name = "openai")]
+    OpenAi,
+    Ollama,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AiConfig {
+    #[serde(default)]
+    pub provider: ProviderKind,
+    /// Model to use, defaults to the provider's default model
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Base URL of the provider's API, only used by Ollama
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// API key used when neither the OS keyring nor the environment has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+/// Client for the configured provider, using the project's network settings
+pub fn new_provider(
+    config: &ProjectConfig,
+) -> Result<Box<dyn LlmProvider>, color_eyre::eyre::Error> {
+    let http = Http::new(config)?;
+    let config = &config.ai;
+    Ok(match config.provider {
+        ProviderKind::Anthropic => Box::new(ClaudeClient::new(
+            http,
+            auth::api_key(config)?,
+            config.model.clone(),
+        )),
+        ProviderKind::OpenAi => Box::new(OpenAiClient::new(
+            http,
+            auth::api_key(config)?,
+            config.model.clone(),
+        )),
+        ProviderKind::Ollama => Box::new(OllamaClient::new(
+            http,
+            config.base_url.clone(),
+            config.model.clone(),
+        )),
+    })
+}
@@ -0,0 +1,310 @@
+use crate::ai::provider::{self, LlmProvider, Usage};
+use crate::metadata::InitializrMetadata;
+use crate::output::{self, OutputFormat};
+use crate::project::default_token_price;
+use crate::{cache, progress, ProjectConfig};
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+const DEFAULT_PROMPT_TEMPLATE: &str = "You are an expert in Spring Boot applications. Your task is to analyze a PRD \
+    (Product Requirements Document) and suggest the most appropriate Spring Boot dependencies from the available \
+    options. Here is the list of available dependencies with their descriptions:\n\n{dependencies}\n\nAnalyze the \
+    following PRD. Only include dependencies that are directly relevant to the requirements.";
+
+/// Assemble the system prompt from the configured (or built-in) template and
+/// append the instructions describing the expected response format
+fn build_system_prompt(
+    config: &ProjectConfig,
+    metadata: &InitializrMetadata,
+    instructions: &str,
+) -> Result<String> {
+    let template = config
+        .prompt_template
+        .as_deref()
+        .unwrap_or(DEFAULT_PROMPT_TEMPLATE);
+    let dependencies = serde_json::to_string_pretty(&metadata.dependencies.values)?;
+
+    Ok(format!(
+        "{}\n\n{}",
+        template.replace("{dependencies}", &dependencies),
+        instructions
+    ))
+}
+
+pub fn estimated_cost(config: &ProjectConfig, model: &str, usage: &Usage) -> Option<f64> {
+    config
+        .token_prices
+        .get(model)
+        .copied()
+        .or_else(|| default_token_price(model))
+        .map(|price| price.cost(usage))
+}
+
+pub fn report_usage(config: &ProjectConfig, model: &str, usage: &Usage) {
+    eprintln!(
+        "Tokens used: {} input, {} output",
+        usage.input_tokens, usage.output_tokens
+    );
+    if let Some(cost) = estimated_cost(config, model, usage) {
+        eprintln!("Estimated cost: ${:.4}", cost);
+    }
+}
+
+/// A dependency recommended by the AI provider
+#[derive(Serialize, Deserialize)]
+pub struct DependencySuggestion {
+    pub id: String,
+    pub reason: String,
+    pub confidence: f64,
+}
+
+#[derive(Deserialize)]
+struct SuggestionList {
+    dependencies: Vec<DependencySuggestion>,
+}
+
+/// JSON schema for a list of suggestions, restricted to known dependency IDs
+fn suggestion_schema(metadata: &InitializrMetadata) -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "dependencies": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "enum": metadata.dependency_ids(),
+                            "description": "Dependency ID",
+                        },
+                        "reason": {
+                            "type": "string",
+                            "description": "One sentence on why the application needs it",
+                        },
+                        "confidence": {
+                            "type": "number",
+                            "description": "Confidence between 0 and 1",
+                        },
+                    },
+                    "required": ["id", "reason", "confidence"],
+                    "additionalProperties": false,
+                },
+            },
+        },
+        "required": ["dependencies"],
+        "additionalProperties": false,
+    })
+}
+
+/// Suggests dependencies for a PRD with the configured AI provider
+pub struct Suggester<'a> {
+    config: &'a ProjectConfig,
+    provider: Box<dyn LlmProvider>,
+}
+
+impl<'a> Suggester<'a> {
+    pub fn new(config: &'a ProjectConfig) -> Result<Self> {
+        Ok(Self {
+            config,
+            provider: provider::new_provider(config)?,
+        })
+    }
+
+    /// Model used for requests, also used to look up token prices
+    pub fn model(&self) -> &str {
+        self.provider.model()
+    }
+
+    /// Ask which dependencies the PRD calls for, returning the suggestions
+    /// along with the token usage
+    pub async fn suggest(
+        &self,
+        metadata: &InitializrMetadata,
+        prd_content: &str,
+        quiet: bool,
+        no_cache: bool,
+    ) -> Result<(Vec<DependencySuggestion>, Usage)> {
+        // Create a system prompt that includes the dependencies data
+        let system_prompt = build_system_prompt(
+            self.config,
+            metadata,
+            "Recommend the dependencies this application needs, with a short reason and a confidence \
+            between 0 and 1 for each.",
+        )?;
+
+        // An unchanged PRD gets the same suggestions without another API call
+        let key = cache::suggestion_key(prd_content, metadata, self.model());
+        if !no_cache {
+            if let Some(value) = cache::cached_suggestions(&key)? {
+                if let Ok(list) = serde_json::from_value::<SuggestionList>(value) {
+                    if !quiet {
+                        eprintln!("Using cached suggestions, pass --no-cache to request new ones");
+                    }
+                    return Ok((list.dependencies, Usage::default()));
+                }
+            }
+        }
+
+        let spinner = progress::spinner("Asking for dependency suggestions...", quiet)?;
+        let (value, usage) = self
+            .provider
+            .send_structured(
+                &system_prompt,
+                prd_content,
+                "suggest_dependencies",
+                &suggestion_schema(metadata),
+            )
+            .await?;
+        spinner.finish_and_clear();
+
+        let list: SuggestionList = serde_json::from_value(value.clone())
+            .map_err(|e| color_eyre::eyre::eyre!("Unexpected suggestion format: {}", e))?;
+        cache::store_suggestions(&key, &value)?;
+        Ok((list.dependencies, usage))
+    }
+}
+
+pub async fn suggest_dependencies(
+    config: &ProjectConfig,
+    prd_path: &str,
+    quiet: bool,
+    no_cache: bool,
+    output: OutputFormat,
+) -> Result<()> {
+    // Read the PRD file
+    let prd_content = fs::read_to_string(prd_path)?;
+
+    // Load the dependencies metadata
+    let deps = cache::metadata(config, false).await?;
+
+    // Get dependency suggestions
+    let suggester = Suggester::new(config)?;
+    let model = suggester.model().to_string();
+    let (suggestions, usage) = suggester
+        .suggest(&deps, &prd_content, quiet, no_cache)
+        .await?;
+    if output.is_json() {
+        return output::print_json(&output::Suggestion {
+            estimated_cost: estimated_cost(config, &model, &usage),
+            model,
+            suggestions,
+            usage,
+        });
+    }
+
+    print_suggestions(&suggestions);
+
+    if !quiet {
+        report_usage(config, &model, &usage);
+    }
+
+    Ok(())
+}
+
+pub fn print_suggestions(suggestions: &[DependencySuggestion]) {
+    println!("{:<30} {:<10} Reason", "ID", "Confidence");
+    println!("{:-<120}", "");
+    for suggestion in suggestions {
+        let reason = textwrap::fill(&suggestion.reason, 78);
+        let mut lines = reason.lines();
+        println!(
+            "{:<30} {:<10.2} {}",
+            suggestion.id,
+            suggestion.confidence,
+            lines.next().unwrap_or_default()
+        );
+        for line in lines {
+            println!("{:<41} {}", "", line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata() -> InitializrMetadata {
+        serde_json::from_value(serde_json::json!({
+            "dependencies": {
+                "values": [{
+                    "name": "Web",
+                    "values": [{"id": "web", "name": "Spring Web", "description": "Build web apps"}]
+                }]
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn custom_template_fills_dependencies() {
+        let metadata = metadata();
+        let config = ProjectConfig {
+            prompt_template: Some(
+                "We use Postgres. Pick from:\n{dependencies}\nThanks".to_string(),
+            ),
+            ..ProjectConfig::default()
+        };
+        let dependencies = serde_json::to_string_pretty(&metadata.dependencies.values).unwrap();
+
+        let prompt = build_system_prompt(&config, &metadata, "Respond with JSON").unwrap();
+
+        assert_eq!(
+            prompt,
+            format!(
+                "We use Postgres. Pick from:\n{}\nThanks\n\nRespond with JSON",
+                dependencies
+            )
+        );
+        assert!(prompt.contains("\"id\": \"web\""));
+        assert!(!prompt.contains("{dependencies}"));
+    }
+
+    #[test]
+    fn default_template_without_config() {
+        let prompt =
+            build_system_prompt(&ProjectConfig::default(), &metadata(), "Respond").unwrap();
+        assert!(prompt.starts_with("You are an expert in Spring Boot applications."));
+        assert!(prompt.contains("\"id\": \"web\""));
+        assert!(prompt.ends_with("\n\nRespond"));
+    }
+
+    #[test]
+    fn usage_and_cost_from_response() {
+        let response: crate::ai::claude::ClaudeResponse =
+            serde_json::from_value(serde_json::json!({
+                "content": [{"type": "tool_use", "input": {"dependencies": []}}],
+                "usage": {"input_tokens": 2000, "output_tokens": 400}
+            }))
+            .unwrap();
+        assert_eq!(response.usage.input_tokens, 2000);
+        assert_eq!(response.usage.output_tokens, 400);
+
+        let config = ProjectConfig::default();
+        let cost =
+            estimated_cost(&config, crate::ai::claude::DEFAULT_MODEL, &response.usage).unwrap();
+        // 2000 * $3 + 400 * $15 per million tokens
+        assert!((cost - 0.012).abs() < 1e-9);
+
+        assert!(estimated_cost(&config, "unknown-model", &response.usage).is_none());
+    }
+
+    #[test]
+    fn configured_price_overrides_default() {
+        let mut config = ProjectConfig::default();
+        config.token_prices.insert(
+            "custom-model".to_string(),
+            crate::TokenPrice {
+                input: 1.0,
+                output: 2.0,
+            },
+        );
+        let usage = Usage {
+            input_tokens: 1_000_000,
+            output_tokens: 500_000,
+        };
+        let cost = estimated_cost(&config, "custom-model", &usage).unwrap();
+        assert!((cost - 2.0).abs() < 1e-9);
+    }
+}
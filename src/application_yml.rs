@@ -0,0 +1,196 @@
+use crate::profiles::Environment;
+use crate::{config, ProjectConfig};
+use color_eyre::eyre::Result;
+use serde_json::{json, Value};
+use std::fs;
+
+pub const POSTGRES_PORT: u16 = 5432;
+pub const MYSQL_PORT: u16 = 3306;
+pub const MONGODB_PORT: u16 = 27017;
+pub const REDIS_PORT: u16 = 6379;
+pub const KAFKA_PORT: u16 = 9092;
+pub const RABBITMQ_PORT: u16 = 5672;
+
+/// Database user and password used in the datasource placeholders
+pub const DB_USER: &str = "app";
+pub const DB_PASSWORD: &str = "secret";
+
+fn datasource(config: &ProjectConfig, scheme: &str, port: u16) -> Value {
+    json!({"spring": {"datasource": {
+        "url": format!("${{DB_URL:jdbc:{}://localhost:{}/{}}}", scheme, port, config.app_name),
+        "username": format!("${{DB_USERNAME:{}}}", DB_USER),
+        "password": format!("${{DB_PASSWORD:{}}}", DB_PASSWORD),
+    }}})
+}
+
+/// Settings for one dependency, `None` for dependencies that need none
+fn settings(config: &ProjectConfig, id: &str) -> Option<Value> {
+    Some(match id {
+        "web" | "webflux" => json!({"server": {"port": 8080}}),
+        "postgresql" => datasource(config, "postgresql", POSTGRES_PORT),
+        "mysql" => datasource(config, "mysql", MYSQL_PORT),
+        "mariadb" => datasource(config, "mariadb", MYSQL_PORT),
+        "h2" => json!({"spring": {"h2": {"console": {"enabled": true}}}}),
+        "data-jpa" => json!({"spring": {"jpa": {
+            "hibernate": {"ddl-auto": "update"},
+            "open-in-view": false,
+        }}}),
+        "data-mongodb" => json!({"spring": {"data": {"mongodb": {
+            "uri": format!(
+                "${{MONGODB_URI:mongodb://localhost:{}/{}}}",
+                MONGODB_PORT, config.app_name
+            ),
+        }}}}),
+        "data-redis" | "data-redis-reactive" => json!({"spring": {"data": {"redis": {
+            "host": "${REDIS_HOST:localhost}",
+            "port": REDIS_PORT,
+        }}}}),
+        "kafka" | "kafka-streams" => json!({"spring": {"kafka": {
+            "bootstrap-servers": format!("${{KAFKA_BOOTSTRAP_SERVERS:localhost:{}}}", KAFKA_PORT),
+        }}}),
+        "amqp" => json!({"spring": {"rabbitmq": {
+            "host": "${RABBITMQ_HOST:localhost}",
+            "port": RABBITMQ_PORT,
+        }}}),
+        "actuator" => json!({"management": {
+            "endpoints": {"web": {"exposure": {"include": "health,info,metrics"}}},
+            "endpoint": {"health": {"probes": {"enabled": true}}},
+        }}),
+        _ => return None,
+    })
+}
+
+/// Default settings for the selected dependencies
+pub fn defaults(config: &ProjectConfig, dependencies: &[String]) -> Value {
+    let mut value = json!({"spring": {"application": {"name": config.app_name}}});
+    if config.env_profiles && !config.build_tool.is_gradle() {
+        // Filtered by the Boot parent from the Maven profile picked at build time
+        config::merge(
+            &mut value,
+            json!({"spring": {"profiles": {"active": "@spring.profiles.active@"}}}),
+        );
+    }
+    for id in dependencies {
+        if let Some(settings) = settings(config, id) {
+            config::merge(&mut value, settings);
+        }
+    }
+    value
+}
+
+fn render(value: &Value, indent: usize, out: &mut String) {
+    let Value::Object(map) = value else {
+        return;
+    };
+    for (key, child) in map {
+        match child {
+            Value::Object(_) => {
+                out.push_str(&format!("{:indent$}{}:\n", "", key, indent = indent));
+                render(child, indent + 2, out);
+            }
+            // JSON strings are valid double-quoted YAML scalars
+            scalar => out.push_str(&format!(
+                "{:indent$}{}: {}\n",
+                "",
+                key,
+                scalar,
+                indent = indent
+            )),
+        }
+    }
+}
+
+/// Settings for one environment's application-{env}.yml
+fn environment_settings(config: &ProjectConfig, environment: Environment) -> Value {
+    match environment {
+        Environment::Dev => json!({
+            "logging": {"level": {config.package_name.clone(): "DEBUG"}},
+            "server": {"error": {"include-message": "always", "include-stacktrace": "on_param"}},
+        }),
+        Environment::Prod => json!({
+            "logging": {"level": {"root": "INFO"}},
+            "server": {"shutdown": "graceful", "error": {"include-stacktrace": "never"}},
+        }),
+    }
+}
+
+/// Write application-dev.yml and application-prod.yml, leaving existing ones alone
+pub fn write_environments(config: &ProjectConfig) -> Result<()> {
+    let resources = config
+        .module_dir()
+        .join("src")
+        .join("main")
+        .join("resources");
+    fs::create_dir_all(&resources)?;
+    for environment in Environment::ALL {
+        let path = resources.join(format!("application-{}.yml", environment.as_str()));
+        if path.exists() {
+            continue;
+        }
+        let mut yaml = String::new();
+        render(&environment_settings(config, environment), 0, &mut yaml);
+        fs::write(&path, yaml)?;
+        eprintln!(
+            "Wrote {}",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        );
+    }
+    Ok(())
+}
+
+/// Dependencies that get settings in application.yml
+pub fn configured(config: &ProjectConfig, dependencies: &[String]) -> Vec<String> {
+    dependencies
+        .iter()
+        .filter(|id| settings(config, id).is_some())
+        .cloned()
+        .collect()
+}
+
+/// Write application.yml with defaults for the selected dependencies in
+/// place of the scaffold's application.properties. An existing
+/// application.yml or customized properties file is left alone.
+pub fn write(config: &ProjectConfig, dependencies: &[String]) -> Result<()> {
+    let resources = config
+        .module_dir()
+        .join("src")
+        .join("main")
+        .join("resources");
+    let yml_path = resources.join("application.yml");
+    let properties_path = resources.join("application.properties");
+    if yml_path.exists() {
+        return Ok(());
+    }
+
+    // start.spring.io only puts the application name in the properties file
+    let scaffold_properties = format!("spring.application.name={}", config.app_name);
+    if let Ok(properties) = fs::read_to_string(&properties_path) {
+        if properties.trim() != scaffold_properties {
+            eprintln!("Keeping customized application.properties, not writing application.yml");
+            if config.env_profiles
+                && !config.build_tool.is_gradle()
+                && !properties.contains("spring.profiles.active")
+            {
+                let separator = if properties.ends_with('\n') { "" } else { "\n" };
+                fs::write(
+                    &properties_path,
+                    format!(
+                        "{}{}spring.profiles.active=@spring.profiles.active@\n",
+                        properties, separator
+                    ),
+                )?;
+            }
+            return Ok(());
+        }
+    }
+
+    let mut yaml = String::new();
+    render(&defaults(config, dependencies), 0, &mut yaml);
+    fs::create_dir_all(&resources)?;
+    fs::write(&yml_path, yaml)?;
+    if properties_path.exists() {
+        fs::remove_file(&properties_path)?;
+    }
+    eprintln!("Wrote application.yml");
+    Ok(())
+}
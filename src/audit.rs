@@ -0,0 +1,349 @@
+use crate::http::Http;
+use crate::output::{self, OutputFormat, Vulnerability};
+use crate::pom::{self, Pom};
+use crate::{progress, wrapper, ProjectConfig};
+use color_eyre::eyre::Result;
+use serde::Deserialize;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::process::Command;
+
+/// Plugin added to the root pom to write target/bom.json
+const CYCLONEDX_PLUGIN: &str = "org.cyclonedx:cyclonedx-maven-plugin:2.9.1";
+
+const OSV_URL: &str = "https://api.osv.dev/v1";
+
+/// OSV accepts at most this many queries per batch
+const OSV_BATCH: usize = 1000;
+
+/// Most severe first
+const SEVERITIES: [&str; 5] = ["CRITICAL", "HIGH", "MODERATE", "LOW", "UNKNOWN"];
+
+#[derive(Deserialize)]
+struct Sbom {
+    metadata: SbomMetadata,
+    #[serde(default)]
+    components: Vec<Component>,
+    #[serde(default)]
+    dependencies: Vec<DependencyNode>,
+}
+
+#[derive(Deserialize)]
+struct SbomMetadata {
+    component: Component,
+}
+
+#[derive(Deserialize)]
+struct Component {
+    #[serde(rename = "bom-ref")]
+    bom_ref: String,
+    #[serde(default)]
+    group: Option<String>,
+    name: String,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    purl: Option<String>,
+}
+
+impl Component {
+    fn label(&self) -> String {
+        match &self.group {
+            Some(group) => format!("{}:{}", group, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct DependencyNode {
+    #[serde(rename = "ref")]
+    reference: String,
+    #[serde(default, rename = "dependsOn")]
+    depends_on: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct BatchResponse {
+    results: Vec<BatchResult>,
+}
+
+#[derive(Deserialize)]
+struct BatchResult {
+    #[serde(default)]
+    vulns: Vec<VulnId>,
+}
+
+#[derive(Deserialize)]
+struct VulnId {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct OsvVulnerability {
+    id: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    database_specific: Option<serde_json::Value>,
+}
+
+impl OsvVulnerability {
+    /// GitHub advisories rate severity in database_specific, other sources
+    /// only carry a CVSS vector
+    fn severity(&self) -> String {
+        let severity = self
+            .database_specific
+            .as_ref()
+            .and_then(|specific| specific["severity"].as_str())
+            .map(str::to_uppercase);
+        match severity.as_deref() {
+            Some("MEDIUM") => "MODERATE".to_string(),
+            Some(severity) if SEVERITIES.contains(&severity) => severity.to_string(),
+            _ => "UNKNOWN".to_string(),
+        }
+    }
+}
+
+/// Add the CycloneDX plugin if needed and write target/bom.json
+fn generate_sbom(config: &ProjectConfig, quiet: bool) -> Result<Sbom> {
+    let pom_path = config.app_dir().join("pom.xml");
+    let mut pom = Pom::load(&pom_path)?;
+    let plugin = pom::Coordinates::parse(CYCLONEDX_PLUGIN)?;
+    let declared = pom
+        .coordinates(&["build", "plugins"], "plugin")?
+        .iter()
+        .any(|declared| {
+            declared.group_id == plugin.group_id && declared.artifact_id == plugin.artifact_id
+        });
+    if !declared {
+        pom.upsert_plugin(&plugin)?;
+        pom.set_plugin_value(
+            &plugin.group_id,
+            &plugin.artifact_id,
+            &["configuration", "outputFormat"],
+            "json",
+        )?;
+        pom.save(&pom_path)?;
+        eprintln!("Adding plugin: {}", CYCLONEDX_PLUGIN);
+    }
+
+    let spinner = progress::spinner("Writing CycloneDX SBOM with Maven...", quiet)?;
+    let program = wrapper::maven(config);
+    let output = Command::new(&program)
+        .args(["-q", "cyclonedx:makeAggregateBom"])
+        .current_dir(config.app_dir())
+        .output()
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to run {}: {}", program, e))?;
+    spinner.finish_and_clear();
+    if !output.status.success() {
+        eprint!("{}", String::from_utf8_lossy(&output.stdout));
+        return Err(color_eyre::eyre::eyre!("Failed to write the SBOM"));
+    }
+
+    let path = config.app_dir().join("target").join("bom.json");
+    let content = fs::read_to_string(&path)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| color_eyre::eyre::eyre!("Invalid SBOM {}: {}", path.display(), e))
+}
+
+/// Shortest chain of components from the project to `target`
+fn dependency_path(sbom: &Sbom, target: &str) -> Vec<String> {
+    let graph: HashMap<&str, &Vec<String>> = sbom
+        .dependencies
+        .iter()
+        .map(|node| (node.reference.as_str(), &node.depends_on))
+        .collect();
+    let root = sbom.metadata.component.bom_ref.as_str();
+    let mut previous: HashMap<&str, &str> = HashMap::new();
+    let mut queue = VecDeque::from([root]);
+    while let Some(current) = queue.pop_front() {
+        if current == target {
+            break;
+        }
+        for next in graph.get(current).into_iter().flat_map(|next| next.iter()) {
+            if next != root && !previous.contains_key(next.as_str()) {
+                previous.insert(next, current);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    let names: HashMap<&str, String> = sbom
+        .components
+        .iter()
+        .chain([&sbom.metadata.component])
+        .map(|component| (component.bom_ref.as_str(), component.label()))
+        .collect();
+    let mut path = vec![target];
+    while let Some(&before) = previous.get(path[path.len() - 1]) {
+        path.push(before);
+    }
+    path.iter()
+        .rev()
+        .map(|reference| {
+            names
+                .get(reference)
+                .cloned()
+                .unwrap_or(reference.to_string())
+        })
+        .collect()
+}
+
+/// Known vulnerabilities of each component, by index in `components`
+async fn query_osv(http: &Http, components: &[&Component]) -> Result<Vec<Vec<String>>> {
+    let mut ids = Vec::new();
+    for batch in components.chunks(OSV_BATCH) {
+        let queries: Vec<serde_json::Value> = batch
+            .iter()
+            .map(|component| serde_json::json!({"package": {"purl": component.purl}}))
+            .collect();
+        let url = format!("{}/querybatch", OSV_URL);
+        let response: BatchResponse = http
+            .send(|client| {
+                client
+                    .post(&url)
+                    .json(&serde_json::json!({ "queries": queries }))
+            })
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to query OSV: {}", e))?
+            .json()
+            .await?;
+        ids.extend(
+            response
+                .results
+                .into_iter()
+                .map(|result| result.vulns.into_iter().map(|vuln| vuln.id).collect()),
+        );
+    }
+    Ok(ids)
+}
+
+async fn fetch_vulnerability(http: &Http, id: &str) -> Result<OsvVulnerability> {
+    let url = format!("{}/vulns/{}", OSV_URL, id);
+    Ok(http
+        .send(|client| client.get(&url))
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to fetch {}: {}", id, e))?
+        .json()
+        .await?)
+}
+
+/// Write an SBOM of the Maven project and check its components against OSV
+pub async fn audit(config: &ProjectConfig, quiet: bool, format: OutputFormat) -> Result<()> {
+    if config.build_tool.is_gradle() {
+        return Err(color_eyre::eyre::eyre!(
+            "Auditing is only supported for Maven projects"
+        ));
+    }
+    if !config.build_file().exists() {
+        return Err(color_eyre::eyre::eyre!(
+            "No build file found at {}, run init first",
+            config.build_file().display()
+        ));
+    }
+
+    let sbom = generate_sbom(config, quiet || format.is_json())?;
+    let components: Vec<&Component> = sbom
+        .components
+        .iter()
+        .filter(|component| component.purl.is_some())
+        .collect();
+
+    let http = Http::new(config)?;
+    let spinner = progress::spinner(
+        &format!("Checking {} components against OSV...", components.len()),
+        quiet || format.is_json(),
+    )?;
+    let found = query_osv(&http, &components).await?;
+    let mut details: HashMap<String, OsvVulnerability> = HashMap::new();
+    let mut vulnerabilities = Vec::new();
+    for (component, ids) in components.iter().zip(found) {
+        for id in ids {
+            if !details.contains_key(&id) {
+                details.insert(id.clone(), fetch_vulnerability(&http, &id).await?);
+            }
+            let vulnerability = &details[&id];
+            vulnerabilities.push(Vulnerability {
+                id: vulnerability.id.clone(),
+                aliases: vulnerability.aliases.clone(),
+                severity: vulnerability.severity(),
+                summary: vulnerability.summary.clone().unwrap_or_default(),
+                component: format!(
+                    "{}@{}",
+                    component.label(),
+                    component.version.as_deref().unwrap_or("?")
+                ),
+                path: dependency_path(&sbom, &component.bom_ref),
+            });
+        }
+    }
+    spinner.finish_and_clear();
+    vulnerabilities.sort_by_key(|vulnerability| {
+        SEVERITIES
+            .iter()
+            .position(|severity| *severity == vulnerability.severity)
+    });
+
+    let count = vulnerabilities.len();
+    let report = output::AuditReport {
+        sbom: config
+            .app_dir()
+            .join("target")
+            .join("bom.json")
+            .display()
+            .to_string(),
+        components: components.len(),
+        vulnerabilities,
+    };
+    if format.is_json() {
+        output::print_json(&report)?;
+    } else {
+        for severity in SEVERITIES {
+            let group: Vec<&Vulnerability> = report
+                .vulnerabilities
+                .iter()
+                .filter(|vulnerability| vulnerability.severity == severity)
+                .collect();
+            if group.is_empty() {
+                continue;
+            }
+            println!("{} ({})", severity, group.len());
+            for vulnerability in group {
+                let cve = vulnerability
+                    .aliases
+                    .iter()
+                    .find(|alias| alias.starts_with("CVE-"));
+                match cve {
+                    Some(cve) => println!(
+                        "  {} ({}) in {}",
+                        cve, vulnerability.id, vulnerability.component
+                    ),
+                    None => println!("  {} in {}", vulnerability.id, vulnerability.component),
+                }
+                if !vulnerability.summary.is_empty() {
+                    println!("    {}", vulnerability.summary);
+                }
+                println!("    via {}", vulnerability.path.join(" > "));
+            }
+            println!();
+        }
+        println!(
+            "{} vulnerabilities in {} components, SBOM at {}",
+            count, report.components, report.sbom
+        );
+    }
+
+    if count > 0 {
+        return Err(color_eyre::eyre::eyre!(
+            "Found {} known vulnerabilities",
+            count
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use crate::metadata::InitializrMetadata;
+use crate::{initializr, ProjectConfig};
+use color_eyre::eyre::Result;
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Root directory for everything spring-init caches, e.g. ~/.cache/spring-init
+pub fn cache_dir() -> Result<PathBuf> {
+    dirs::cache_dir()
+        .map(|dir| dir.join("spring-init"))
+        .ok_or_else(|| color_eyre::eyre::eyre!("Could not determine cache directory"))
+}
+
+fn is_fresh(path: &Path, ttl: Duration) -> bool {
+    fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age < ttl)
+}
+
+fn read_cached<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Cached metadata file, one per Initializr instance
+fn metadata_path(config: &ProjectConfig) -> Result<PathBuf> {
+    let url = config.initializr_url.trim_end_matches('/');
+    let name = if url == initializr::DEFAULT_URL {
+        "metadata.json".to_string()
+    } else {
+        let host: String = url
+            .split_once("://")
+            .map_or(url, |(_, rest)| rest)
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        format!("metadata-{}.json", host)
+    };
+    Ok(cache_dir()?.join(name))
+}
+
+/// Load the Initializr client metadata, downloading it when the cached copy
+/// is missing, older than `metadata_cache_ttl_hours`, or `refresh` is set
+pub async fn metadata(config: &ProjectConfig, refresh: bool) -> Result<InitializrMetadata> {
+    let path = metadata_path(config)?;
+    let ttl = Duration::from_secs(config.metadata_cache_ttl_hours * 60 * 60);
+
+    if !refresh && is_fresh(&path, ttl) {
+        if let Some(metadata) = read_cached(&path) {
+            return Ok(metadata);
+        }
+    }
+
+    eprintln!("Fetching metadata from {}...", config.initializr_url);
+    match initializr::Client::new(config)?.metadata().await {
+        Ok(metadata) => {
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(&path, serde_json::to_string(&metadata)?)?;
+            Ok(metadata)
+        }
+        Err(e) => match read_cached(&path) {
+            // A stale copy beats failing outright
+            Some(metadata) => {
+                eprintln!("Warning: {}, using cached metadata", e);
+                Ok(metadata)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+/// The cached metadata regardless of its age, without going to the network
+pub fn cached_metadata(config: &ProjectConfig) -> Result<Option<InitializrMetadata>> {
+    Ok(read_cached(&metadata_path(config)?))
+}
+
+/// FNV-1a, stable across Rust releases unlike `DefaultHasher`
+fn fnv1a(bytes: impl Iterator<Item = u8>) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Hex digest of `parts`
+fn hash(parts: &[&str]) -> String {
+    fnv1a(parts.iter().flat_map(|part| part.bytes().chain([0])))
+}
+
+/// Hex digest of a file's content, e.g. a starter zip
+pub fn digest(bytes: &[u8]) -> String {
+    fnv1a(bytes.iter().copied())
+}
+
+/// Cache key for AI suggestions, changes whenever the PRD, the dependency
+/// metadata, or the model does
+pub fn suggestion_key(prd: &str, metadata: &InitializrMetadata, model: &str) -> String {
+    let metadata = serde_json::to_string(metadata).unwrap_or_default();
+    hash(&[prd, &metadata, model])
+}
+
+fn suggestion_path(key: &str) -> Result<PathBuf> {
+    Ok(cache_dir()?
+        .join("suggestions")
+        .join(format!("{}.json", key)))
+}
+
+/// Suggestions previously stored under `key`, if any
+pub fn cached_suggestions(key: &str) -> Result<Option<serde_json::Value>> {
+    Ok(read_cached(&suggestion_path(key)?))
+}
+
+pub fn store_suggestions(key: &str, suggestions: &serde_json::Value) -> Result<()> {
+    let path = suggestion_path(key)?;
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(&path, serde_json::to_string(suggestions)?)?;
+    Ok(())
+}
+
+/// Starter zips are keyed by their URL, which holds every project parameter
+fn starter_path(url: &str) -> Result<PathBuf> {
+    Ok(cache_dir()?
+        .join("starters")
+        .join(format!("{}.zip", hash(&[url]))))
+}
+
+/// Starter zip previously downloaded from `url`, if any
+pub fn cached_starter(url: &str) -> Result<Option<PathBuf>> {
+    let path = starter_path(url)?;
+    Ok(path.exists().then_some(path))
+}
+
+/// Keep a copy of the starter zip downloaded from `url` for `init --offline`
+pub fn store_starter(url: &str, zip: &Path) -> Result<()> {
+    let path = starter_path(url)?;
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::copy(zip, &path)?;
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use crate::{docker, ProjectConfig};
+use clap::ValueEnum;
+use color_eyre::eyre::Result;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Target {
+    /// GitHub Actions, .github/workflows/build.yml
+    Github,
+    /// GitLab CI, .gitlab-ci.yml
+    Gitlab,
+}
+
+fn build_command(config: &ProjectConfig) -> &'static str {
+    if config.build_tool.is_gradle() {
+        "./gradlew build"
+    } else {
+        "./mvnw -B verify"
+    }
+}
+
+fn github(config: &ProjectConfig, publish: bool) -> String {
+    let cache = if config.build_tool.is_gradle() {
+        "gradle"
+    } else {
+        "maven"
+    };
+    let mut workflow = format!(
+        "name: Build
+
+on:
+  push:
+    branches: [main]
+  pull_request:
+
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/setup-java@v4
+        with:
+          distribution: temurin
+          java-version: '{java}'
+          cache: {cache}
+      - name: Build
+        run: {build}
+",
+        java = config.java_version,
+        build = build_command(config),
+    );
+    if publish {
+        workflow.push_str(
+            "
+  publish:
+    needs: build
+    if: github.event_name == 'push'
+    runs-on: ubuntu-latest
+    permissions:
+      contents: read
+      packages: write
+    steps:
+      - uses: actions/checkout@v4
+      - uses: docker/login-action@v3
+        with:
+          registry: ghcr.io
+          username: ${{ github.actor }}
+          password: ${{ secrets.GITHUB_TOKEN }}
+      - uses: docker/build-push-action@v6
+        with:
+          context: .
+          push: true
+          tags: ghcr.io/${{ github.repository }}:${{ github.sha }}
+",
+        );
+    }
+    workflow
+}
+
+fn gitlab(config: &ProjectConfig, publish: bool) -> String {
+    let (variables, cache) = if config.build_tool.is_gradle() {
+        ("GRADLE_USER_HOME: $CI_PROJECT_DIR/.gradle", ".gradle/")
+    } else {
+        (
+            "MAVEN_OPTS: -Dmaven.repo.local=$CI_PROJECT_DIR/.m2/repository",
+            ".m2/repository/",
+        )
+    };
+    let mut pipeline = format!(
+        "stages:
+  - build
+  - publish
+
+variables:
+  {variables}
+
+build:
+  stage: build
+  image: eclipse-temurin:{java}-jdk
+  cache:
+    key: $CI_COMMIT_REF_SLUG
+    paths:
+      - {cache}
+  script:
+    - {build}
+",
+        java = config.java_version,
+        build = build_command(config),
+    );
+    if publish {
+        pipeline.push_str(
+            "
+publish:
+  stage: publish
+  image: docker:27
+  services:
+    - docker:27-dind
+  rules:
+    - if: $CI_COMMIT_BRANCH == $CI_DEFAULT_BRANCH
+  script:
+    - docker login -u \"$CI_REGISTRY_USER\" -p \"$CI_REGISTRY_PASSWORD\" $CI_REGISTRY
+    - docker build -t $CI_REGISTRY_IMAGE:$CI_COMMIT_SHORT_SHA .
+    - docker push $CI_REGISTRY_IMAGE:$CI_COMMIT_SHORT_SHA
+",
+        );
+    }
+    pipeline
+}
+
+/// Write the CI configuration for `target` into the project, with a job
+/// publishing a container image built from the Dockerfile if `publish` is set
+pub fn write(
+    config: &ProjectConfig,
+    target: Target,
+    publish: bool,
+    force: bool,
+    dry_run: bool,
+) -> Result<()> {
+    if !config.build_file().exists() {
+        return Err(color_eyre::eyre::eyre!(
+            "No {} found, run init first",
+            config.build_file().display()
+        ));
+    }
+
+    let (path, content): (PathBuf, String) = match target {
+        Target::Github => (
+            config
+                .app_dir()
+                .join(".github")
+                .join("workflows")
+                .join("build.yml"),
+            github(config, publish),
+        ),
+        Target::Gitlab => (
+            config.app_dir().join(".gitlab-ci.yml"),
+            gitlab(config, publish),
+        ),
+    };
+    if dry_run {
+        println!("Would write {}", path.display());
+        return Ok(());
+    }
+    if path.exists() && !force {
+        return Err(color_eyre::eyre::eyre!(
+            "{} already exists, use --force to overwrite it",
+            path.display()
+        ));
+    }
+
+    if publish {
+        docker::write_dockerfile(config)?;
+    }
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(&path, content)?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
@@ -0,0 +1,268 @@
+use crate::application_yml::{
+    DB_PASSWORD, DB_USER, KAFKA_PORT, MONGODB_PORT, MYSQL_PORT, POSTGRES_PORT, RABBITMQ_PORT,
+    REDIS_PORT,
+};
+use crate::ProjectConfig;
+use color_eyre::eyre::Result;
+use std::fs;
+use std::path::Path;
+
+/// Host port of the first app in a workspace compose file
+const APP_PORT: u16 = 8080;
+
+/// A backing service started for a dependency
+struct Service {
+    /// Initializr dependency ID
+    dependency: &'static str,
+    /// Text identifying the dependency in pom.xml or build.gradle
+    artifact: &'static str,
+    name: &'static str,
+    image: &'static str,
+    ports: &'static [u16],
+    /// Environment variables, `{db}`, `{user}`, and `{password}` are replaced
+    /// with the values application.yml expects
+    environment: &'static [(&'static str, &'static str)],
+    /// Variables pointing an app container at the service, `{db}` is replaced
+    /// as above
+    connection: &'static [(&'static str, &'static str)],
+}
+
+const SERVICES: &[Service] = &[
+    Service {
+        dependency: "postgresql",
+        artifact: "org.postgresql",
+        name: "postgres",
+        image: "postgres:16",
+        ports: &[POSTGRES_PORT],
+        environment: &[
+            ("POSTGRES_DB", "{db}"),
+            ("POSTGRES_USER", "{user}"),
+            ("POSTGRES_PASSWORD", "{password}"),
+        ],
+        connection: &[("DB_URL", "jdbc:postgresql://postgres:5432/{db}")],
+    },
+    Service {
+        dependency: "mysql",
+        artifact: "mysql-connector-j",
+        name: "mysql",
+        image: "mysql:8.4",
+        ports: &[MYSQL_PORT],
+        environment: &[
+            ("MYSQL_DATABASE", "{db}"),
+            ("MYSQL_USER", "{user}"),
+            ("MYSQL_PASSWORD", "{password}"),
+            ("MYSQL_ROOT_PASSWORD", "{password}"),
+        ],
+        connection: &[("DB_URL", "jdbc:mysql://mysql:3306/{db}")],
+    },
+    Service {
+        dependency: "mariadb",
+        artifact: "mariadb-java-client",
+        name: "mariadb",
+        image: "mariadb:11",
+        ports: &[MYSQL_PORT],
+        environment: &[
+            ("MARIADB_DATABASE", "{db}"),
+            ("MARIADB_USER", "{user}"),
+            ("MARIADB_PASSWORD", "{password}"),
+            ("MARIADB_ROOT_PASSWORD", "{password}"),
+        ],
+        connection: &[("DB_URL", "jdbc:mariadb://mariadb:3306/{db}")],
+    },
+    Service {
+        dependency: "data-mongodb",
+        artifact: "spring-boot-starter-data-mongodb",
+        name: "mongodb",
+        image: "mongo:7",
+        ports: &[MONGODB_PORT],
+        environment: &[],
+        connection: &[("MONGODB_URI", "mongodb://mongodb:27017/{db}")],
+    },
+    Service {
+        dependency: "data-redis",
+        artifact: "spring-boot-starter-data-redis",
+        name: "redis",
+        image: "redis:7",
+        ports: &[REDIS_PORT],
+        environment: &[],
+        connection: &[("REDIS_HOST", "redis")],
+    },
+    Service {
+        dependency: "kafka",
+        artifact: "spring-kafka",
+        name: "kafka",
+        image: "apache/kafka:3.8.0",
+        ports: &[KAFKA_PORT],
+        environment: &[],
+        connection: &[("KAFKA_BOOTSTRAP_SERVERS", "kafka:9092")],
+    },
+    Service {
+        dependency: "amqp",
+        artifact: "spring-boot-starter-amqp",
+        name: "rabbitmq",
+        image: "rabbitmq:3-management",
+        ports: &[RABBITMQ_PORT, 15672],
+        environment: &[],
+        connection: &[("RABBITMQ_HOST", "rabbitmq")],
+    },
+];
+
+/// Render the backing services, `db` names the database they create
+/// Backing services for the dependencies declared in a build file
+fn backing_services(build_file: &str) -> Vec<&'static Service> {
+    SERVICES
+        .iter()
+        .filter(|service| build_file.contains(service.artifact))
+        .collect()
+}
+
+fn render(db: &str, services: &[&Service]) -> String {
+    let mut yaml = String::from("services:\n");
+    for service in services {
+        yaml.push_str(&format!(
+            "  {}:\n    image: {}\n",
+            service.name, service.image
+        ));
+        if !service.environment.is_empty() {
+            yaml.push_str("    environment:\n");
+            for (name, value) in service.environment {
+                let value = value
+                    .replace("{db}", db)
+                    .replace("{user}", DB_USER)
+                    .replace("{password}", DB_PASSWORD);
+                yaml.push_str(&format!("      {}: {}\n", name, value));
+            }
+        }
+        yaml.push_str("    ports:\n");
+        for port in service.ports {
+            yaml.push_str(&format!("      - \"{}:{}\"\n", port, port));
+        }
+    }
+    yaml
+}
+
+/// Write docker-compose.yml with a service for each backing service the
+/// project depends on
+pub fn write(config: &ProjectConfig, force: bool, dry_run: bool) -> Result<()> {
+    let build_file = config.build_file();
+    let content = fs::read_to_string(&build_file).map_err(|_| {
+        color_eyre::eyre::eyre!("No {} found, run init first", build_file.display())
+    })?;
+
+    let services = backing_services(&content);
+    if services.is_empty() {
+        println!("No dependencies with a known backing service, nothing to do");
+        return Ok(());
+    }
+
+    let path = config.app_dir().join("docker-compose.yml");
+    if dry_run {
+        println!("Would write {}", path.display());
+        for service in &services {
+            println!(
+                "  {} ({}) for {}",
+                service.name, service.image, service.dependency
+            );
+        }
+        return Ok(());
+    }
+    if path.exists() && !force {
+        return Err(color_eyre::eyre::eyre!(
+            "{} already exists, use --force to overwrite it",
+            path.display()
+        ));
+    }
+
+    fs::write(&path, render(&config.app_name, &services))?;
+    for service in &services {
+        println!(
+            "Added {} ({}) for {}",
+            service.name, service.image, service.dependency
+        );
+    }
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+/// Write docker-compose.yml next to the workspace's services, running each
+/// one from its Dockerfile on its own port from 8080 up, with one shared
+/// instance of every backing service they depend on
+pub fn write_workspace(
+    dir: &Path,
+    name: &str,
+    projects: &[ProjectConfig],
+    force: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let mut apps = Vec::new();
+    let mut shared: Vec<&Service> = Vec::new();
+    for project in projects {
+        let build_file = project.build_file();
+        let content = fs::read_to_string(&build_file).map_err(|_| {
+            color_eyre::eyre::eyre!(
+                "No {} found, run `workspace init` first",
+                build_file.display()
+            )
+        })?;
+        let services = backing_services(&content);
+        for service in &services {
+            if !shared.iter().any(|shared| shared.name == service.name) {
+                shared.push(service);
+            }
+        }
+        apps.push((project, services));
+    }
+
+    let path = dir.join("docker-compose.yml");
+    if dry_run {
+        println!("Would write {}", path.display());
+        for (port, (project, _)) in (APP_PORT..).zip(&apps) {
+            println!("  {} on port {}", project.app_name, port);
+        }
+        for service in &shared {
+            println!("  {} ({})", service.name, service.image);
+        }
+        return Ok(());
+    }
+    if path.exists() && !force {
+        return Err(color_eyre::eyre::eyre!(
+            "{} already exists, use --force to overwrite it",
+            path.display()
+        ));
+    }
+
+    let mut yaml = render(name, &shared);
+    for (port, (project, services)) in (APP_PORT..).zip(&apps) {
+        // Imported services can live outside projects_dir
+        let app_dir = project.app_dir();
+        let context = match app_dir.strip_prefix(dir) {
+            Ok(relative) => format!("./{}", relative.display()),
+            Err(_) => app_dir.display().to_string(),
+        };
+        yaml.push_str(&format!(
+            "  {}:\n    build: {}\n    ports:\n      - \"{}:8080\"\n",
+            project.app_name, context, port
+        ));
+        if services.is_empty() {
+            continue;
+        }
+        yaml.push_str("    environment:\n");
+        for service in services {
+            for (variable, value) in service.connection {
+                yaml.push_str(&format!(
+                    "      {}: {}\n",
+                    variable,
+                    value.replace("{db}", name)
+                ));
+            }
+        }
+        yaml.push_str("    depends_on:\n");
+        for service in services {
+            yaml.push_str(&format!("      - {}\n", service.name));
+        }
+    }
+
+    fs::write(&path, yaml)?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
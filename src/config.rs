@@ -0,0 +1,217 @@
+use crate::output::{self, OutputFormat};
+use crate::ProjectConfig;
+use color_eyre::eyre::Result;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Settings written by `config init`, everything else keeps its default
+const STARTER_KEYS: [&str; 8] = [
+    "boot_version",
+    "java_version",
+    "app_name",
+    "app_version",
+    "package_name",
+    "projects_dir",
+    "maven_plugins",
+    "include_deps",
+];
+
+/// File names looked up in each config directory, in order of preference
+const FILE_NAMES: [&str; 2] = ["config.toml", "config.json"];
+
+/// Directory holding the user-wide config, e.g. ~/.config/spring-init
+fn global_dir() -> Result<PathBuf> {
+    dirs::config_dir()
+        .map(|dir| dir.join("spring-init"))
+        .ok_or_else(|| color_eyre::eyre::eyre!("Could not determine config directory"))
+}
+
+fn find(dir: &Path) -> Option<PathBuf> {
+    FILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.exists())
+}
+
+/// Config file written by `config init` and `config set`, an existing file
+/// is preferred over a new config.toml
+fn target(global: bool) -> Result<PathBuf> {
+    let dir = if global {
+        global_dir()?
+    } else {
+        PathBuf::from(".")
+    };
+    Ok(find(&dir).unwrap_or_else(|| dir.join(FILE_NAMES[0])))
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+fn is_toml(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "toml")
+}
+
+fn read(path: &Path) -> Result<Value> {
+    let content = fs::read_to_string(path)?;
+    let value = if is_toml(path) {
+        toml::from_str(&content)
+            .map_err(|e| color_eyre::eyre::eyre!("Invalid {}: {}", path.display(), e))?
+    } else {
+        serde_json::from_str(&content)
+            .map_err(|e| color_eyre::eyre::eyre!("Invalid {}: {}", path.display(), e))?
+    };
+    Ok(value)
+}
+
+/// Remove null object members, TOML has no way to represent them
+fn strip_nulls(value: &mut Value) {
+    if let Value::Object(map) = value {
+        map.retain(|_, value| !value.is_null());
+        map.values_mut().for_each(strip_nulls);
+    }
+}
+
+fn write(path: &Path, value: &Value) -> Result<()> {
+    let content = if is_toml(path) {
+        let mut value = value.clone();
+        strip_nulls(&mut value);
+        toml::to_string_pretty(&value)?
+    } else {
+        serde_json::to_string_pretty(value)? + "\n"
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)?;
+    Ok(())
+}
+
+/// Recursively overlay `overlay` onto `base`, objects are merged key by key
+pub fn merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Existing config files, the global one first so the local one wins
+fn sources() -> Result<Vec<PathBuf>> {
+    Ok([find(&global_dir()?), find(Path::new("."))]
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+/// Defaults overlaid with each config file, or None when no file exists
+pub fn load() -> Result<Option<Value>> {
+    let sources = sources()?;
+    if sources.is_empty() {
+        return Ok(None);
+    }
+
+    let mut value = serde_json::to_value(ProjectConfig::default())?;
+    for path in sources {
+        merge(&mut value, read(&path)?);
+    }
+    Ok(Some(value))
+}
+
+/// Write a starter config to the project-local or global config file
+pub fn init(global: bool) -> Result<()> {
+    let path = target(global)?;
+    if path.exists() {
+        return Err(color_eyre::eyre::eyre!("{} already exists", path.display()));
+    }
+    let mut starter = serde_json::to_value(ProjectConfig::default())?;
+    if let Value::Object(map) = &mut starter {
+        map.retain(|key, _| STARTER_KEYS.contains(&key.as_str()));
+    }
+    write(&path, &starter)?;
+    println!("Created {}", path.display());
+    Ok(())
+}
+
+/// Print the effective config and the files it was loaded from
+pub fn show(output: OutputFormat) -> Result<()> {
+    let config = ProjectConfig::load_or_default()?;
+    if output.is_json() {
+        return output::print_json(&config);
+    }
+
+    let sources = sources()?;
+    if sources.is_empty() {
+        println!("# No config files found, showing defaults");
+    }
+    for path in sources {
+        println!("# Loaded from {}", path.display());
+    }
+    print!("{}", toml::to_string_pretty(&config)?);
+    Ok(())
+}
+
+/// Set a dotted `key` such as `ai.provider` in the project-local or global
+/// config file, `value` is parsed as JSON unless the setting is a string
+pub fn set(key: &str, value: &str, global: bool) -> Result<()> {
+    let path = target(global)?;
+    let mut document = if path.exists() {
+        read(&path)?
+    } else {
+        Value::Object(Default::default())
+    };
+
+    // Versions like 21 or 3.4 would otherwise turn into numbers
+    let defaults = serde_json::to_value(ProjectConfig::default())?;
+    let is_string = defaults
+        .pointer(&format!("/{}", key.replace('.', "/")))
+        .is_some_and(Value::is_string);
+    let value = match serde_json::from_str(value) {
+        Ok(parsed) if !is_string => parsed,
+        _ => Value::String(value.to_string()),
+    };
+    let mut node = &mut document;
+    for segment in key.split('.') {
+        if !node.is_object() {
+            *node = Value::Object(Default::default());
+        }
+        node = node
+            .as_object_mut()
+            .unwrap()
+            .entry(segment)
+            .or_insert(Value::Null);
+    }
+    *node = value;
+
+    // Make sure the effective config is still valid before writing it
+    let mut merged = defaults;
+    let mut layers = Vec::new();
+    for source in sources()? {
+        if !same_file(&source, &path) {
+            layers.push(read(&source)?);
+        }
+    }
+    // The global file sits below the project-local one
+    let position = if global { 0 } else { layers.len() };
+    layers.insert(position, document.clone());
+    for layer in layers {
+        merge(&mut merged, layer);
+    }
+    ProjectConfig::from_value(merged)?;
+
+    write(&path, &document)?;
+    println!("Set {} in {}", key, path.display());
+    Ok(())
+}
@@ -0,0 +1,611 @@
+use crate::initializr::{self, CatalogEntry};
+use crate::metadata::{self, InitializrMetadata};
+use crate::output::{self, OutputFormat};
+use crate::{cache, gradle, pom, wizard, BuildTool, ProjectConfig};
+use clap_complete::CompletionCandidate;
+use color_eyre::eyre::Result;
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fs;
+
+pub async fn compare_dependencies(
+    config: &ProjectConfig,
+    from: &str,
+    to: &str,
+    output: OutputFormat,
+) -> Result<()> {
+    eprintln!(
+        "Fetching dependencies for Spring Boot {} and {}...",
+        from, to
+    );
+    let client = initializr::Client::new(config)?;
+    let old = client.dependency_catalog(from).await?;
+    let new = client.dependency_catalog(to).await?;
+    let diff = initializr::compare_catalogs(&old, &new);
+
+    if output.is_json() {
+        return output::print_json(&output::DependencyChanges {
+            from: from.to_string(),
+            to: to.to_string(),
+            added: diff.added,
+            removed: diff.removed,
+            renamed: diff
+                .renamed
+                .into_iter()
+                .map(|(from, to)| output::Rename { from, to })
+                .collect(),
+        });
+    }
+
+    println!("Dependency changes from {} to {}\n", from, to);
+    if diff.added.is_empty() && diff.removed.is_empty() && diff.renamed.is_empty() {
+        println!("No dependency changes");
+        return Ok(());
+    }
+
+    if !diff.added.is_empty() {
+        println!("Added:");
+        for id in &diff.added {
+            println!("  + {}", id);
+        }
+    }
+    if !diff.removed.is_empty() {
+        println!("Removed:");
+        for id in &diff.removed {
+            println!("  - {}", id);
+        }
+    }
+    if !diff.renamed.is_empty() {
+        println!("Renamed:");
+        for (old_id, new_id) in &diff.renamed {
+            println!("  ~ {} -> {}", old_id, new_id);
+        }
+    }
+
+    Ok(())
+}
+
+/// Sort `(id, details)` pairs case-insensitively by ID, falling back to
+/// the exact ID so ties between differently-cased IDs order deterministically
+fn sort_dependencies<T>(dep_list: &mut [(String, T)]) {
+    dep_list.sort_by(|a, b| {
+        a.0.to_lowercase()
+            .cmp(&b.0.to_lowercase())
+            .then_with(|| a.0.cmp(&b.0))
+    });
+}
+
+/// `(id, (name, description))` of a metadata entry
+type DependencySummary = (String, (String, String));
+
+fn dependency_summary(dep: &metadata::Dependency) -> DependencySummary {
+    (dep.id.clone(), (dep.name.clone(), dep.description.clone()))
+}
+
+fn dependency_list(dep_list: Vec<DependencySummary>) -> output::DependencyList {
+    output::DependencyList {
+        count: dep_list.len(),
+        dependencies: dep_list
+            .into_iter()
+            .map(|(id, (name, description))| output::DependencyInfo {
+                id,
+                name,
+                description,
+            })
+            .collect(),
+    }
+}
+
+fn print_dependencies(
+    title: &str,
+    dep_list: Vec<DependencySummary>,
+    output: OutputFormat,
+) -> Result<()> {
+    if output.is_json() {
+        return output::print_json(&dependency_list(dep_list));
+    }
+
+    // Print in a formatted table
+    println!("{}\n", title);
+    println!("{:<40} Description", "ID");
+    println!("{:-<120}", "");
+
+    let count = dep_list.len();
+    for (id, (name, description)) in dep_list {
+        // Wrap description text
+        let wrapped_desc = textwrap::fill(&format!("{} - {}", name, description), 70);
+        let mut lines = wrapped_desc.lines();
+
+        if let Some(first_line) = lines.next() {
+            println!("{:<40} {}", id, first_line);
+            for line in lines {
+                println!("{:<40} {}", "", line);
+            }
+        }
+    }
+
+    println!("\n{} dependencies", count);
+
+    Ok(())
+}
+
+pub async fn list_dependencies(
+    config: &ProjectConfig,
+    refresh: bool,
+    output: OutputFormat,
+) -> Result<()> {
+    let metadata = cache::metadata(config, refresh).await?;
+
+    let mut dep_list: Vec<DependencySummary> = metadata
+        .dependencies()
+        .map(|(_, dep)| dependency_summary(dep))
+        .collect();
+
+    // Sort dependencies by ID
+    sort_dependencies(&mut dep_list);
+
+    print_dependencies("Available Spring Boot Dependencies", dep_list, output)
+}
+
+/// Relevance of a dependency to a lowercase search `query`, higher is
+/// better, None when it doesn't match at all
+fn search_score(query: &str, id: &str, name: &str, description: &str) -> Option<u32> {
+    let (id, name, description) = (
+        id.to_lowercase(),
+        name.to_lowercase(),
+        description.to_lowercase(),
+    );
+    if id == query {
+        return Some(100);
+    }
+    if id.contains(query) {
+        return Some(80);
+    }
+    if name.contains(query) {
+        return Some(60);
+    }
+    if description.contains(query) {
+        return Some(40);
+    }
+
+    // Tolerate typos by comparing against the ID and each word of the name
+    let similarity = std::iter::once(id.as_str())
+        .chain(name.split_whitespace())
+        .map(|word| strsim::jaro_winkler(query, word))
+        .fold(0.0, f64::max);
+    (similarity >= 0.85).then_some((similarity * 30.0) as u32)
+}
+
+/// Dependencies matching a lowercase search `query`, most relevant first
+fn search_matches(metadata: &InitializrMetadata, query: &str) -> Vec<DependencySummary> {
+    let mut matches: Vec<(u32, DependencySummary)> = metadata
+        .dependencies()
+        .map(|(_, dep)| dependency_summary(dep))
+        .filter_map(|dep| {
+            let (id, (name, description)) = &dep;
+            search_score(query, id, name, description).map(|score| (score, dep))
+        })
+        .collect();
+    matches.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1 .0.cmp(&b.1 .0)));
+    matches.into_iter().map(|(_, dep)| dep).collect()
+}
+
+pub async fn search_dependencies(
+    config: &ProjectConfig,
+    query: &str,
+    refresh: bool,
+    output: OutputFormat,
+) -> Result<()> {
+    let metadata = cache::metadata(config, refresh).await?;
+    let query = query.to_lowercase();
+
+    print_dependencies(
+        &format!("Dependencies matching '{}'", query),
+        search_matches(&metadata, &query),
+        output,
+    )
+}
+
+pub async fn show_dependency(
+    config: &ProjectConfig,
+    id: &str,
+    refresh: bool,
+    output: OutputFormat,
+) -> Result<()> {
+    let metadata = cache::metadata(config, refresh).await?;
+    let Some((category, dep)) = metadata.dependency(id) else {
+        let known = metadata.dependency_ids();
+        return Err(match closest_dependency(id, &known) {
+            Some(suggestion) => color_eyre::eyre::eyre!(
+                "Unknown dependency '{}', did you mean '{}'?",
+                id,
+                suggestion
+            ),
+            None => color_eyre::eyre::eyre!("Unknown dependency '{}'", id),
+        });
+    };
+
+    if output.is_json() {
+        let mut entry = serde_json::to_value(dep)?;
+        entry["category"] = serde_json::Value::from(category.name.as_str());
+        return output::print_json(&entry);
+    }
+
+    println!("           ID: {}", dep.id);
+    println!("         NAME: {}", dep.name);
+    println!("     CATEGORY: {}", category.name);
+    println!(
+        "VERSION RANGE: {}",
+        dep.version_range.as_deref().unwrap_or("-")
+    );
+    let description = textwrap::fill(&dep.description, 70);
+    for (i, line) in description.lines().enumerate() {
+        let label = if i == 0 { "DESCRIPTION:" } else { "" };
+        println!("{:>14} {}", label, line);
+    }
+
+    if !dep.links.is_empty() {
+        println!("        LINKS:");
+        for (rel, links) in &dep.links {
+            for link in links.iter() {
+                match &link.title {
+                    Some(title) => println!("               {}: {} ({})", rel, title, link.href),
+                    None => println!("               {}: {}", rel, link.href),
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Shell completion candidates for dependency IDs, read from the cached
+/// metadata so completing never waits on start.spring.io
+pub fn complete_ids(current: &OsStr) -> Vec<CompletionCandidate> {
+    let metadata =
+        ProjectConfig::load_or_default().and_then(|config| cache::cached_metadata(&config));
+    let (Some(current), Ok(Some(metadata))) = (current.to_str(), metadata) else {
+        return Vec::new();
+    };
+    metadata
+        .dependencies()
+        .filter(|(_, dep)| dep.id.starts_with(current))
+        .map(|(category, dep)| {
+            CompletionCandidate::new(&dep.id)
+                .help(Some(dep.name.clone().into()))
+                .tag(Some(category.name.clone().into()))
+        })
+        .collect()
+}
+
+/// Closest known ID to `id`, if any is near enough to be a likely typo
+fn closest_dependency<'a>(id: &str, known: &'a [String]) -> Option<&'a str> {
+    let max_distance = id.len() / 3 + 1;
+    known
+        .iter()
+        .map(|candidate| (strsim::levenshtein(id, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.as_str())
+}
+
+/// Replace `@name` entries with the IDs of the preset they name. Presets may
+/// reference other presets. IDs requested more than once, directly or
+/// through presets, are kept once where they first appear.
+pub fn expand_presets(
+    ids: Vec<String>,
+    presets: &BTreeMap<String, Vec<String>>,
+) -> Result<Vec<String>> {
+    fn expand(
+        id: String,
+        presets: &BTreeMap<String, Vec<String>>,
+        expanding: &mut Vec<String>,
+        expanded: &mut Vec<String>,
+    ) -> Result<()> {
+        let Some(name) = id.strip_prefix('@') else {
+            if !expanded.contains(&id) {
+                expanded.push(id);
+            }
+            return Ok(());
+        };
+        if expanding.iter().any(|outer| outer == name) {
+            return Err(color_eyre::eyre::eyre!("Preset @{} includes itself", name));
+        }
+        let preset = presets.get(name).ok_or_else(|| {
+            if presets.is_empty() {
+                color_eyre::eyre::eyre!("Unknown preset @{}, no presets are configured", name)
+            } else {
+                color_eyre::eyre::eyre!(
+                    "Unknown preset @{}, configured presets: {}",
+                    name,
+                    presets
+                        .keys()
+                        .map(|name| format!("@{}", name))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            }
+        })?;
+        expanding.push(name.to_string());
+        for id in preset {
+            expand(id.clone(), presets, expanding, expanded)?;
+        }
+        expanding.pop();
+        Ok(())
+    }
+
+    let mut expanded = Vec::new();
+    for id in ids {
+        expand(id, presets, &mut Vec::new(), &mut expanded)?;
+    }
+    Ok(expanded)
+}
+
+/// Check requested IDs against the metadata. Typos close to a known ID are
+/// corrected, after asking when `prompt` is set. Other unknown IDs are
+/// dropped when `skip_unknown` is set and fail otherwise.
+pub fn validate_dependencies(
+    deps: Vec<String>,
+    metadata: &InitializrMetadata,
+    skip_unknown: bool,
+    prompt: bool,
+) -> Result<Vec<String>> {
+    let known = metadata.dependency_ids();
+    let mut valid = Vec::new();
+    let mut unknown = Vec::new();
+    for id in deps {
+        if known.contains(&id) {
+            valid.push(id);
+            continue;
+        }
+        match closest_dependency(&id, &known) {
+            Some(suggestion) if !prompt => {
+                eprintln!("Unknown dependency '{}', using '{}'", id, suggestion);
+                valid.push(suggestion.to_string());
+            }
+            Some(suggestion) => {
+                let message = format!(
+                    "Unknown dependency '{}', did you mean '{}'?",
+                    id, suggestion
+                );
+                if wizard::confirm(&message, true)? {
+                    valid.push(suggestion.to_string());
+                } else {
+                    unknown.push(id);
+                }
+            }
+            None => {
+                eprintln!("Unknown dependency '{}'", id);
+                unknown.push(id);
+            }
+        }
+    }
+    // A corrected ID may already have been requested
+    valid.sort();
+    valid.dedup();
+
+    if unknown.is_empty() {
+        return Ok(valid);
+    }
+
+    if skip_unknown {
+        eprintln!("Skipping unknown dependencies: {}", unknown.join(", "));
+        Ok(valid)
+    } else {
+        Err(color_eyre::eyre::eyre!(
+            "Unknown dependencies: {}, use --skip-unknown to drop them",
+            unknown.join(", ")
+        ))
+    }
+}
+
+/// Look up IDs in the catalog for the configured Boot version
+async fn resolve_catalog_entries(
+    config: &ProjectConfig,
+    ids: &[String],
+) -> Result<Vec<(String, CatalogEntry)>> {
+    let build_file = config.build_file();
+    if !build_file.exists() {
+        return Err(color_eyre::eyre::eyre!(
+            "No build file found at {}, run init first",
+            build_file.display()
+        ));
+    }
+
+    let catalog = initializr::Client::new(config)?
+        .dependency_catalog(&config.boot_version)
+        .await?;
+    let known: Vec<String> = catalog.keys().cloned().collect();
+
+    let mut entries = Vec::new();
+    for id in ids {
+        match catalog.get(id).cloned() {
+            Some(entry) => entries.push((id.clone(), entry)),
+            None => {
+                return Err(match closest_dependency(id, &known) {
+                    Some(suggestion) => color_eyre::eyre::eyre!(
+                        "Unknown dependency '{}', did you mean '{}'?",
+                        id,
+                        suggestion
+                    ),
+                    None => color_eyre::eyre::eyre!(
+                        "Unknown dependency '{}' for Spring Boot {}",
+                        id,
+                        config.boot_version
+                    ),
+                });
+            }
+        }
+    }
+    Ok(entries)
+}
+
+pub async fn add_dependencies(config: &ProjectConfig, ids: &[String]) -> Result<()> {
+    let ids = expand_presets(ids.to_vec(), &config.presets)?;
+    let entries = resolve_catalog_entries(config, &ids).await?;
+    let build_file = config.build_file();
+
+    if config.build_tool.is_gradle() {
+        let mut content = fs::read_to_string(&build_file)?;
+        for (id, entry) in &entries {
+            let mut notation = entry.group_artifact();
+            if let Some(version) = &entry.coordinates.version {
+                notation = format!("{}:{}", notation, version);
+            }
+            match gradle::add_dependency(
+                &content,
+                config.build_tool == BuildTool::GradleKotlin,
+                entry.gradle_configurations(),
+                &notation,
+            )? {
+                Some(updated) => {
+                    println!("Adding dependency: {} ({})", id, entry.group_artifact());
+                    content = updated;
+                }
+                None => println!("Dependency {} is already present", id),
+            }
+        }
+        fs::write(&build_file, content)?;
+    } else {
+        let mut pom = pom::Pom::load(&build_file)?;
+        for (id, entry) in &entries {
+            match pom.upsert_dependency(&entry.maven_dependency())? {
+                pom::Change::Added => {
+                    println!("Adding dependency: {} ({})", id, entry.group_artifact())
+                }
+                pom::Change::Updated => {
+                    println!("Updating dependency: {} ({})", id, entry.group_artifact())
+                }
+                pom::Change::Unchanged => println!("Dependency {} is already present", id),
+            }
+        }
+        pom.save(&build_file)?;
+    }
+
+    for (id, entry) in &entries {
+        if let Some(bom) = &entry.bom {
+            println!(
+                "Note: {} is managed by the {} BOM, make sure it is imported",
+                id, bom
+            );
+        }
+    }
+    Ok(())
+}
+
+pub async fn remove_dependencies(config: &ProjectConfig, ids: &[String]) -> Result<()> {
+    let ids = expand_presets(ids.to_vec(), &config.presets)?;
+    let entries = resolve_catalog_entries(config, &ids).await?;
+    let build_file = config.build_file();
+
+    if config.build_tool.is_gradle() {
+        let mut content = fs::read_to_string(&build_file)?;
+        for (id, entry) in &entries {
+            match gradle::remove_dependency(&content, &entry.group_artifact()) {
+                Some(updated) => {
+                    println!("Removing dependency: {} ({})", id, entry.group_artifact());
+                    content = updated;
+                }
+                None => println!("Dependency {} is not present", id),
+            }
+        }
+        fs::write(&build_file, content)?;
+    } else {
+        let mut pom = pom::Pom::load(&build_file)?;
+        for (id, entry) in &entries {
+            let coordinates = &entry.coordinates;
+            if pom.remove_dependency(&coordinates.group_id, &coordinates.artifact_id)? {
+                println!("Removing dependency: {} ({})", id, entry.group_artifact());
+            } else {
+                println!("Dependency {} is not present", id);
+            }
+        }
+        pom.save(&build_file)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog() -> InitializrMetadata {
+        serde_json::from_value(serde_json::json!({
+            "dependencies": {
+                "values": [
+                    {
+                        "name": "Web",
+                        "values": [
+                            {"id": "web", "name": "Spring Web", "description": "Build web apps"},
+                            {"id": "webflux", "name": "Spring Reactive Web", "description": "Reactive web apps"}
+                        ]
+                    },
+                    {
+                        "name": "SQL",
+                        "values": [
+                            {"id": "data-jpa", "name": "Spring Data JPA", "description": "Persist data with JPA"},
+                            {"id": "postgresql", "name": "PostgreSQL Driver", "description": "JDBC driver"}
+                        ]
+                    }
+                ]
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn count_matches_filtered_rows() {
+        let matches = search_matches(&catalog(), "web");
+        assert_eq!(matches.len(), 2);
+
+        let list = dependency_list(matches);
+        assert_eq!(list.count, 2);
+        assert_eq!(list.dependencies.len(), 2);
+        let json = serde_json::to_value(&list).unwrap();
+        assert_eq!(json["count"], 2);
+        assert_eq!(json["dependencies"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn sorts_case_insensitively() {
+        let mut dep_list: Vec<(String, ())> = ["web", "Actuator", "data-jpa", "Web", "batch"]
+            .into_iter()
+            .map(|id| (id.to_string(), ()))
+            .collect();
+        sort_dependencies(&mut dep_list);
+        let ids: Vec<&str> = dep_list.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, ["Actuator", "batch", "data-jpa", "Web", "web"]);
+    }
+
+    #[test]
+    fn presets_expand_without_duplicates() {
+        let presets = BTreeMap::from([
+            (
+                "webstack".to_string(),
+                vec![
+                    "web".to_string(),
+                    "validation".to_string(),
+                    "@ops".to_string(),
+                ],
+            ),
+            (
+                "ops".to_string(),
+                vec!["actuator".to_string(), "web".to_string()],
+            ),
+        ]);
+        let ids = ["web", "web", "@webstack", "actuator", "devtools"]
+            .map(String::from)
+            .to_vec();
+
+        let expanded = expand_presets(ids, &presets).unwrap();
+
+        assert_eq!(expanded, ["web", "validation", "actuator", "devtools"]);
+    }
+
+    #[test]
+    fn unknown_and_recursive_presets_fail() {
+        let presets = BTreeMap::from([("loop".to_string(), vec!["@loop".to_string()])]);
+        assert!(expand_presets(vec!["@missing".to_string()], &presets).is_err());
+        assert!(expand_presets(vec!["@loop".to_string()], &presets).is_err());
+    }
+}
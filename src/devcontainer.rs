@@ -0,0 +1,89 @@
+use crate::project::Language;
+use crate::ProjectConfig;
+use color_eyre::eyre::Result;
+use serde_json::json;
+use std::fs;
+
+/// Port the app listens on, forwarded out of the container
+const APP_PORT: u16 = 8080;
+
+/// Image with the JDK, git, and a non-root `vscode` user
+fn dockerfile(config: &ProjectConfig) -> String {
+    format!(
+        "FROM mcr.microsoft.com/devcontainers/java:{}\n",
+        config.java_version
+    )
+}
+
+fn extensions(config: &ProjectConfig) -> Vec<&'static str> {
+    let mut extensions = vec!["vscjava.vscode-java-pack", "vmware.vscode-boot-dev-pack"];
+    if config.build_tool.is_gradle() {
+        extensions.push("vscjava.vscode-gradle");
+    }
+    if config.language == Language::Kotlin {
+        extensions.push("fwcd.kotlin");
+    }
+    extensions
+}
+
+fn devcontainer(config: &ProjectConfig) -> String {
+    let (volume, cache, warm_up) = if config.build_tool.is_gradle() {
+        ("gradle", "/home/vscode/.gradle", "./gradlew dependencies")
+    } else {
+        ("m2", "/home/vscode/.m2", "./mvnw -B dependency:go-offline")
+    };
+    let value = json!({
+        "name": config.app_name,
+        "build": {"dockerfile": "Dockerfile"},
+        // A named volume shares downloaded dependencies across projects and
+        // container rebuilds
+        "mounts": [format!("source=spring-init-{},target={},type=volume", volume, cache)],
+        // The volume is created owned by root
+        "postCreateCommand": format!("sudo chown -R vscode {} && {}", cache, warm_up),
+        "forwardPorts": [APP_PORT],
+        "customizations": {
+            "vscode": {
+                "extensions": extensions(config),
+            },
+        },
+    });
+    let mut content = serde_json::to_string_pretty(&value).unwrap();
+    content.push('\n');
+    content
+}
+
+/// Write .devcontainer/devcontainer.json and its Dockerfile, so the project
+/// opens ready to build in VS Code or Codespaces
+pub fn write(config: &ProjectConfig, force: bool, dry_run: bool) -> Result<()> {
+    if !config.build_file().exists() {
+        return Err(color_eyre::eyre::eyre!(
+            "No {} found, run init first",
+            config.build_file().display()
+        ));
+    }
+
+    let dir = config.app_dir().join(".devcontainer");
+    let files = [
+        (dir.join("devcontainer.json"), devcontainer(config)),
+        (dir.join("Dockerfile"), dockerfile(config)),
+    ];
+    if dry_run {
+        for (path, _) in &files {
+            println!("Would write {}", path.display());
+        }
+        return Ok(());
+    }
+    if let Some((path, _)) = files.iter().find(|(path, _)| path.exists() && !force) {
+        return Err(color_eyre::eyre::eyre!(
+            "{} already exists, use --force to overwrite it",
+            path.display()
+        ));
+    }
+
+    fs::create_dir_all(&dir)?;
+    for (path, content) in &files {
+        fs::write(path, content)?;
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
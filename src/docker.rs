@@ -0,0 +1,170 @@
+use crate::{versions, wrapper, Packaging, ProjectConfig};
+use clap::ValueEnum;
+use color_eyre::eyre::Result;
+use std::fs;
+use std::process::Command;
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum Strategy {
+    /// Multi-stage Dockerfile extracting the jar's layers
+    #[default]
+    Dockerfile,
+    /// Cloud Native Buildpacks through the Spring Boot build plugin
+    Buildpacks,
+}
+
+const DOCKERIGNORE: &str = "target/\nbuild/\n.gradle/\n.idea/\n*.iml\n";
+
+fn dockerfile(config: &ProjectConfig) -> String {
+    let war = config.packaging == Packaging::War;
+    let build = if !config.build_tool.is_gradle() {
+        "./mvnw -q package -DskipTests"
+    } else if war {
+        "./gradlew -q bootWar -x test"
+    } else {
+        "./gradlew -q bootJar -x test"
+    };
+    // Relative to the project directory, the jar is in the runnable module of a multi-module project
+    let jar_path = config.jar_path();
+    let jar = jar_path
+        .strip_prefix(config.app_dir())
+        .unwrap_or(&jar_path)
+        .display()
+        .to_string()
+        .replace('\\', "/");
+
+    // Boot 3.3 replaced layertools with the tools jar mode, 3.2 moved the launcher
+    let extract = if versions::in_range("3.3.0", &config.boot_version) {
+        format!(
+            "java -Djarmode=tools -jar {} extract --layers --launcher --destination extracted",
+            jar
+        )
+    } else {
+        format!(
+            "mkdir extracted && cd extracted && java -Djarmode=layertools -jar ../{} extract",
+            jar
+        )
+    };
+    let launcher = match (versions::in_range("3.2.0", &config.boot_version), war) {
+        (true, false) => "org.springframework.boot.loader.launch.JarLauncher",
+        (true, true) => "org.springframework.boot.loader.launch.WarLauncher",
+        (false, false) => "org.springframework.boot.loader.JarLauncher",
+        (false, true) => "org.springframework.boot.loader.WarLauncher",
+    };
+
+    format!(
+        "FROM eclipse-temurin:{java}-jdk AS build\n\
+        WORKDIR /workspace\n\
+        COPY . .\n\
+        RUN {build}\n\
+        RUN {extract}\n\
+        \n\
+        FROM eclipse-temurin:{java}-jre\n\
+        WORKDIR /app\n\
+        COPY --from=build /workspace/extracted/dependencies/ ./\n\
+        COPY --from=build /workspace/extracted/spring-boot-loader/ ./\n\
+        COPY --from=build /workspace/extracted/snapshot-dependencies/ ./\n\
+        COPY --from=build /workspace/extracted/application/ ./\n\
+        EXPOSE 8080\n\
+        ENTRYPOINT [\"java\", \"{launcher}\"]\n",
+        java = config.java_version,
+    )
+}
+
+/// Write a Dockerfile and .dockerignore unless the project already has them
+pub fn write_dockerfile(config: &ProjectConfig) -> Result<()> {
+    let app_dir = config.app_dir();
+    let path = app_dir.join("Dockerfile");
+    if path.exists() {
+        println!("Using existing {}", path.display());
+    } else {
+        fs::write(&path, dockerfile(config))?;
+        println!("Wrote {}", path.display());
+    }
+
+    let ignore = app_dir.join(".dockerignore");
+    if !ignore.exists() {
+        fs::write(&ignore, DOCKERIGNORE)?;
+    }
+    Ok(())
+}
+
+fn run_command(program: &str, args: &[String], config: &ProjectConfig) -> Result<()> {
+    let status = Command::new(program)
+        .args(args)
+        .current_dir(config.app_dir())
+        .status()
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to run {}: {}", program, e))?;
+    if !status.success() {
+        return Err(color_eyre::eyre::eyre!(
+            "{} exited with {}",
+            program,
+            status
+        ));
+    }
+    Ok(())
+}
+
+/// Build a container image for the project
+pub fn build(config: &ProjectConfig, strategy: Strategy, tag: Option<&str>) -> Result<()> {
+    if !config.build_file().exists() {
+        return Err(color_eyre::eyre::eyre!(
+            "No {} found, run init first",
+            config.build_file().display()
+        ));
+    }
+
+    let image = tag.map(String::from).unwrap_or_else(|| config.image());
+    println!("Building image {}...", image);
+    match strategy {
+        Strategy::Dockerfile => {
+            write_dockerfile(config)?;
+            run_command(
+                "docker",
+                &[
+                    "build".to_string(),
+                    "-t".to_string(),
+                    image.clone(),
+                    ".".to_string(),
+                ],
+                config,
+            )?;
+        }
+        Strategy::Buildpacks if config.build_tool.is_gradle() => run_command(
+            &wrapper::program(config),
+            &[
+                "bootBuildImage".to_string(),
+                format!("--imageName={}", image),
+            ],
+            config,
+        )?,
+        Strategy::Buildpacks => run_command(
+            &wrapper::maven(config),
+            &[
+                "spring-boot:build-image".to_string(),
+                format!("-Dspring-boot.build-image.imageName={}", image),
+            ],
+            config,
+        )?,
+    }
+
+    println!("Built image {}", image);
+    Ok(())
+}
+
+/// Start a container from the built image, publishing the app port
+pub fn run(config: &ProjectConfig, tag: Option<&str>, port: u16) -> Result<()> {
+    let image = tag.map(String::from).unwrap_or_else(|| config.image());
+    println!("Running {} on port {}", image, port);
+    run_command(
+        "docker",
+        &[
+            "run".to_string(),
+            "--rm".to_string(),
+            "-p".to_string(),
+            format!("{}:8080", port),
+            image,
+        ],
+        config,
+    )
+}
@@ -0,0 +1,250 @@
+use crate::ai::provider::ProviderKind;
+use crate::{ai, http, initializr, jdk, native, wrapper, ProjectConfig};
+use color_eyre::eyre::Result;
+use reqwest::header::HeaderMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+/// Oldest Maven release supported by Spring Boot 3
+const MIN_MAVEN_VERSION: (u32, u32, u32) = (3, 6, 3);
+
+struct Check {
+    name: &'static str,
+    result: Result<String, String>,
+    hint: &'static str,
+}
+
+/// Run `program` with `args`, returning stdout and stderr combined
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Some(text)
+}
+
+/// The quoted version in `java -version` output, e.g. "21.0.2" or "1.8.0_392"
+fn java_major_version(output: &str) -> Option<u32> {
+    let version = output.split('"').nth(1)?;
+    let mut parts = version.split(['.', '_', '-', '+']);
+    match parts.next()? {
+        "1" => parts.next()?.parse().ok(),
+        major => major.parse().ok(),
+    }
+}
+
+fn check_java(config: &ProjectConfig) -> Check {
+    if let Some(home) = jdk::managed_home(&config.java_version) {
+        return Check {
+            name: "java",
+            result: Ok(format!(
+                "Java {} installed by spring-init at {}",
+                config.java_version,
+                home.display()
+            )),
+            hint: "",
+        };
+    }
+    let result = match command_output("java", &["-version"]) {
+        None => Err("java not found on PATH".to_string()),
+        Some(output) => match java_major_version(&output) {
+            None => Err("could not determine the Java version".to_string()),
+            Some(major) => match config.java_version.parse::<u32>() {
+                Ok(wanted) if major < wanted => Err(format!(
+                    "Java {} is older than the configured java_version {}",
+                    major, wanted
+                )),
+                _ => Ok(format!("Java {}", major)),
+            },
+        },
+    };
+    Check {
+        name: "java",
+        result,
+        hint: "Run `spring-init jdk install`, or install a JDK matching java_version and put it on PATH",
+    }
+}
+
+/// The version in `mvn -v` output, e.g. "Apache Maven 3.9.6 (...)"
+fn maven_version(output: &str) -> Option<(u32, u32, u32)> {
+    let version = output
+        .lines()
+        .find_map(|line| line.strip_prefix("Apache Maven "))?
+        .split_whitespace()
+        .next()?;
+    let mut parts = version.split('.').map(|part| part.parse::<u32>().ok());
+    Some((
+        parts.next()??,
+        parts.next().flatten().unwrap_or(0),
+        parts.next().flatten().unwrap_or(0),
+    ))
+}
+
+fn check_maven(config: &ProjectConfig) -> Check {
+    let result = match command_output(wrapper::MVN, &["-v"]) {
+        None if wrapper::has_maven_wrapper(&config.app_dir()) => {
+            Ok("mvn not on PATH, the project's Maven wrapper is used".to_string())
+        }
+        None => Err("mvn not found on PATH".to_string()),
+        Some(output) => match maven_version(&output) {
+            None => Err("could not determine the Maven version".to_string()),
+            Some(version) if version < MIN_MAVEN_VERSION => Err(format!(
+                "Maven {}.{}.{} is older than 3.6.3",
+                version.0, version.1, version.2
+            )),
+            Some(version) => Ok(format!("Maven {}.{}.{}", version.0, version.1, version.2)),
+        },
+    };
+    Check {
+        name: "maven",
+        result,
+        hint: "Install Maven 3.6.3 or later and put mvn on PATH",
+    }
+}
+
+/// Find native-image and report its version, e.g. "native-image 21.0.2 2024-01-16"
+fn check_graalvm() -> Check {
+    // native-image is often only under GRAALVM_HOME, not on PATH
+    let name = if cfg!(windows) {
+        "native-image.cmd"
+    } else {
+        "native-image"
+    };
+    let program = env::var("GRAALVM_HOME")
+        .ok()
+        .map(|home| Path::new(&home).join("bin").join(name))
+        .filter(|path| path.exists())
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_else(|| name.to_string());
+    let result = match command_output(&program, &["--version"]) {
+        None => Err("native-image not found on PATH or in GRAALVM_HOME".to_string()),
+        Some(output) => output
+            .lines()
+            .next()
+            .map(|line| line.trim().to_string())
+            .filter(|line| line.starts_with("native-image"))
+            .ok_or_else(|| "could not determine the native-image version".to_string()),
+    };
+    Check {
+        name: "graalvm",
+        result,
+        hint: "Install a GraalVM JDK (e.g. `sdk install java 21-graal`) and set GRAALVM_HOME",
+    }
+}
+
+async fn check_reachable(
+    config: &ProjectConfig,
+    name: &'static str,
+    url: &str,
+    headers: HeaderMap,
+    hint: &'static str,
+) -> Check {
+    let client = http::client_builder(&config.network).and_then(|builder| {
+        Ok(builder
+            .timeout(Duration::from_secs(10))
+            .default_headers(headers)
+            .build()?)
+    });
+    let result = match client {
+        Ok(client) => match client.get(url).send().await {
+            Ok(response) if response.status().is_success() => Ok(format!("{} reachable", url)),
+            Ok(response) => Err(format!("{} returned {}", url, response.status())),
+            Err(e) => Err(format!("{} unreachable: {}", url, e)),
+        },
+        Err(e) => Err(e.to_string()),
+    };
+    Check { name, result, hint }
+}
+
+fn check_api_key(config: &ProjectConfig, hint: &'static str) -> Check {
+    let result = match ai::auth::lookup(&config.ai) {
+        Some((_, source)) => Ok(format!("API key found in {}", source)),
+        None => Err("No API key found".to_string()),
+    };
+    Check {
+        name: "ai",
+        result,
+        hint,
+    }
+}
+
+async fn check_ai(config: &ProjectConfig) -> Check {
+    match config.ai.provider {
+        ProviderKind::Anthropic => check_api_key(
+            config,
+            "Run `spring-init auth login anthropic`, export ANTHROPIC_API_KEY, or choose another provider with --provider",
+        ),
+        ProviderKind::OpenAi => check_api_key(
+            config,
+            "Run `spring-init auth login openai`, export OPENAI_API_KEY, or choose another provider with --provider",
+        ),
+        ProviderKind::Ollama => {
+            let base_url = config
+                .ai
+                .base_url
+                .as_deref()
+                .unwrap_or(ai::ollama::DEFAULT_BASE_URL);
+            check_reachable(
+                config,
+                "ai",
+                &format!("{}/api/tags", base_url.trim_end_matches('/')),
+                HeaderMap::new(),
+                "Start the Ollama server with `ollama serve` or set ai.base_url",
+            )
+            .await
+        }
+    }
+}
+
+/// Check the tools and services spring-init relies on, failing if any are missing
+pub async fn run(config: &ProjectConfig) -> Result<()> {
+    let mut checks = vec![check_java(config)];
+    if !config.build_tool.is_gradle() {
+        // Gradle projects build with the generated wrapper
+        checks.push(check_maven(config));
+    }
+    // Only projects building native executables need GraalVM
+    let build_file = fs::read_to_string(config.build_file()).unwrap_or_default();
+    if native::is_configured(&build_file) {
+        checks.push(check_graalvm());
+    }
+    let hint = "Check your internet connection, or set network.proxy, network.ca_bundle, and initializr_url";
+    checks.push(match initializr::headers(config) {
+        Ok(headers) => {
+            check_reachable(
+                config,
+                "network",
+                &initializr::metadata_url(config),
+                headers,
+                hint,
+            )
+            .await
+        }
+        Err(e) => Check {
+            name: "network",
+            result: Err(e.to_string()),
+            hint,
+        },
+    });
+    checks.push(check_ai(config).await);
+
+    let mut failures = 0;
+    for check in &checks {
+        match &check.result {
+            Ok(detail) => println!("[ok]   {:<8} {}", check.name, detail),
+            Err(detail) => {
+                failures += 1;
+                println!("[fail] {:<8} {}", check.name, detail);
+                println!("       {:<8} {}", "", check.hint);
+            }
+        }
+    }
+
+    if failures > 0 {
+        return Err(color_eyre::eyre::eyre!("{} check(s) failed", failures));
+    }
+    println!("All checks passed");
+    Ok(())
+}
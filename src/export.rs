@@ -0,0 +1,190 @@
+use crate::{application_yml, deps, initializr, lock, ProjectConfig};
+use clap::ValueEnum;
+use color_eyre::eyre::Result;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    /// curl and unzip
+    Curl,
+    /// HTTPie and unzip
+    Httpie,
+    /// `spring init` from the Spring Boot CLI
+    SpringCli,
+}
+
+/// Single-quote `value` unless the shell would read it as is
+fn quote(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "._,:/=@+-".contains(c));
+    if plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+/// Double-quote a header so `${VAR}` references are expanded by the shell,
+/// like spring-init expands them
+fn quote_header(header: &str) -> String {
+    let escaped: String = header
+        .chars()
+        .flat_map(|c| match c {
+            '"' | '\\' | '`' => vec!['\\', c],
+            _ => vec![c],
+        })
+        .collect();
+    format!("\"{}\"", escaped)
+}
+
+/// Settings and dependencies to export: the active project's lockfile when
+/// it has one and no extra dependencies are asked for, else the config with
+/// include_deps and `include`
+fn settings(config: &ProjectConfig, include: &[String]) -> Result<(ProjectConfig, Vec<String>)> {
+    let lock_path = config.app_dir().join(lock::FILE_NAME);
+    if include.is_empty() && lock_path.exists() {
+        let lockfile = lock::read(&lock_path)?;
+        let locked = ProjectConfig {
+            initializr_headers: config.initializr_headers.clone(),
+            ..lockfile.config
+        };
+        return Ok((locked, lockfile.dependencies));
+    }
+
+    let requested = config.include_deps.iter().chain(include).cloned().collect();
+    let mut dependencies = deps::expand_presets(requested, &config.presets)?;
+    if dependencies.is_empty() {
+        // Same default as init without a PRD
+        dependencies.push("web".to_string());
+    }
+    dependencies.sort();
+    dependencies.dedup();
+    Ok((config.clone(), dependencies))
+}
+
+/// Print a standalone command that downloads the same scaffold as `init`
+pub fn export(config: &ProjectConfig, format: Format, include: &[String]) -> Result<()> {
+    let (config, dependencies) = settings(config, include)?;
+    initializr::validate(&config)?;
+    let params = initializr::starter_params(&config, &dependencies);
+    let url = format!("{}/starter.zip", initializr::base_url(&config));
+    let zip = format!("{}.zip", config.base_dir());
+    let headers: Vec<String> = config
+        .initializr_headers
+        .iter()
+        .map(|(name, value)| format!("{}: {}", name, value))
+        .collect();
+
+    let mut lines = Vec::new();
+    match format {
+        Format::Curl => {
+            lines.push(format!("curl -fG {}", quote(&url)));
+            for header in &headers {
+                lines.push(format!("  -H {}", quote_header(header)));
+            }
+            for (name, value) in &params {
+                lines.push(format!(
+                    "  --data-urlencode {}",
+                    quote(&format!("{}={}", name, value))
+                ));
+            }
+            lines.push(format!("  -o {} && unzip {}", quote(&zip), quote(&zip)));
+        }
+        Format::Httpie => {
+            lines.push(format!(
+                "http --download --output {} GET {}",
+                quote(&zip),
+                quote(&url)
+            ));
+            for header in &headers {
+                lines.push(format!(
+                    "  {}",
+                    quote_header(&header.replacen(": ", ":", 1))
+                ));
+            }
+            for (name, value) in &params {
+                lines.push(format!("  {}", quote(&format!("{}=={}", name, value))));
+            }
+            lines.push(format!("  && unzip {}", quote(&zip)));
+        }
+        Format::SpringCli => {
+            lines.push("spring init".to_string());
+            if initializr::base_url(&config) != initializr::DEFAULT_URL {
+                lines.push(format!(
+                    "  --target={}",
+                    quote(initializr::base_url(&config))
+                ));
+            }
+            for (name, value) in &params {
+                // The directory argument takes the place of baseDir
+                if *name == "baseDir" {
+                    continue;
+                }
+                lines.push(format!("  --{}={}", kebab_case(name), quote(value)));
+            }
+            lines.push(format!("  {}", quote(config.base_dir())));
+            if !headers.is_empty() {
+                eprintln!("Note: the Spring Boot CLI can't send initializr_headers");
+            }
+        }
+    }
+    println!("{}", lines.join(" \\\n"));
+
+    let mut skipped = Vec::new();
+    if !config.maven_plugins.is_empty() {
+        skipped.push("maven_plugins");
+    }
+    if !config.maven_dependencies.is_empty() {
+        skipped.push("maven_dependencies");
+    }
+    if !config.maven_boms.is_empty() {
+        skipped.push("maven_boms");
+    }
+    if !config.maven_repositories.is_empty() || !config.maven_mirrors.is_empty() {
+        skipped.push("maven_repositories");
+    }
+    if !config.modules.is_empty() {
+        skipped.push("modules");
+    }
+    if config.layered_jar {
+        skipped.push("layered_jar");
+    }
+    if config.env_profiles {
+        skipped.push("env_profiles");
+    }
+    if !config.quality.is_empty() {
+        skipped.push("quality");
+    }
+    if config.lombok || config.mapstruct {
+        skipped.push("lombok and mapstruct");
+    }
+    if config.layout.is_some() {
+        skipped.push("layout");
+    }
+    if !config.hooks.post_init.is_empty() {
+        skipped.push("post_init hooks");
+    }
+    if !application_yml::configured(&config, &dependencies).is_empty() {
+        skipped.push("application.yml");
+    }
+    if !skipped.is_empty() {
+        eprintln!(
+            "Note: init also applies {} after the download, which the command leaves out",
+            skipped.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// `bootVersion` to `boot-version`, the Spring Boot CLI's option names
+fn kebab_case(name: &str) -> String {
+    let mut kebab = String::new();
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            kebab.push('-');
+        }
+        kebab.push(c.to_ascii_lowercase());
+    }
+    kebab
+}
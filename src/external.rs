@@ -0,0 +1,88 @@
+use crate::ProjectConfig;
+use color_eyre::eyre::Result;
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Executables named `spring-init-<name>` on PATH extend the CLI as
+/// `spring-init <name>`, like cargo's external subcommands
+const PREFIX: &str = "spring-init-";
+
+/// File name endings tried for each PATH entry, Windows runs batch files
+/// as well as executables
+const SUFFIXES: &[&str] = if cfg!(windows) {
+    &[".exe", ".cmd", ".bat"]
+} else {
+    &[""]
+};
+
+/// Path of the executable providing `spring-init <name>`
+pub fn find(name: &str) -> Option<PathBuf> {
+    env::split_paths(&env::var_os("PATH")?)
+        .flat_map(|dir| {
+            SUFFIXES
+                .iter()
+                .map(move |suffix| dir.join(format!("{}{}{}", PREFIX, name, suffix)))
+        })
+        .find(|path| is_executable(path))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &std::path::Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &std::path::Path) -> bool {
+    path.is_file()
+}
+
+/// Run the external command for `args[0]` with the rest of `args`, passing
+/// the resolved config as JSON in SPRING_INIT_CONFIG along with the active
+/// app dir, the global flags, and this executable for calling back. On Unix
+/// the process is replaced, elsewhere its exit code is passed on.
+pub fn run(config: &ProjectConfig, args: &[String], quiet: bool, dry_run: bool) -> Result<()> {
+    let (name, args) = args
+        .split_first()
+        .ok_or_else(|| color_eyre::eyre::eyre!("No command given"))?;
+    let program = find(name).ok_or_else(|| {
+        color_eyre::eyre::eyre!(
+            "Unknown command {}, and no {}{} found on PATH",
+            name,
+            PREFIX,
+            name
+        )
+    })?;
+
+    let mut command = Command::new(&program);
+    command
+        .args(args)
+        .env("SPRING_INIT_CONFIG", serde_json::to_string(config)?)
+        .env("SPRING_INIT_APP_DIR", config.app_dir())
+        .env("SPRING_INIT_QUIET", if quiet { "1" } else { "0" })
+        .env("SPRING_INIT_DRY_RUN", if dry_run { "1" } else { "0" });
+    if let Ok(exe) = env::current_exe() {
+        command.env("SPRING_INIT", exe);
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        let e = command.exec();
+        Err(color_eyre::eyre::eyre!(
+            "Failed to run {}: {}",
+            program.display(),
+            e
+        ))
+    }
+    #[cfg(not(unix))]
+    {
+        let status = command
+            .status()
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to run {}: {}", program.display(), e))?;
+        std::process::exit(status.code().unwrap_or(1));
+    }
+}
@@ -0,0 +1,480 @@
+use crate::ai::provider::{self, LlmProvider, Usage};
+use crate::ai::suggest::report_usage;
+use crate::{deps, progress, test, wrapper, ProjectConfig};
+use color_eyre::eyre::Result;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+/// How often a step may ask the AI provider to fix compile errors
+const MAX_FIX_ATTEMPTS: usize = 2;
+
+struct Step {
+    name: &'static str,
+    instructions: &'static str,
+}
+
+/// Generation steps in order, each one sees the files written by the ones
+/// before. Entities and repositories come first so `entities` can run just those
+const STEPS: &[Step] = &[
+    Step {
+        name: "entities",
+        instructions: "Write the JPA entities for the domain described above in the `model` \
+            subpackage, using jakarta.persistence annotations and a generated `Long` id.",
+    },
+    Step {
+        name: "repositories",
+        instructions: "Write a Spring Data JPA repository interface for each entity in the \
+            `repository` subpackage.",
+    },
+    Step {
+        name: "services",
+        instructions: "Write a service for each entity in the `service` subpackage, with the \
+            business logic the PRD describes. Inject repositories through the constructor.",
+    },
+    Step {
+        name: "controllers",
+        instructions: "Write REST controllers exposing the services in the `web` subpackage, \
+            returning appropriate HTTP status codes.",
+    },
+    Step {
+        name: "application.yml",
+        instructions: "Write src/main/resources/application.yml with the settings the \
+            application needs, such as spring.application.name, the datasource, JPA, and the \
+            server port. Don't write any other file.",
+    },
+];
+
+#[derive(Deserialize)]
+struct GeneratedFiles {
+    files: Vec<GeneratedFile>,
+}
+
+#[derive(Deserialize)]
+struct GeneratedFile {
+    path: String,
+    content: String,
+}
+
+fn files_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "files": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "path": {
+                            "type": "string",
+                            "description": "Path relative to the project root"
+                        },
+                        "content": {"type": "string"}
+                    },
+                    "required": ["path", "content"],
+                    "additionalProperties": false
+                }
+            }
+        },
+        "required": ["files"],
+        "additionalProperties": false
+    })
+}
+
+fn system_prompt(config: &ProjectConfig) -> String {
+    format!(
+        "You are an expert Spring Boot developer adding code to a project generated by \
+        start.spring.io. The project uses Spring Boot {}, {} {}, and the base package {}, so \
+        sources go under src/main/{}/{}. Only use libraries declared in the build file. Return \
+        complete files with paths relative to the project root.",
+        config.boot_version,
+        config.language.as_str(),
+        config.java_version,
+        config.package_name,
+        config.language.as_str(),
+        config.package_name.replace('.', "/"),
+    )
+}
+
+/// Resolve a path returned by the AI provider, refusing anything outside src/
+fn resolve(dir: &Path, path: &str) -> Result<PathBuf> {
+    let relative = Path::new(path);
+    let inside = relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+        && relative.starts_with("src");
+    if !inside {
+        return Err(color_eyre::eyre::eyre!(
+            "Refusing to write generated file outside src/: {}",
+            path
+        ));
+    }
+    Ok(dir.join(relative))
+}
+
+fn write_files(config: &ProjectConfig, files: &[GeneratedFile]) -> Result<()> {
+    let module_dir = config.module_dir();
+    for file in files {
+        let path = resolve(&module_dir, &file.path)?;
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, &file.content)?;
+        eprintln!("Wrote {}", file.path);
+    }
+    Ok(())
+}
+
+/// Compile the project, and its tests if `tests` is set, returning the
+/// build output when it fails
+fn compile(config: &ProjectConfig, tests: bool, quiet: bool) -> Result<Option<String>> {
+    let goal = match (config.build_tool.is_gradle(), tests) {
+        (true, false) => "classes",
+        (true, true) => "testClasses",
+        (false, false) => "compile",
+        (false, true) => "test-compile",
+    };
+    let program = wrapper::program(config);
+    let args = ["-q", goal];
+    let spinner = progress::spinner("Compiling...", quiet)?;
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(config.app_dir())
+        .output()?;
+    spinner.finish_and_clear();
+
+    if output.status.success() {
+        return Ok(None);
+    }
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Ok(Some(text))
+}
+
+/// Files concatenated for a prompt, each preceded by its path
+fn listing(files: &[GeneratedFile]) -> String {
+    files
+        .iter()
+        .map(|file| format!("--- {}\n{}\n", file.path, file.content))
+        .collect()
+}
+
+/// State shared by the steps of one generation run
+struct Session<'a> {
+    config: &'a ProjectConfig,
+    provider: Box<dyn LlmProvider>,
+    system_prompt: String,
+    /// What the code is generated from, e.g. the PRD
+    source: String,
+    written: Vec<GeneratedFile>,
+    total: Usage,
+    /// Compile test sources too, for steps that generate tests
+    tests: bool,
+    quiet: bool,
+}
+
+impl<'a> Session<'a> {
+    fn new(config: &'a ProjectConfig, source: String, quiet: bool) -> Result<Self> {
+        Ok(Self {
+            config,
+            provider: provider::new_provider(config)?,
+            system_prompt: system_prompt(config),
+            source,
+            written: Vec::new(),
+            total: Usage::default(),
+            tests: false,
+            quiet,
+        })
+    }
+
+    async fn request_files(
+        &mut self,
+        message: &str,
+        spinner_message: &str,
+    ) -> Result<Vec<GeneratedFile>> {
+        let spinner = progress::spinner(spinner_message, self.quiet)?;
+        let (value, usage) = self
+            .provider
+            .send_structured(&self.system_prompt, message, "write_files", &files_schema())
+            .await?;
+        spinner.finish_and_clear();
+        self.total.input_tokens += usage.input_tokens;
+        self.total.output_tokens += usage.output_tokens;
+
+        let generated: GeneratedFiles = serde_json::from_value(value)
+            .map_err(|e| color_eyre::eyre::eyre!("Unexpected generated files format: {}", e))?;
+        write_files(self.config, &generated.files)?;
+        Ok(generated.files)
+    }
+
+    /// Generate the files for one step, then compile and ask for fixes until
+    /// the project builds or `MAX_FIX_ATTEMPTS` is reached
+    async fn step(&mut self, name: &str, instructions: &str) -> Result<()> {
+        // Re-read so dependencies added since the last step are visible
+        let build_file = fs::read_to_string(self.config.build_file())?;
+        let message = format!(
+            "{}\n\nBuild file:\n{}\n\nFiles generated so far:\n{}\n\n{}",
+            self.source,
+            build_file,
+            listing(&self.written),
+            instructions
+        );
+        let files = self
+            .request_files(&message, &format!("Generating {}...", name))
+            .await?;
+        self.written.extend(files);
+
+        let mut attempts = 0;
+        while let Some(errors) = compile(self.config, self.tests, self.quiet)? {
+            if attempts == MAX_FIX_ATTEMPTS {
+                eprintln!("{}", errors);
+                return Err(color_eyre::eyre::eyre!(
+                    "Generated {} don't compile, see the errors above",
+                    name
+                ));
+            }
+            attempts += 1;
+            eprintln!(
+                "Compilation failed, asking for a fix ({}/{})",
+                attempts, MAX_FIX_ATTEMPTS
+            );
+
+            let message = format!(
+                "Build file:\n{}\n\nGenerated files:\n{}\n\nThe project fails to compile:\n{}\n\n\
+                Return corrected versions of the files that need changes.",
+                build_file,
+                listing(&self.written),
+                errors
+            );
+            let fixes = self
+                .request_files(&message, "Fixing compile errors...")
+                .await?;
+            for fix in fixes {
+                match self.written.iter_mut().find(|file| file.path == fix.path) {
+                    Some(file) => file.content = fix.content,
+                    None => self.written.push(fix),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self) {
+        eprintln!("Generated {} files", self.written.len());
+        if !self.quiet {
+            report_usage(self.config, self.provider.model(), &self.total);
+        }
+    }
+}
+
+/// Generate the application code described by the PRD into an initialized
+/// project, compiling after each step and feeding errors back for fixes
+pub async fn app(config: &ProjectConfig, prd_path: &str, quiet: bool) -> Result<()> {
+    let prd_content = fs::read_to_string(prd_path)?;
+    let mut session = Session::new(config, format!("PRD:\n{}", prd_content), quiet)?;
+    for step in STEPS {
+        session.step(step.name, step.instructions).await?;
+    }
+
+    // application.yml replaces the scaffold's properties file
+    let properties = config
+        .module_dir()
+        .join("src")
+        .join("main")
+        .join("resources")
+        .join("application.properties");
+    if session
+        .written
+        .iter()
+        .any(|file| file.path.ends_with("application.yml"))
+        && properties.exists()
+    {
+        fs::remove_file(properties)?;
+    }
+
+    session.finish();
+    Ok(())
+}
+
+/// Generate JPA entities and their repositories from a PRD or SQL DDL file
+pub async fn entities(
+    config: &ProjectConfig,
+    prd_path: Option<&str>,
+    schema_path: Option<&str>,
+    lombok: bool,
+    quiet: bool,
+) -> Result<()> {
+    let source = match (prd_path, schema_path) {
+        (Some(path), _) => format!("PRD:\n{}", fs::read_to_string(path)?),
+        (None, Some(path)) => format!(
+            "SQL schema, map each table to an entity and each foreign key to a relationship:\n{}",
+            fs::read_to_string(path)?
+        ),
+        (None, None) => {
+            return Err(color_eyre::eyre::eyre!(
+                "Either --prd or --schema is required"
+            ))
+        }
+    };
+
+    let mut ids = vec!["data-jpa".to_string()];
+    if lombok {
+        ids.push("lombok".to_string());
+    }
+    deps::add_dependencies(config, &ids).await?;
+
+    let mut session = Session::new(config, source, quiet)?;
+    for step in &STEPS[..2] {
+        let instructions = if lombok {
+            format!(
+                "{} Use Lombok (@Getter, @Setter, @NoArgsConstructor) instead of writing \
+                accessors and constructors by hand.",
+                step.instructions
+            )
+        } else {
+            step.instructions.to_string()
+        };
+        session.step(step.name, &instructions).await?;
+    }
+    session.finish();
+    Ok(())
+}
+
+/// File extension of sources in the configured language
+fn source_extension(config: &ProjectConfig) -> &'static str {
+    match config.language.as_str() {
+        "kotlin" => "kt",
+        other => other,
+    }
+}
+
+/// Source files under `dir`, with paths relative to the project root
+fn collect_sources(
+    config: &ProjectConfig,
+    dir: &Path,
+    sources: &mut Vec<GeneratedFile>,
+) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_sources(config, &path, sources)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext == source_extension(config))
+        {
+            let relative = path.strip_prefix(config.module_dir())?;
+            sources.push(GeneratedFile {
+                path: relative.to_string_lossy().into_owned(),
+                content: fs::read_to_string(&path)?,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Results of one test class from the JUnit XML reports
+/// Generate JUnit 5 tests for the project's sources, or only for `class`,
+/// then run them and report which pass
+pub async fn tests(config: &ProjectConfig, class: Option<&str>, quiet: bool) -> Result<()> {
+    let mut sources = Vec::new();
+    match class {
+        Some(class) => {
+            let root = config
+                .module_dir()
+                .join("src")
+                .join("main")
+                .join(config.language.as_str());
+            let path = class
+                .split('.')
+                .fold(root, |dir, segment| dir.join(segment))
+                .with_extension(source_extension(config));
+            if !path.exists() {
+                return Err(color_eyre::eyre::eyre!(
+                    "No source file for {} at {}",
+                    class,
+                    path.display()
+                ));
+            }
+            sources.push(GeneratedFile {
+                path: path
+                    .strip_prefix(config.module_dir())?
+                    .to_string_lossy()
+                    .into_owned(),
+                content: fs::read_to_string(&path)?,
+            });
+        }
+        None => collect_sources(config, &config.source_dir(), &mut sources)?,
+    }
+
+    let mut session = Session::new(
+        config,
+        format!("Source files to test:\n{}", listing(&sources)),
+        quiet,
+    )?;
+    session.tests = true;
+    session
+        .step(
+            "tests",
+            &format!(
+                "Write JUnit 5 tests for these classes under src/test/{}, in the same package as \
+                the class under test and named after it with a `Test` suffix. Use MockMvc \
+                (@WebMvcTest) for controllers and Mockito for the collaborators of other classes.",
+                config.language.as_str()
+            ),
+        )
+        .await?;
+
+    // Test classes by fully qualified name, e.g. com.acme.FooServiceTest
+    let test_root = format!("src/test/{}/", config.language.as_str());
+    let classes: Vec<String> = session
+        .written
+        .iter()
+        .filter_map(|file| {
+            let name = file.path.strip_prefix(&test_root)?;
+            let name = name.rsplit_once('.')?.0;
+            Some(name.replace('/', "."))
+        })
+        .collect();
+
+    let program = wrapper::program(config);
+    let spinner = progress::spinner("Running tests...", quiet)?;
+    // Failing tests are reported below, the exit status adds nothing
+    Command::new(program)
+        .arg("test")
+        .current_dir(config.app_dir())
+        .output()?;
+    spinner.finish_and_clear();
+
+    let reports = test::suite_reports(config);
+    for class in &classes {
+        match reports.iter().find(|report| &report.class == class) {
+            Some(report) if report.failures.is_empty() => {
+                println!("[pass] {} ({} tests)", class, report.tests)
+            }
+            Some(report) => println!(
+                "[fail] {} ({} of {} tests failed)",
+                class,
+                report.failures.len(),
+                report.tests
+            ),
+            None => println!("[fail] {} (no test report)", class),
+        }
+    }
+
+    session.finish();
+    let failed = classes
+        .iter()
+        .filter(|class| {
+            !reports
+                .iter()
+                .any(|report| &report.class == *class && report.failures.is_empty())
+        })
+        .count();
+    if failed > 0 {
+        return Err(color_eyre::eyre::eyre!(
+            "{} of {} generated test classes failed",
+            failed,
+            classes.len()
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,334 @@
+use crate::http::Http;
+use crate::ProjectConfig;
+use clap::ValueEnum;
+use color_eyre::eyre::Result;
+use git2::{
+    BranchType, Cred, IndexAddOption, ProxyOptions, PushOptions, RemoteCallbacks, Repository,
+    Signature,
+};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Entries every generated project's .gitignore should have
+const IGNORED: &[&str] = &[
+    "target/",
+    "build/",
+    ".gradle/",
+    "*.iml",
+    ".idea/",
+    ".spring-init/",
+];
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum License {
+    #[serde(rename = "mit")]
+    #[value(name = "mit")]
+    Mit,
+    #[serde(rename = "apache-2.0")]
+    #[value(name = "apache-2.0")]
+    Apache2,
+}
+
+impl License {
+    fn text(self, holder: &str) -> String {
+        match self {
+            License::Mit => include_str!("../assets/licenses/MIT.txt")
+                .replace("{year}", &current_year().to_string())
+                .replace("{holder}", holder),
+            License::Apache2 => include_str!("../assets/licenses/Apache-2.0.txt").to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct GitConfig {
+    /// Initialize a repository with an initial commit after init
+    #[serde(default)]
+    pub init: bool,
+    /// License added to the repository
+    #[serde(default)]
+    pub license: Option<License>,
+}
+
+/// Gregorian year of the current date in UTC
+fn current_year() -> i64 {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+    // Days to civil date, counting eras of 400 years from 0000-03-01
+    let days = secs / 86_400 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let year = year_of_era + era * 400;
+    // Months are counted from March, so January and February belong to the next year
+    if month >= 10 {
+        year + 1
+    } else {
+        year
+    }
+}
+
+/// Add the entries from `IGNORED` that the scaffold's .gitignore lacks
+fn write_gitignore(config: &ProjectConfig) -> Result<()> {
+    let path = config.app_dir().join(".gitignore");
+    let mut content = fs::read_to_string(&path).unwrap_or_default();
+    let missing: Vec<&str> = IGNORED
+        .iter()
+        .copied()
+        .filter(|entry| !content.lines().any(|line| line.trim() == *entry))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    for entry in missing {
+        content.push_str(entry);
+        content.push('\n');
+    }
+    fs::write(&path, content)?;
+    Ok(())
+}
+
+/// Initialize a repository in the project directory, add .gitignore and an
+/// optional LICENSE, and commit everything
+pub fn bootstrap(config: &ProjectConfig, license: Option<License>) -> Result<()> {
+    let app_dir = config.app_dir();
+    let repo = match Repository::open(&app_dir) {
+        Ok(repo) => repo,
+        Err(_) => Repository::init(&app_dir)?,
+    };
+
+    // Use the committer from the git config when there is one
+    let configured = repo.signature().ok();
+
+    write_gitignore(config)?;
+    if let Some(license) = license {
+        let holder = configured
+            .as_ref()
+            .and_then(|signature| signature.name())
+            .unwrap_or(&config.app_name);
+        fs::write(app_dir.join("LICENSE"), license.text(holder))?;
+    }
+
+    let mut index = repo.index()?;
+    index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+    index.write()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    let signature = match configured {
+        Some(signature) => signature,
+        None => Signature::now("spring-init", "spring-init@localhost")?,
+    };
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        "Initial commit",
+        &tree,
+        &parents,
+    )?;
+
+    eprintln!("Initialized git repository in {}", app_dir.display());
+    Ok(())
+}
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+#[derive(Deserialize)]
+struct GithubUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct GithubRepo {
+    clone_url: String,
+    html_url: String,
+}
+
+#[derive(Deserialize)]
+struct GithubError {
+    message: String,
+}
+
+fn split_repo_name(full_name: &str) -> Result<(&str, &str)> {
+    full_name
+        .split_once('/')
+        .filter(|(owner, name)| !owner.is_empty() && !name.is_empty() && !name.contains('/'))
+        .ok_or_else(|| {
+            color_eyre::eyre::eyre!("Expected a repository as owner/name, got {}", full_name)
+        })
+}
+
+/// Fail before generating anything when the repository can't be created
+pub fn check_github(full_name: &str) -> Result<()> {
+    split_repo_name(full_name)?;
+    github_token()?;
+    Ok(())
+}
+
+/// Keyring service the GitHub token is stored under, next to the API keys
+const SERVICE: &str = "spring-init";
+
+fn entry() -> Result<keyring::Entry> {
+    keyring::Entry::new(SERVICE, "github")
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to open the OS keyring: {}", e))
+}
+
+/// Token for the GitHub API, from the OS keyring, then GITHUB_TOKEN or GH_TOKEN
+fn github_token() -> Result<String> {
+    // An unavailable keyring (e.g. no secret service on a CI runner) falls through to the environment
+    if let Some(token) = entry().ok().and_then(|entry| entry.get_password().ok()) {
+        return Ok(token);
+    }
+    env::var("GITHUB_TOKEN")
+        .or_else(|_| env::var("GH_TOKEN"))
+        .ok()
+        .filter(|token| !token.trim().is_empty())
+        .ok_or_else(|| {
+            color_eyre::eyre::eyre!(
+                "No GitHub token, run `spring-init auth login github` or set GITHUB_TOKEN or GH_TOKEN"
+            )
+        })
+}
+
+/// Store a GitHub token in the OS keyring, read without echo or from stdin
+pub fn login() -> Result<()> {
+    let token = if io::stdin().is_terminal() {
+        inquire::Password::new("GitHub token:")
+            .without_confirmation()
+            .prompt()?
+    } else {
+        let mut line = String::new();
+        io::stdin().lock().read_line(&mut line)?;
+        line
+    };
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(color_eyre::eyre::eyre!("No GitHub token given"));
+    }
+    entry()?
+        .set_password(token)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to store the GitHub token: {}", e))?;
+    println!("Stored the GitHub token in the OS keyring");
+    Ok(())
+}
+
+/// Remove the GitHub token from the OS keyring
+pub fn logout() -> Result<()> {
+    match entry()?.delete_credential() {
+        Ok(()) => println!("Removed the GitHub token from the OS keyring"),
+        Err(keyring::Error::NoEntry) => println!("No GitHub token in the OS keyring"),
+        Err(e) => {
+            return Err(color_eyre::eyre::eyre!(
+                "Failed to remove the GitHub token: {}",
+                e
+            ))
+        }
+    }
+    Ok(())
+}
+
+/// Create `owner/name` on GitHub, as a user repository when `owner` is the
+/// authenticated user and an organization repository otherwise
+async fn create_github_repo(
+    config: &ProjectConfig,
+    token: &str,
+    full_name: &str,
+    private: bool,
+) -> Result<GithubRepo> {
+    let (owner, name) = split_repo_name(full_name)?;
+
+    let http = Http::new(config)?;
+    let user: GithubUser = http
+        .send(|client| {
+            client
+                .get(format!("{}/user", GITHUB_API_URL))
+                .bearer_auth(token)
+        })
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let url = if user.login.eq_ignore_ascii_case(owner) {
+        format!("{}/user/repos", GITHUB_API_URL)
+    } else {
+        format!("{}/orgs/{}/repos", GITHUB_API_URL, owner)
+    };
+
+    let response = http
+        .send(|client| {
+            client
+                .post(&url)
+                .bearer_auth(token)
+                .json(&serde_json::json!({"name": name, "private": private}))
+        })
+        .await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let message = response
+            .json::<GithubError>()
+            .await
+            .map(|error| error.message)
+            .unwrap_or_default();
+        return Err(color_eyre::eyre::eyre!(
+            "Failed to create GitHub repository {} ({}): {}",
+            full_name,
+            status,
+            message
+        ));
+    }
+    Ok(response.json().await?)
+}
+
+/// Create the GitHub repository, add it as `origin`, and push the current branch
+pub async fn publish_github(config: &ProjectConfig, full_name: &str, private: bool) -> Result<()> {
+    let token = github_token()?;
+    let github_repo = create_github_repo(config, &token, full_name, private).await?;
+    eprintln!("Created {}", github_repo.html_url);
+
+    let repo = Repository::open(config.app_dir())?;
+    let mut remote = match repo.find_remote("origin") {
+        Ok(_) => {
+            repo.remote_set_url("origin", &github_repo.clone_url)?;
+            repo.find_remote("origin")?
+        }
+        Err(_) => repo.remote("origin", &github_repo.clone_url)?,
+    };
+
+    let head = repo.head()?;
+    let branch = head
+        .shorthand()
+        .ok_or_else(|| color_eyre::eyre::eyre!("HEAD is not on a branch"))?
+        .to_string();
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(|_, _, _| Cred::userpass_plaintext("x-access-token", &token));
+    let mut proxy = ProxyOptions::new();
+    match &config.network.proxy {
+        Some(url) => proxy.url(url),
+        None => proxy.auto(),
+    };
+    let mut options = PushOptions::new();
+    options.remote_callbacks(callbacks);
+    options.proxy_options(proxy);
+    remote.push(
+        &[format!("refs/heads/{0}:refs/heads/{0}", branch)],
+        Some(&mut options),
+    )?;
+
+    // Track the remote branch so plain `git push` and `git pull` work
+    repo.find_branch(&branch, BranchType::Local)?
+        .set_upstream(Some(&format!("origin/{}", branch)))?;
+    eprintln!("Pushed {} to {}", branch, github_repo.html_url);
+    Ok(())
+}
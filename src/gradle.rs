@@ -0,0 +1,231 @@
+use color_eyre::eyre::Result;
+
+/// Whether `line` declares `group:artifact`, with or without a version
+fn declares(line: &str, group_artifact: &str) -> bool {
+    ['\'', '"'].iter().any(|quote| {
+        line.contains(&format!("{}{}{}", quote, group_artifact, quote))
+            || line.contains(&format!("{}{}:", quote, group_artifact))
+    })
+}
+
+/// Line range of the top-level `name { ... }` block, excluding the opening
+/// and closing lines
+fn block(lines: &[&str], name: &str) -> Option<(usize, usize)> {
+    let opening = format!("{} {{", name);
+    let start = lines.iter().position(|line| line.trim_end() == opening)?;
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| line.trim_end() == "}")?;
+    Some((start + 1, start + 1 + end))
+}
+
+/// Leading whitespace of the first non-blank line, or a tab
+fn indent<'a>(lines: &[&'a str]) -> &'a str {
+    lines
+        .iter()
+        .find(|line| !line.trim().is_empty())
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .unwrap_or("\t")
+}
+
+fn join(lines: &[String], trailing_newline: bool) -> String {
+    let mut content = lines.join("\n");
+    if trailing_newline {
+        content.push('\n');
+    }
+    content
+}
+
+/// Add a declaration for `group:artifact[:version]` to each configuration,
+/// returning `None` when the build file already declares it
+pub fn add_dependency(
+    content: &str,
+    kotlin: bool,
+    configurations: &[&str],
+    notation: &str,
+) -> Result<Option<String>> {
+    let lines: Vec<&str> = content.lines().collect();
+    let (start, end) = block(&lines, "dependencies").ok_or_else(|| {
+        color_eyre::eyre::eyre!("Could not find a top-level dependencies block in the build file")
+    })?;
+
+    let group_artifact = notation
+        .splitn(3, ':')
+        .take(2)
+        .collect::<Vec<_>>()
+        .join(":");
+    if lines[start..end]
+        .iter()
+        .any(|line| declares(line, &group_artifact))
+    {
+        return Ok(None);
+    }
+
+    let indent = indent(&lines[start..end]);
+
+    let mut updated: Vec<String> = lines[..end].iter().map(|line| line.to_string()).collect();
+    for configuration in configurations {
+        updated.push(if kotlin {
+            format!("{}{}(\"{}\")", indent, configuration, notation)
+        } else {
+            format!("{}{} '{}'", indent, configuration, notation)
+        });
+    }
+    updated.extend(lines[end..].iter().map(|line| line.to_string()));
+
+    Ok(Some(join(&updated, content.ends_with('\n'))))
+}
+
+/// Apply the plugin `id` at `version` in the `plugins` block, returning `None`
+/// when the build file already applies it
+pub fn add_plugin(content: &str, kotlin: bool, id: &str, version: &str) -> Result<Option<String>> {
+    let lines: Vec<&str> = content.lines().collect();
+    let (start, end) = block(&lines, "plugins").ok_or_else(|| {
+        color_eyre::eyre::eyre!("Could not find a plugins block in the build file")
+    })?;
+
+    if lines[start..end]
+        .iter()
+        .any(|line| line.contains(&format!("'{}'", id)) || line.contains(&format!("\"{}\"", id)))
+    {
+        return Ok(None);
+    }
+
+    let indent = indent(&lines[start..end]);
+    let mut updated: Vec<String> = lines[..end].iter().map(|line| line.to_string()).collect();
+    updated.push(if kotlin {
+        format!("{}id(\"{}\") version \"{}\"", indent, id, version)
+    } else {
+        format!("{}id '{}' version '{}'", indent, id, version)
+    });
+    updated.extend(lines[end..].iter().map(|line| line.to_string()));
+
+    Ok(Some(join(&updated, content.ends_with('\n'))))
+}
+
+/// Remove every declaration of `group:artifact`, returning `None` when the
+/// build file doesn't declare it
+pub fn remove_dependency(content: &str, group_artifact: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let (start, end) = block(&lines, "dependencies")?;
+
+    let mut removed = false;
+    let mut updated = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if (start..end).contains(&i) && declares(line, group_artifact) {
+            removed = true;
+        } else {
+            updated.push(line.to_string());
+        }
+    }
+
+    removed.then(|| join(&updated, content.ends_with('\n')))
+}
+
+/// First quoted string on `line`
+fn quoted(line: &str) -> Option<&str> {
+    let start = line.find(['\'', '"'])?;
+    let quote = line[start..].chars().next()?;
+    let rest = &line[start + 1..];
+    rest.find(quote).map(|end| &rest[..end])
+}
+
+/// Version the `plugins` block applies plugin `id` at
+pub fn plugin_version(content: &str, id: &str) -> Option<String> {
+    content
+        .lines()
+        .filter(|line| quoted(line) == Some(id))
+        .find_map(|line| {
+            line.split_once(" version ")
+                .and_then(|(_, version)| quoted(version))
+        })
+        .map(str::to_string)
+}
+
+/// Change the version the `plugins` block applies plugin `id` at, returning
+/// `None` when the build file doesn't apply it with a version
+pub fn set_plugin_version(content: &str, id: &str, version: &str) -> Option<String> {
+    let current = plugin_version(content, id)?;
+    let mut changed = false;
+    let updated: Vec<String> = content
+        .lines()
+        .map(|line| match line.split_once(" version ") {
+            Some((plugin, rest)) if quoted(plugin) == Some(id) => {
+                changed = true;
+                format!("{} version {}", plugin, rest.replacen(&current, version, 1))
+            }
+            _ => line.to_string(),
+        })
+        .collect();
+    changed.then(|| join(&updated, content.ends_with('\n')))
+}
+
+/// Value of a top-level `name = 'value'` assignment, e.g. `group` or
+/// `rootProject.name`
+pub fn assignment(content: &str, name: &str) -> Option<String> {
+    content
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == name)
+        .and_then(|(_, value)| quoted(value))
+        .map(str::to_string)
+}
+
+/// Java version from a toolchain or sourceCompatibility declaration
+pub fn java_version(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let line = line.trim();
+        let version = if let Some((_, rest)) = line.split_once("JavaLanguageVersion.of(") {
+            rest.split(')').next()?
+        } else if let Some((_, rest)) = line.split_once("JavaVersion.VERSION_") {
+            rest.split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .next()?
+        } else if line.starts_with("sourceCompatibility") {
+            let (_, value) = line.split_once('=')?;
+            quoted(value).unwrap_or(value.trim())
+        } else {
+            return None;
+        };
+        let version = version.trim().trim_start_matches("1_").replace('_', ".");
+        (!version.is_empty()).then_some(version)
+    })
+}
+
+/// IDs of the plugins applied in the `plugins` block, `kotlin("jvm")` is
+/// reported as `org.jetbrains.kotlin.jvm`
+pub fn plugins(content: &str) -> Vec<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let Some((start, end)) = block(&lines, "plugins") else {
+        return Vec::new();
+    };
+    lines[start..end]
+        .iter()
+        .filter_map(|line| {
+            let id = quoted(line)?;
+            Some(if line.trim_start().starts_with("kotlin(") {
+                format!("org.jetbrains.kotlin.{}", id)
+            } else {
+                id.to_string()
+            })
+        })
+        .collect()
+}
+
+/// `group:artifact` of every dependency declared in the top-level
+/// dependencies block
+pub fn dependencies(content: &str) -> Vec<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let Some((start, end)) = block(&lines, "dependencies") else {
+        return Vec::new();
+    };
+    lines[start..end]
+        .iter()
+        .filter_map(|line| quoted(line))
+        .filter_map(|notation| {
+            let mut parts = notation.split(':');
+            let group = parts.next()?;
+            let artifact = parts.next()?;
+            Some(format!("{}:{}", group, artifact))
+        })
+        .collect()
+}
@@ -0,0 +1,118 @@
+use crate::{lock, ProjectConfig};
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+
+/// Commands run in the app dir at points of the project lifecycle, through
+/// `sh -c` (`cmd /C` on Windows)
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct HooksConfig {
+    /// After init has generated and configured the project, before the
+    /// initial commit
+    #[serde(default)]
+    pub post_init: Vec<String>,
+    /// Before every build, including the ones of run and upgrade
+    #[serde(default)]
+    pub pre_build: Vec<String>,
+    /// After every successful build
+    #[serde(default)]
+    pub post_build: Vec<String>,
+}
+
+#[derive(Clone, Copy)]
+pub enum Hook {
+    PostInit,
+    PreBuild,
+    PostBuild,
+}
+
+impl Hook {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Hook::PostInit => "post_init",
+            Hook::PreBuild => "pre_build",
+            Hook::PostBuild => "post_build",
+        }
+    }
+
+    fn commands<'a>(&self, config: &'a ProjectConfig) -> &'a [String] {
+        match self {
+            Hook::PostInit => &config.hooks.post_init,
+            Hook::PreBuild => &config.hooks.pre_build,
+            Hook::PostBuild => &config.hooks.post_build,
+        }
+    }
+}
+
+fn shell(script: &str) -> Command {
+    if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.arg("/C").arg(script);
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(script);
+        command
+    }
+}
+
+/// Initializr IDs of the project's dependencies, from its lockfile when it
+/// has one
+fn dependency_ids(config: &ProjectConfig) -> Vec<String> {
+    lock::read(&config.app_dir().join(lock::FILE_NAME))
+        .map(|lockfile| lockfile.dependencies)
+        .unwrap_or_default()
+}
+
+/// Run the commands configured for `hook` in order, stopping at the first
+/// one that fails. `dependencies` are the project's Initializr IDs, read
+/// from the lockfile when not given.
+pub fn run(
+    config: &ProjectConfig,
+    hook: Hook,
+    dependencies: Option<&[String]>,
+    dry_run: bool,
+) -> Result<()> {
+    let commands = hook.commands(config);
+    if commands.is_empty() {
+        return Ok(());
+    }
+    if dry_run {
+        for script in commands {
+            println!("Would run {} hook: {}", hook.as_str(), script);
+        }
+        return Ok(());
+    }
+
+    let dependencies = match dependencies {
+        Some(dependencies) => dependencies.to_vec(),
+        None => dependency_ids(config),
+    };
+    // Absolute, since the commands run from inside it
+    let app_dir = config.app_dir();
+    let app_dir = app_dir.canonicalize().unwrap_or(app_dir);
+    for script in commands {
+        eprintln!("Running {} hook: {}", hook.as_str(), script);
+        let status = shell(script)
+            .current_dir(&app_dir)
+            .env("APP_NAME", &config.app_name)
+            .env("APP_VERSION", config.version())
+            .env("APP_DIR", &app_dir)
+            .env("PACKAGE_NAME", &config.package_name)
+            .env("BUILD_TOOL", config.build_tool.project_type())
+            .env("DEPS", dependencies.join(","))
+            .env("HOOK", hook.as_str())
+            .status()
+            .map_err(|e| {
+                color_eyre::eyre::eyre!("Failed to run {} hook {}: {}", hook.as_str(), script, e)
+            })?;
+        if !status.success() {
+            return Err(color_eyre::eyre::eyre!(
+                "{} hook failed: {}",
+                hook.as_str(),
+                script
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,287 @@
+use crate::profiles::Environment;
+use crate::project::Language;
+use crate::{jdk, ProjectConfig};
+use clap::ValueEnum;
+use color_eyre::eyre::Result;
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Target {
+    /// IntelliJ IDEA, .run/*.run.xml
+    Intellij,
+    /// VS Code, .vscode/launch.json and .vscode/settings.json
+    Vscode,
+}
+
+/// A run configuration, one for the plain app and one per environment
+struct Launch {
+    name: String,
+    /// Suffix for the IntelliJ file name
+    file: String,
+    vm_args: Vec<String>,
+}
+
+/// Fully qualified name of the `@SpringBootApplication` class under `dir`.
+/// Kotlin compiles the top-level `main` into a `<File>Kt` class.
+fn find_main_class(dir: &Path, language: Language) -> Option<String> {
+    for entry in fs::read_dir(dir).ok()?.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        if path.is_dir() {
+            if let Some(class) = find_main_class(&path, language) {
+                return Some(class);
+            }
+            continue;
+        }
+        let Ok(source) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if !source.contains("@SpringBootApplication") {
+            continue;
+        }
+        let package = source.lines().find_map(|line| {
+            line.trim()
+                .strip_prefix("package ")
+                .map(|package| package.trim_end_matches(';').trim().to_string())
+        })?;
+        let stem = path.file_stem()?.to_string_lossy();
+        let class = match language {
+            Language::Kotlin => format!("{}Kt", stem),
+            _ => stem.to_string(),
+        };
+        return Some(format!("{}.{}", package, class));
+    }
+    None
+}
+
+fn launches(config: &ProjectConfig, profile: Option<&str>, jvm_args: &[String]) -> Vec<Launch> {
+    let launch = |name: String, file: String, environment: Option<Environment>| {
+        // The environment's profile comes first so --profile can override it,
+        // as with `run --env`
+        let profiles: Vec<&str> = environment
+            .map(|environment| environment.as_str())
+            .into_iter()
+            .chain(profile)
+            .collect();
+        let mut vm_args = jvm_args.to_vec();
+        if !profiles.is_empty() {
+            vm_args.push(format!("-Dspring.profiles.active={}", profiles.join(",")));
+        }
+        Launch {
+            name,
+            file,
+            vm_args,
+        }
+    };
+
+    let mut launches = vec![launch(
+        config.app_name.clone(),
+        config.app_name.clone(),
+        None,
+    )];
+    if config.env_profiles {
+        for environment in Environment::ALL {
+            launches.push(launch(
+                format!("{} ({})", config.app_name, environment.as_str()),
+                format!("{}-{}", config.app_name, environment.as_str()),
+                Some(environment),
+            ));
+        }
+    }
+    launches
+}
+
+fn attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Name of the configuration attaching a debugger to `run --debug`
+fn attach_name(config: &ProjectConfig) -> String {
+    format!("Attach to {}", config.app_name)
+}
+
+/// IntelliJ module of the runnable application, named after its directory
+fn module_name(config: &ProjectConfig) -> String {
+    config
+        .module_dir()
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| config.app_name.clone())
+}
+
+fn intellij(config: &ProjectConfig, launch: &Launch, main_class: &str) -> String {
+    format!(
+        "<component name=\"ProjectRunConfigurationManager\">
+  <configuration default=\"false\" name=\"{name}\" type=\"Application\" factoryName=\"Application\">
+    <option name=\"MAIN_CLASS_NAME\" value=\"{main_class}\" />
+    <module name=\"{module}\" />
+    <option name=\"VM_PARAMETERS\" value=\"{vm_args}\" />
+    <option name=\"WORKING_DIRECTORY\" value=\"$PROJECT_DIR$\" />
+    <method v=\"2\">
+      <option name=\"Make\" enabled=\"true\" />
+    </method>
+  </configuration>
+</component>
+",
+        name = attribute(&launch.name),
+        main_class = attribute(main_class),
+        module = attribute(&module_name(config)),
+        vm_args = attribute(&launch.vm_args.join(" ")),
+    )
+}
+
+/// Remote JVM Debug configuration for the app started with `run --debug`
+fn intellij_attach(config: &ProjectConfig, debug_port: u16) -> String {
+    format!(
+        "<component name=\"ProjectRunConfigurationManager\">
+  <configuration default=\"false\" name=\"{name}\" type=\"Remote\">
+    <module name=\"{module}\" />
+    <option name=\"USE_SOCKET_TRANSPORT\" value=\"true\" />
+    <option name=\"SERVER_MODE\" value=\"false\" />
+    <option name=\"HOST\" value=\"localhost\" />
+    <option name=\"PORT\" value=\"{port}\" />
+    <option name=\"AUTO_RESTART\" value=\"false\" />
+    <method v=\"2\" />
+  </configuration>
+</component>
+",
+        name = attribute(&attach_name(config)),
+        module = attribute(&module_name(config)),
+        port = debug_port,
+    )
+}
+
+fn vscode_launch(
+    config: &ProjectConfig,
+    launches: &[Launch],
+    main_class: &str,
+    debug_port: u16,
+) -> String {
+    let mut configurations: Vec<serde_json::Value> = launches
+        .iter()
+        .map(|launch| {
+            json!({
+                "type": "java",
+                "name": launch.name,
+                "request": "launch",
+                "mainClass": main_class,
+                "projectName": module_name(config),
+                "cwd": "${workspaceFolder}",
+                "vmArgs": launch.vm_args.join(" "),
+            })
+        })
+        .collect();
+    configurations.push(json!({
+        "type": "java",
+        "name": attach_name(config),
+        "request": "attach",
+        "hostName": "localhost",
+        "port": debug_port,
+        "projectName": module_name(config),
+    }));
+    let value = json!({
+        "version": "0.2.0",
+        "configurations": configurations,
+    });
+    format!("{}\n", serde_json::to_string_pretty(&value).unwrap())
+}
+
+fn vscode_settings(config: &ProjectConfig) -> String {
+    let mut value = json!({
+        "java.configuration.updateBuildConfiguration": "automatic",
+    });
+    if config.lombok {
+        value["java.jdt.ls.lombokSupport.enabled"] = json!(true);
+    }
+    // Point the Java extension at the JDK `jdk install` manages, if any
+    if let Some(home) = jdk::managed_home(&config.java_version) {
+        value["java.configuration.runtimes"] = json!([{
+            "name": format!("JavaSE-{}", config.java_version),
+            "path": home,
+            "default": true,
+        }]);
+    }
+    format!("{}\n", serde_json::to_string_pretty(&value).unwrap())
+}
+
+/// Write run configurations for `target`, launching the application class
+/// with `profile` and `jvm_args`, plus one per environment with `env_profiles`
+/// and one attaching to `run --debug` on `debug_port`
+pub fn write(
+    config: &ProjectConfig,
+    target: Target,
+    profile: Option<&str>,
+    jvm_args: &[String],
+    debug_port: u16,
+    force: bool,
+    dry_run: bool,
+) -> Result<()> {
+    if !config.build_file().exists() {
+        return Err(color_eyre::eyre::eyre!(
+            "No {} found, run init first",
+            config.build_file().display()
+        ));
+    }
+    let main_class = find_main_class(&config.source_dir(), config.language).ok_or_else(|| {
+        color_eyre::eyre::eyre!(
+            "No @SpringBootApplication class found in {}",
+            config.source_dir().display()
+        )
+    })?;
+
+    let app_dir = config.app_dir();
+    let launches = launches(config, profile, jvm_args);
+    let files: Vec<(PathBuf, String)> = match target {
+        Target::Intellij => launches
+            .iter()
+            .map(|launch| {
+                (
+                    app_dir
+                        .join(".run")
+                        .join(format!("{}.run.xml", launch.file)),
+                    intellij(config, launch, &main_class),
+                )
+            })
+            .chain([(
+                app_dir
+                    .join(".run")
+                    .join(format!("{}-attach.run.xml", config.app_name)),
+                intellij_attach(config, debug_port),
+            )])
+            .collect(),
+        Target::Vscode => vec![
+            (
+                app_dir.join(".vscode").join("launch.json"),
+                vscode_launch(config, &launches, &main_class, debug_port),
+            ),
+            (
+                app_dir.join(".vscode").join("settings.json"),
+                vscode_settings(config),
+            ),
+        ],
+    };
+    if dry_run {
+        for (path, _) in &files {
+            println!("Would write {}", path.display());
+        }
+        return Ok(());
+    }
+    if let Some((path, _)) = files.iter().find(|(path, _)| path.exists() && !force) {
+        return Err(color_eyre::eyre::eyre!(
+            "{} already exists, use --force to overwrite it",
+            path.display()
+        ));
+    }
+
+    for (path, content) in &files {
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, content)?;
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
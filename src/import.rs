@@ -0,0 +1,289 @@
+use crate::pom::Pom;
+use crate::projects::Registry;
+use crate::{gradle, initializr, BuildTool, Language, Packaging, ProjectConfig};
+use color_eyre::eyre::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Settings read from a project's build file
+pub struct BuildSettings {
+    /// artifactId, or the Gradle root project name
+    pub artifact_id: String,
+    pub group_id: Option<String>,
+    pub version: Option<String>,
+    pub boot_version: Option<String>,
+    pub java_version: Option<String>,
+    pub packaging: Packaging,
+    /// `group:artifact` of every declared dependency
+    pub dependencies: Vec<String>,
+    /// `group:artifact` of Maven plugins, or Gradle plugin IDs
+    pub plugins: Vec<String>,
+}
+
+/// Read the settings from the build file of the project in `dir`
+pub fn read_build_settings(build_tool: BuildTool, dir: &Path) -> Result<BuildSettings> {
+    match build_tool {
+        BuildTool::Maven => read_pom(&dir.join("pom.xml")),
+        BuildTool::Gradle => read_gradle(dir, &dir.join("build.gradle")),
+        BuildTool::GradleKotlin => read_gradle(dir, &dir.join("build.gradle.kts")),
+    }
+}
+
+/// Replace a `${name}` reference with the pom property it names
+pub(crate) fn resolve(pom: &Pom, value: Option<String>) -> Result<Option<String>> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let Some(name) = value
+        .strip_prefix("${")
+        .and_then(|rest| rest.strip_suffix('}'))
+    else {
+        return Ok(Some(value));
+    };
+    match name {
+        "project.version" => Ok(pom
+            .text(&["version"])?
+            .or(pom.text(&["parent", "version"])?)),
+        "project.parent.version" => pom.text(&["parent", "version"]),
+        _ => pom.text(&["properties", name]),
+    }
+}
+
+fn read_pom(path: &Path) -> Result<BuildSettings> {
+    let pom = Pom::load(path)?;
+    let artifact_id = pom
+        .text(&["artifactId"])?
+        .ok_or_else(|| color_eyre::eyre::eyre!("{} has no artifactId", path.display()))?;
+    let group_id = pom
+        .text(&["groupId"])?
+        .or(pom.text(&["parent", "groupId"])?);
+    let version = resolve(&pom, pom.text(&["version"])?)?.or(pom.text(&["parent", "version"])?);
+
+    // The Boot parent, else the imported spring-boot-dependencies BOM
+    let boot_version =
+        if pom.text(&["parent", "artifactId"])?.as_deref() == Some("spring-boot-starter-parent") {
+            pom.text(&["parent", "version"])?
+        } else {
+            let bom = pom
+                .coordinates(&["dependencyManagement", "dependencies"], "dependency")?
+                .into_iter()
+                .find(|bom| bom.artifact_id == "spring-boot-dependencies");
+            resolve(&pom, bom.and_then(|bom| bom.version))?
+        };
+
+    let mut java_version = None;
+    for property in [
+        "java.version",
+        "maven.compiler.release",
+        "maven.compiler.source",
+    ] {
+        java_version = resolve(&pom, pom.text(&["properties", property])?)?;
+        if java_version.is_some() {
+            break;
+        }
+    }
+
+    let packaging = match pom.text(&["packaging"])?.as_deref() {
+        Some("war") => Packaging::War,
+        _ => Packaging::Jar,
+    };
+
+    let dependencies = pom
+        .coordinates(&["dependencies"], "dependency")?
+        .iter()
+        .map(|dependency| format!("{}:{}", dependency.group_id, dependency.artifact_id))
+        .collect();
+    let plugins = pom
+        .coordinates(&["build", "plugins"], "plugin")?
+        .iter()
+        .map(|plugin| format!("{}:{}", plugin.group_id, plugin.artifact_id))
+        .collect();
+
+    Ok(BuildSettings {
+        artifact_id,
+        group_id,
+        version,
+        boot_version,
+        java_version,
+        packaging,
+        dependencies,
+        plugins,
+    })
+}
+
+fn read_gradle(dir: &Path, build_file: &Path) -> Result<BuildSettings> {
+    let content = fs::read_to_string(build_file)?;
+    // Gradle names the jar after the root project, which defaults to the directory name
+    let artifact_id = ["settings.gradle", "settings.gradle.kts"]
+        .iter()
+        .filter_map(|name| fs::read_to_string(dir.join(name)).ok())
+        .find_map(|settings| gradle::assignment(&settings, "rootProject.name"))
+        .or_else(|| {
+            dir.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        })
+        .ok_or_else(|| color_eyre::eyre::eyre!("Could not determine the project name"))?;
+    let plugins = gradle::plugins(&content);
+    // The Kotlin DSL applies it unquoted, as `war`
+    let war = plugins.iter().any(|plugin| plugin == "war")
+        || content.lines().any(|line| line.trim() == "war");
+
+    Ok(BuildSettings {
+        artifact_id,
+        group_id: gradle::assignment(&content, "group"),
+        version: gradle::assignment(&content, "version"),
+        boot_version: gradle::plugin_version(&content, "org.springframework.boot"),
+        java_version: gradle::java_version(&content),
+        packaging: if war { Packaging::War } else { Packaging::Jar },
+        dependencies: gradle::dependencies(&content),
+        plugins,
+    })
+}
+
+/// Package of the `@SpringBootApplication` class under `dir`
+fn find_application_package(dir: &Path) -> Result<Option<String>> {
+    if !dir.is_dir() {
+        return Ok(None);
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if let Some(package) = find_application_package(&path)? {
+                return Ok(Some(package));
+            }
+            continue;
+        }
+        let Ok(source) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if !source.contains("@SpringBootApplication") {
+            continue;
+        }
+        let package = source.lines().find_map(|line| {
+            line.trim()
+                .strip_prefix("package ")
+                .map(|package| package.trim_end_matches(';').trim().to_string())
+        });
+        if package.is_some() {
+            return Ok(package);
+        }
+    }
+    Ok(None)
+}
+
+/// Initializr IDs of the declared dependencies, looked up in the catalog for
+/// the project's Boot version. Dependencies without an ID are left out.
+pub(crate) async fn dependency_ids(config: &ProjectConfig, dependencies: &[String]) -> Vec<String> {
+    let catalog = match initializr::Client::new(config) {
+        Ok(client) => client.dependency_catalog(&config.boot_version).await,
+        Err(e) => Err(e),
+    };
+    match catalog {
+        Ok(catalog) => catalog
+            .into_iter()
+            .filter(|(_, entry)| dependencies.contains(&entry.group_artifact()))
+            .map(|(id, _)| id)
+            .collect(),
+        Err(e) => {
+            eprintln!(
+                "Warning: could not map dependencies to initializr IDs: {}",
+                e
+            );
+            Vec::new()
+        }
+    }
+}
+
+/// Register an existing Spring Boot project so the other commands can work on it
+pub async fn import(config: &ProjectConfig, path: &Path, name: Option<&str>) -> Result<()> {
+    let dir: PathBuf = path
+        .canonicalize()
+        .map_err(|e| color_eyre::eyre::eyre!("Cannot open {}: {}", path.display(), e))?;
+    let (build_tool, build_file) = [
+        (BuildTool::Maven, "pom.xml"),
+        (BuildTool::GradleKotlin, "build.gradle.kts"),
+        (BuildTool::Gradle, "build.gradle"),
+    ]
+    .into_iter()
+    .map(|(build_tool, name)| (build_tool, dir.join(name)))
+    .find(|(_, build_file)| build_file.exists())
+    .ok_or_else(|| {
+        color_eyre::eyre::eyre!(
+            "No pom.xml, build.gradle, or build.gradle.kts in {}",
+            dir.display()
+        )
+    })?;
+
+    let detected = read_build_settings(build_tool, &dir)?;
+    let boot_version = detected.boot_version.ok_or_else(|| {
+        color_eyre::eyre::eyre!(
+            "Could not find the Spring Boot version in {}, is it a Spring Boot project?",
+            build_file.display()
+        )
+    })?;
+
+    let language = [Language::Kotlin, Language::Groovy]
+        .into_iter()
+        .find(|language| {
+            dir.join("src")
+                .join("main")
+                .join(language.as_str())
+                .is_dir()
+        })
+        .unwrap_or(Language::Java);
+
+    let mut project = config.clone();
+    project.app_name = name.unwrap_or(&detected.artifact_id).to_string();
+    project.artifact_id =
+        (project.app_name != detected.artifact_id).then(|| detected.artifact_id.clone());
+    project.base_dir = None;
+    project.boot_version = boot_version;
+    project.build_tool = build_tool;
+    project.language = language;
+    project.packaging = detected.packaging;
+    project.subdir = None;
+    project.project_dir = Some(dir.display().to_string());
+    if let Some(version) = detected.version {
+        project.app_version = version;
+        project.version_qualifier = None;
+    }
+    if let Some(java_version) = detected.java_version {
+        project.java_version = java_version;
+    }
+    let sources = dir.join("src").join("main").join(language.as_str());
+    if let Some(package) = find_application_package(&sources)?.or(detected.group_id.clone()) {
+        project.package_name = package;
+    }
+    project.group_id = detected
+        .group_id
+        .filter(|group_id| *group_id != project.package_name);
+
+    let mut registry = Registry::load(config)?;
+    if let Some(existing) = registry.project_config(&project.app_name, config) {
+        if existing.app_dir() != dir {
+            return Err(color_eyre::eyre::eyre!(
+                "A project named {} is already registered at {}, import with --name to pick another",
+                project.app_name,
+                existing.app_dir().display()
+            ));
+        }
+    }
+
+    let ids = dependency_ids(&project, &detected.dependencies).await;
+
+    registry.register(&project);
+    registry.save(config)?;
+
+    println!("Imported {} from {}", project.app_name, dir.display());
+    println!("   BUILD FILE: {}", build_file.display());
+    println!("  APP VERSION: {}", project.app_version);
+    println!(" PACKAGE NAME: {}", project.package_name);
+    println!(" JAVA VERSION: {}", project.java_version);
+    println!(" BOOT VERSION: {}", project.boot_version);
+    if ids.is_empty() {
+        println!(" DEPENDENCIES: none recognized");
+    } else {
+        println!(" DEPENDENCIES: {}", ids.join(", "));
+    }
+    Ok(())
+}
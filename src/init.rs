@@ -0,0 +1,1058 @@
+use crate::ai::suggest::{self, Suggester};
+use crate::hooks::{self, Hook};
+use crate::layout::{self, Layout};
+use crate::metadata::InitializrMetadata;
+use crate::output::{self, OutputFormat};
+use crate::project::{self, Project};
+use crate::quality::{self, QualityTool};
+use crate::template::Template;
+use crate::{
+    application_yml, cache, deps, git, initializr, lock, modules, openapi, plugins, processors,
+    profiles, progress, projects, repositories, toolchains, versions, wizard, wrapper, BuildTool,
+    IncompatiblePolicy, Language, Packaging, ProjectConfig,
+};
+use color_eyre::eyre::Result;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::process::Command;
+
+/// Everything `init` needs besides the config, built up with chained setters:
+///
+/// ```no_run
+/// # async fn example(config: &spring_init::ProjectConfig) -> color_eyre::eyre::Result<()> {
+/// let project = spring_init::InitRequest::new()
+///     .app_name("orders")
+///     .include(["web", "data-jpa"])
+///     .force(true)
+///     .generate(config)
+///     .await?;
+/// println!("Generated {}", project.dir().display());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct InitRequest {
+    /// Application name, overrides app_name
+    pub app_name: Option<String>,
+    /// Application version, overrides app_version
+    pub app_version: Option<String>,
+    /// Version qualifier, overrides version_qualifier
+    pub version_qualifier: Option<String>,
+    /// Base package, overrides package_name
+    pub package_name: Option<String>,
+    /// groupId, overrides group_id
+    pub group_id: Option<String>,
+    /// artifactId, overrides artifact_id
+    pub artifact_id: Option<String>,
+    /// Project description, overrides description
+    pub description: Option<String>,
+    /// Project directory name, overrides base_dir
+    pub base_dir: Option<String>,
+    /// Java version, overrides java_version
+    pub java_version: Option<String>,
+    /// Spring Boot version, overrides boot_version
+    pub boot_version: Option<String>,
+    /// Directory projects are created in, overrides projects_dir
+    pub projects_dir: Option<String>,
+    /// Maven plugin coordinates, overrides maven_plugins
+    pub plugins: Option<Vec<String>>,
+    /// Maven modules, the first one runnable, overrides modules
+    pub modules: Option<Vec<String>>,
+    /// Formatter and static analysis plugins, overrides quality
+    pub quality: Option<Vec<QualityTool>>,
+    /// What to do when a dependency doesn't support the Boot version, overrides incompatible_deps
+    pub incompatible_deps: Option<IncompatiblePolicy>,
+    /// Path to PRD file for automatic dependency selection
+    pub prd: Option<String>,
+    /// Ask the AI provider again instead of reusing cached suggestions
+    pub no_cache: bool,
+    /// Prompt for project settings and dependencies
+    pub interactive: bool,
+    /// Accept AI-suggested dependencies and skip confirmation prompts
+    pub yes: bool,
+    /// File with pre-selected dependency IDs, one per line
+    pub answers_file: Option<String>,
+    /// Pre-selected dependency IDs, used like an answers file
+    pub dependencies: Option<Vec<String>>,
+    /// Additional dependencies to always include
+    pub include: Vec<String>,
+    /// Drop dependency IDs that aren't in the metadata instead of failing
+    pub skip_unknown: bool,
+    /// Check that configured Maven plugins exist on Maven Central
+    pub verify_plugins: bool,
+    /// Print the resulting build file once initialization completes
+    pub print_pom: bool,
+    /// Relative directory under projects_dir to place the project in
+    pub subdir: Option<String>,
+    /// Build tool for the generated project
+    pub build_tool: Option<BuildTool>,
+    /// Language for the generated project
+    pub language: Option<Language>,
+    /// Archive the application is packaged as, overrides packaging
+    pub packaging: Option<Packaging>,
+    /// Enable layered jars in the spring-boot-maven-plugin configuration
+    pub layered: bool,
+    /// Add dev and prod Maven profiles and application-{env}.yml files
+    pub env_profiles: bool,
+    /// Add Lombok with its annotation processor
+    pub lombok: bool,
+    /// Add MapStruct with its annotation processor
+    pub mapstruct: bool,
+    /// Package skeleton to create, overrides layout
+    pub layout: Option<Layout>,
+    /// Registered template to apply on top of the starter
+    pub template: Option<String>,
+    /// Values for the template's variables
+    pub variables: BTreeMap<String, String>,
+    /// Initialize a git repository and make an initial commit
+    pub git: bool,
+    /// License to add to the repository, implies git
+    pub license: Option<git::License>,
+    /// GitHub repository (owner/name) to create and push to, implies git
+    pub github: Option<String>,
+    /// Make the GitHub repository public instead of private
+    pub public: bool,
+    /// OpenAPI spec to generate API interfaces and controllers from
+    pub openapi: Option<String>,
+    /// Register the configured Java version in ~/.m2/toolchains.xml
+    pub toolchains: bool,
+    /// JDK installation to register, defaults to JAVA_HOME
+    pub jdk_home: Option<String>,
+    /// Replace an existing toolchains.xml entry for the Java version
+    pub replace_toolchain: bool,
+    /// Overwrite an existing project directory
+    pub force: bool,
+    /// Extract over an existing project directory, keeping files that aren't in the scaffold
+    pub merge: bool,
+    /// Generate from cached metadata and a previously downloaded starter
+    pub offline: bool,
+}
+
+impl InitRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn app_name(mut self, app_name: impl Into<String>) -> Self {
+        self.app_name = Some(app_name.into());
+        self
+    }
+
+    pub fn app_version(mut self, app_version: impl Into<String>) -> Self {
+        self.app_version = Some(app_version.into());
+        self
+    }
+
+    pub fn version_qualifier(mut self, version_qualifier: impl Into<String>) -> Self {
+        self.version_qualifier = Some(version_qualifier.into());
+        self
+    }
+
+    pub fn package_name(mut self, package_name: impl Into<String>) -> Self {
+        self.package_name = Some(package_name.into());
+        self
+    }
+
+    pub fn group_id(mut self, group_id: impl Into<String>) -> Self {
+        self.group_id = Some(group_id.into());
+        self
+    }
+
+    pub fn artifact_id(mut self, artifact_id: impl Into<String>) -> Self {
+        self.artifact_id = Some(artifact_id.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn base_dir(mut self, base_dir: impl Into<String>) -> Self {
+        self.base_dir = Some(base_dir.into());
+        self
+    }
+
+    pub fn java_version(mut self, java_version: impl Into<String>) -> Self {
+        self.java_version = Some(java_version.into());
+        self
+    }
+
+    pub fn boot_version(mut self, boot_version: impl Into<String>) -> Self {
+        self.boot_version = Some(boot_version.into());
+        self
+    }
+
+    pub fn projects_dir(mut self, projects_dir: impl Into<String>) -> Self {
+        self.projects_dir = Some(projects_dir.into());
+        self
+    }
+
+    pub fn plugins<I, S>(mut self, plugins: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.plugins = Some(plugins.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn incompatible_deps(mut self, incompatible_deps: IncompatiblePolicy) -> Self {
+        self.incompatible_deps = Some(incompatible_deps);
+        self
+    }
+
+    pub fn prd(mut self, prd: impl Into<String>) -> Self {
+        self.prd = Some(prd.into());
+        self
+    }
+
+    pub fn no_cache(mut self, no_cache: bool) -> Self {
+        self.no_cache = no_cache;
+        self
+    }
+
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    pub fn yes(mut self, yes: bool) -> Self {
+        self.yes = yes;
+        self
+    }
+
+    pub fn answers_file(mut self, answers_file: impl Into<String>) -> Self {
+        self.answers_file = Some(answers_file.into());
+        self
+    }
+
+    /// Formatter and static analysis plugins to add, overriding the config
+    pub fn quality<I>(mut self, quality: I) -> Self
+    where
+        I: IntoIterator<Item = QualityTool>,
+    {
+        self.quality = Some(quality.into_iter().collect());
+        self
+    }
+
+    pub fn modules<I, S>(mut self, modules: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.modules = Some(modules.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn dependencies<I, S>(mut self, dependencies: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.dependencies = Some(dependencies.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Add dependency IDs to include
+    pub fn include<I, S>(mut self, include: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.include.extend(include.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn skip_unknown(mut self, skip_unknown: bool) -> Self {
+        self.skip_unknown = skip_unknown;
+        self
+    }
+
+    pub fn verify_plugins(mut self, verify_plugins: bool) -> Self {
+        self.verify_plugins = verify_plugins;
+        self
+    }
+
+    pub fn print_pom(mut self, print_pom: bool) -> Self {
+        self.print_pom = print_pom;
+        self
+    }
+
+    pub fn subdir(mut self, subdir: impl Into<String>) -> Self {
+        self.subdir = Some(subdir.into());
+        self
+    }
+
+    pub fn build_tool(mut self, build_tool: BuildTool) -> Self {
+        self.build_tool = Some(build_tool);
+        self
+    }
+
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    pub fn packaging(mut self, packaging: Packaging) -> Self {
+        self.packaging = Some(packaging);
+        self
+    }
+
+    pub fn layered(mut self, layered: bool) -> Self {
+        self.layered = layered;
+        self
+    }
+
+    pub fn env_profiles(mut self, env_profiles: bool) -> Self {
+        self.env_profiles = env_profiles;
+        self
+    }
+
+    pub fn lombok(mut self, lombok: bool) -> Self {
+        self.lombok = lombok;
+        self
+    }
+
+    pub fn mapstruct(mut self, mapstruct: bool) -> Self {
+        self.mapstruct = mapstruct;
+        self
+    }
+
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = Some(layout);
+        self
+    }
+
+    pub fn template(mut self, template: impl Into<String>) -> Self {
+        self.template = Some(template.into());
+        self
+    }
+
+    pub fn variable(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.variables.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn git(mut self, git: bool) -> Self {
+        self.git = git;
+        self
+    }
+
+    pub fn license(mut self, license: git::License) -> Self {
+        self.license = Some(license);
+        self
+    }
+
+    pub fn github(mut self, github: impl Into<String>) -> Self {
+        self.github = Some(github.into());
+        self
+    }
+
+    pub fn public(mut self, public: bool) -> Self {
+        self.public = public;
+        self
+    }
+
+    pub fn openapi(mut self, openapi: impl Into<String>) -> Self {
+        self.openapi = Some(openapi.into());
+        self
+    }
+
+    pub fn toolchains(mut self, toolchains: bool) -> Self {
+        self.toolchains = toolchains;
+        self
+    }
+
+    pub fn jdk_home(mut self, jdk_home: impl Into<String>) -> Self {
+        self.jdk_home = Some(jdk_home.into());
+        self
+    }
+
+    pub fn replace_toolchain(mut self, replace_toolchain: bool) -> Self {
+        self.replace_toolchain = replace_toolchain;
+        self
+    }
+
+    pub fn force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    pub fn merge(mut self, merge: bool) -> Self {
+        self.merge = merge;
+        self
+    }
+
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Replace config values with the ones set on the request
+    pub fn configure(&self, config: &mut ProjectConfig) -> Result<()> {
+        if let Some(app_name) = &self.app_name {
+            config.app_name = app_name.clone();
+        }
+        if let Some(app_version) = &self.app_version {
+            config.app_version = app_version.clone();
+        }
+        if let Some(version_qualifier) = &self.version_qualifier {
+            config.version_qualifier = Some(version_qualifier.clone());
+        }
+        if let Some(package_name) = &self.package_name {
+            config.package_name = package_name.clone();
+        }
+        if let Some(group_id) = &self.group_id {
+            config.group_id = Some(group_id.clone());
+        }
+        if let Some(artifact_id) = &self.artifact_id {
+            config.artifact_id = Some(artifact_id.clone());
+        }
+        if let Some(description) = &self.description {
+            config.description = Some(description.clone());
+        }
+        if let Some(base_dir) = &self.base_dir {
+            config.base_dir = Some(base_dir.clone());
+        }
+        if let Some(java_version) = &self.java_version {
+            config.java_version = java_version.clone();
+        }
+        if let Some(boot_version) = &self.boot_version {
+            config.boot_version = boot_version.clone();
+        }
+        if let Some(projects_dir) = &self.projects_dir {
+            config.projects_dir = projects_dir.clone();
+        }
+        if let Some(plugins) = &self.plugins {
+            config.maven_plugins = plugins
+                .iter()
+                .cloned()
+                .map(plugins::MavenPlugin::Coordinates)
+                .collect();
+        }
+        if let Some(modules) = &self.modules {
+            config.modules = modules.clone();
+        }
+        if let Some(quality) = &self.quality {
+            config.quality = quality.clone();
+        }
+        if self.env_profiles {
+            config.env_profiles = true;
+        }
+        if self.lombok {
+            config.lombok = true;
+        }
+        if self.mapstruct {
+            config.mapstruct = true;
+        }
+        if let Some(layout) = self.layout {
+            config.layout = Some(layout);
+        }
+        if let Some(template) = &self.template {
+            Template::load(config, template)?.configure(config);
+        }
+        if let Some(policy) = self.incompatible_deps {
+            config.incompatible_deps = policy;
+        }
+        if let Some(subdir) = &self.subdir {
+            config.subdir = Some(subdir.clone());
+        }
+        if let Some(build_tool) = self.build_tool {
+            config.build_tool = build_tool;
+        }
+        if let Some(language) = self.language {
+            config.language = language;
+        }
+        if let Some(packaging) = self.packaging {
+            config.packaging = packaging;
+        }
+        // Generated projects always live under projects_dir
+        config.project_dir = None;
+        config.validate()
+    }
+
+    /// Generate the project without prompting, returning it
+    pub async fn generate(&self, config: &ProjectConfig) -> Result<Project> {
+        init_project(config, self, true, false, OutputFormat::Text).await?;
+        let mut config = config.clone();
+        self.configure(&mut config)?;
+        Project::new(config)
+    }
+}
+
+/// Apply the incompatible_deps policy to dependencies whose version range
+/// excludes boot_version, returning the Boot version to use instead if any
+fn check_compatibility(
+    config: &ProjectConfig,
+    metadata: &InitializrMetadata,
+    deps: &[String],
+) -> Result<Option<String>> {
+    let incompatible = versions::incompatible_dependencies(metadata, deps, &config.boot_version);
+    if incompatible.is_empty() {
+        return Ok(None);
+    }
+
+    let details = incompatible
+        .iter()
+        .map(|(id, range)| format!("{} requires {}", id, range))
+        .collect::<Vec<_>>()
+        .join(", ");
+    match config.incompatible_deps {
+        IncompatiblePolicy::Fail => Err(color_eyre::eyre::eyre!(
+            "Dependencies not compatible with Spring Boot {}: {} (use --incompatible-deps to warn or substitute instead)",
+            config.boot_version,
+            details
+        )),
+        IncompatiblePolicy::Warn => {
+            eprintln!(
+                "Warning: dependencies not compatible with Spring Boot {}: {}",
+                config.boot_version, details
+            );
+            Ok(None)
+        }
+        IncompatiblePolicy::Substitute => match versions::compatible_version(metadata, deps) {
+            Some(boot_version) => {
+                eprintln!(
+                    "Using Spring Boot {} instead of {} ({})",
+                    boot_version, config.boot_version, details
+                );
+                Ok(Some(boot_version))
+            }
+            None => Err(color_eyre::eyre::eyre!(
+                "No Spring Boot version supports all selected dependencies: {}",
+                details
+            )),
+        },
+    }
+}
+
+/// Read dependency IDs from an answers file, skipping blank lines and `#` comments
+pub fn read_answers_file(path: &str) -> Result<Vec<String>> {
+    let content = fs::read_to_string(path)?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect())
+}
+
+pub async fn init_project(
+    config: &ProjectConfig,
+    args: &InitRequest,
+    quiet: bool,
+    dry_run: bool,
+    output: OutputFormat,
+) -> Result<()> {
+    let mut config = config.clone();
+    args.configure(&mut config)?;
+    let config = &config;
+
+    if args.merge && !config.modules.is_empty() {
+        return Err(color_eyre::eyre::eyre!(
+            "--merge can't restructure an existing project into modules, use --force"
+        ));
+    }
+    if let (Some(_), true) = (&args.github, args.offline) {
+        return Err(color_eyre::eyre::eyre!(
+            "Creating a GitHub repository needs the network, drop --offline"
+        ));
+    }
+    // An answers file stands in for every prompt, so scripted runs of the
+    // interactive path never wait for input
+    let interactive = args.interactive && args.answers_file.is_none();
+
+    // The wizard's answers are checked once the starter URL is built
+    if !interactive {
+        initializr::validate(config)?;
+    }
+
+    let metadata = if dry_run || args.offline {
+        cache::cached_metadata(config)?.ok_or_else(|| {
+            color_eyre::eyre::eyre!(
+                "No cached metadata, run `spring-init deps` before using --{}",
+                if dry_run { "dry-run" } else { "offline" }
+            )
+        })?
+    } else {
+        cache::metadata(config, false).await?
+    };
+
+    // Let the user adjust the project settings when running interactively
+    let wizard_config;
+    let config = if interactive {
+        wizard_config = wizard::prompt_project(config, &metadata)?;
+        &wizard_config
+    } else {
+        config
+    };
+
+    if let Some(warning) = versions::check(&metadata, &config.boot_version) {
+        eprintln!("Warning: {}", warning);
+    }
+
+    if let Some(spec) = &args.openapi {
+        openapi::check(config, Path::new(spec))?;
+    }
+    if let (Some(github), false) = (&args.github, dry_run) {
+        git::check_github(github)?;
+    }
+
+    let template = args
+        .template
+        .as_deref()
+        .map(|name| Template::load(config, name))
+        .transpose()?;
+
+    // Warn about plugin coordinates that don't resolve before doing any work
+    if args.verify_plugins && !dry_run && !args.offline {
+        plugins::verify_plugins(config).await?;
+    }
+
+    // Included dependencies from both config and command line, `@name`
+    // presets are expanded along with the rest
+    let included: Vec<String> = config
+        .include_deps
+        .iter()
+        .chain(&args.include)
+        .chain(
+            template
+                .iter()
+                .flat_map(|template| &template.manifest.dependencies),
+        )
+        .cloned()
+        .collect();
+
+    // Dependencies picked interactively are final, the picker starts from
+    // the configured and included dependencies
+    let mut picked = false;
+
+    // Get dependencies from the answers file or PRD if provided
+    let mut all_deps = if let Some(dependencies) = &args.dependencies {
+        dependencies.join(",")
+    } else if let Some(answers_file) = &args.answers_file {
+        read_answers_file(answers_file)?.join(",")
+    } else if interactive {
+        let selected = deps::expand_presets(included.clone(), &config.presets)?;
+        picked = true;
+        wizard::prompt_dependencies(&metadata, &selected)?.join(",")
+    } else if let (Some(prd_path), true) = (&args.prd, dry_run) {
+        // Suggestions need the AI provider, only the included dependencies are resolved
+        println!("Would suggest dependencies based on {}", prd_path);
+        String::new()
+    } else if let Some(prd_path) = &args.prd {
+        // Read the PRD file
+        let prd_content = fs::read_to_string(prd_path)?;
+
+        // Get dependency suggestions
+        let suggester = Suggester::new(config)?;
+        let (suggestions, usage) = suggester
+            .suggest(&metadata, &prd_content, quiet, args.no_cache)
+            .await?;
+        if !quiet {
+            suggest::report_usage(config, suggester.model(), &usage);
+        }
+        let suggested: Vec<String> = suggestions.iter().map(|s| s.id.clone()).collect();
+
+        // Let the user review the suggestions unless running unattended
+        if args.yes || output.is_json() || !std::io::stdin().is_terminal() {
+            suggested.join(",")
+        } else {
+            suggest::print_suggestions(&suggestions);
+            let mut selected = suggested;
+            selected.extend(deps::expand_presets(included.clone(), &config.presets)?);
+            picked = true;
+            wizard::prompt_dependencies(&metadata, &selected)?.join(",")
+        }
+    } else {
+        String::from("web")
+    };
+
+    let prd_deps: Vec<&str> = all_deps
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .collect();
+    let mut combined_deps: Vec<String> = prd_deps.iter().map(|&s| s.to_string()).collect();
+
+    if !picked {
+        combined_deps.extend(included);
+    }
+
+    // The generated API interfaces need these even when deps were picked
+    if args.openapi.is_some() {
+        combined_deps.extend(openapi::DEPENDENCIES.iter().map(|id| id.to_string()));
+    }
+
+    let mut combined_deps = deps::expand_presets(combined_deps, &config.presets)?;
+    combined_deps.sort();
+    combined_deps.dedup();
+    let prompt = !args.yes && !output.is_json() && std::io::stdin().is_terminal();
+    let combined_deps =
+        deps::validate_dependencies(combined_deps, &metadata, args.skip_unknown, prompt)?;
+    all_deps = combined_deps.join(",");
+
+    let substituted;
+    let config = match check_compatibility(config, &metadata, &combined_deps)? {
+        Some(boot_version) => {
+            substituted = ProjectConfig {
+                boot_version,
+                ..config.clone()
+            };
+            &substituted
+        }
+        None => config,
+    };
+
+    let url = initializr::starter_url(config, &combined_deps)?;
+
+    // Ask for the template's variables before anything is downloaded
+    let variables = match &template {
+        Some(template) => template.variables(&args.variables, prompt && !dry_run)?,
+        None => BTreeMap::new(),
+    };
+
+    if !output.is_json() {
+        println!("     APP NAME: {}", config.app_name);
+        println!("     GROUP ID: {}", config.group_id());
+        println!("  ARTIFACT ID: {}", config.artifact_id());
+        println!("  APP VERSION: {}", config.version());
+        println!(" PACKAGE NAME: {}", config.package_name);
+        println!(" JAVA VERSION: {}", config.java_version);
+        println!(" BOOT VERSION: {}", config.boot_version);
+        println!("   BUILD TOOL: {}", config.build_tool.project_type());
+        println!("     LANGUAGE: {}", config.language.as_str());
+        println!("    PACKAGING: {}", config.packaging.as_str());
+        println!("      APP DIR: {}", config.app_dir().display());
+        if !config.modules.is_empty() {
+            println!("      MODULES: {}", config.modules.join(", "));
+        }
+        println!("Using dependencies: {}", all_deps.trim());
+        println!("Full URL: {}", url);
+    }
+
+    let exists = config.app_dir().exists();
+    if exists && !args.force && !args.merge && !dry_run {
+        return Err(color_eyre::eyre::eyre!(
+            "{} already exists, use --force to overwrite it or --merge to keep files that aren't in the scaffold",
+            config.app_dir().display()
+        ));
+    }
+
+    // Fail before anything is deleted when there's nothing to generate from
+    let cached_starter = if args.offline {
+        Some(cache::cached_starter(&url)?.ok_or_else(|| {
+            color_eyre::eyre::eyre!(
+                "No cached starter for these settings and dependencies, run the same init once without --offline"
+            )
+        })?)
+    } else {
+        None
+    };
+
+    if dry_run {
+        if args.merge {
+            if exists {
+                println!("Would merge into: {}", config.app_dir().display());
+            }
+        } else if exists && !args.force {
+            println!("Would abort: {} already exists", config.app_dir().display());
+            return Ok(());
+        } else {
+            project::reset(config, true)?;
+        }
+        let configured = application_yml::configured(config, &combined_deps);
+        if !configured.is_empty() {
+            println!(
+                "Would write application.yml with settings for: {}",
+                configured.join(", ")
+            );
+        }
+        hooks::run(config, Hook::PostInit, Some(&combined_deps), true)?;
+        if args.git || args.license.is_some() || args.github.is_some() || config.git.init {
+            println!("Would initialize a git repository with an initial commit");
+        }
+        if let Some(github) = &args.github {
+            println!("Would create GitHub repository {} and push to it", github);
+        }
+        if let Some(layout) = config.layout {
+            println!("Would create the {} package layout", layout.as_str());
+        }
+        if let Some(template) = &template {
+            println!("Would apply template {}", template.name);
+            template.apply(config, &variables, true)?;
+        }
+        if config.build_tool.is_gradle() {
+            return Ok(());
+        }
+        if !config.modules.is_empty() {
+            println!(
+                "Would split into modules: {}, {} is runnable",
+                config.modules.join(", "),
+                config.modules[0]
+            );
+        }
+        for plugin in &config.maven_plugins {
+            println!("Would add plugin: {}", plugin);
+        }
+        for bom in &config.maven_boms {
+            println!("Would import BOM: {}", bom);
+        }
+        for dependency in &config.maven_dependencies {
+            println!("Would add dependency: {}", dependency);
+        }
+        if !config.maven_repositories.is_empty() || !config.maven_mirrors.is_empty() {
+            repositories::sync(config, true)?;
+        }
+        if args.layered || config.layered_jar {
+            println!("Would enable layered jars");
+        }
+        if config.env_profiles {
+            println!("Would add dev and prod profiles");
+        }
+        for tool in &config.quality {
+            println!("Would add {}", tool.as_str());
+        }
+        if config.lombok {
+            println!("Would add lombok with its annotation processor");
+        }
+        if config.mapstruct {
+            println!("Would add mapstruct with its annotation processor");
+        }
+        if let Some(spec) = &args.openapi {
+            println!(
+                "Would generate API interfaces and controllers from {}",
+                spec
+            );
+        }
+        if args.toolchains {
+            println!(
+                "Would register JDK {} in ~/.m2/toolchains.xml",
+                config.java_version
+            );
+        }
+        return Ok(());
+    }
+
+    if interactive && !args.yes && !wizard::confirm("Generate project?", true)? {
+        println!("Initialization cancelled");
+        return Ok(());
+    }
+
+    // Clear the previous project unless merging into it
+    if args.merge {
+        if Path::new("spring.zip").exists() {
+            fs::remove_file("spring.zip")?;
+        }
+    } else {
+        project::reset(config, false)?;
+    }
+
+    // Download Spring Boot scaffold
+    if let Some(cached) = cached_starter {
+        fs::copy(cached, "spring.zip")?;
+    } else {
+        initializr::Client::new(config)?
+            .download_scaffold(&url, Path::new("spring.zip"), quiet)
+            .await?;
+        cache::store_starter(&url, Path::new("spring.zip"))?;
+    }
+
+    let starter_digest = cache::digest(&fs::read("spring.zip")?);
+
+    // Unzip the scaffold
+    fs::create_dir_all(config.scaffold_dir())?;
+    initializr::extract_scaffold(Path::new("spring.zip"), &config.scaffold_dir(), quiet)?;
+
+    // Clean up zip file
+    fs::remove_file("spring.zip")?;
+
+    modules::split(config)?;
+
+    let mut plugins_synced = Vec::new();
+    if config.build_tool.is_gradle() {
+        // Maven plugins, layering, and toolchains only apply to pom.xml
+        if !config.maven_plugins.is_empty() {
+            eprintln!("Skipping maven_plugins for Gradle project");
+        }
+        if !config.maven_dependencies.is_empty() || !config.maven_boms.is_empty() {
+            eprintln!("Skipping maven_dependencies and maven_boms for Gradle project");
+        }
+        if config.env_profiles {
+            eprintln!("Skipping env_profiles for Gradle project");
+        }
+        if !config.maven_repositories.is_empty() || !config.maven_mirrors.is_empty() {
+            eprintln!("Skipping maven_repositories and maven_mirrors for Gradle project");
+        }
+        if !config.quality.is_empty() {
+            eprintln!("Skipping quality for Gradle project");
+        }
+        if config.lombok || config.mapstruct {
+            eprintln!("Skipping lombok and mapstruct for Gradle project");
+        }
+    } else {
+        // The parent and starters may only resolve from the configured repositories
+        if !config.maven_repositories.is_empty() || !config.maven_mirrors.is_empty() {
+            repositories::sync(config, false)?;
+        }
+
+        // Get project version from pom.xml using Maven
+        let spinner = progress::spinner("Resolving project version with Maven...", quiet)?;
+        let mut command = Command::new(wrapper::maven(config));
+        command
+            .current_dir(config.app_dir())
+            .arg("help:evaluate")
+            .arg("-Dexpression=project.version")
+            .arg("-q")
+            .arg("-DforceStdout")
+            // Maven resolves the help plugin from the local repository only
+            .args(args.offline.then_some("-o"));
+        repositories::configure(&mut command, config);
+        let output = command.output()?;
+        spinner.finish_and_clear();
+
+        if !output.status.success() {
+            return Err(color_eyre::eyre::eyre!(
+                "Failed to get project version from pom.xml"
+            ));
+        }
+
+        // Sync plugins, BOMs, and dependencies from config.json to pom.xml
+        plugins_synced = plugins::sync_plugins(config)?;
+        plugins::sync_dependencies(config)?;
+
+        if args.layered || config.layered_jar {
+            plugins::enable_layered_jar(config)?;
+        }
+
+        if config.env_profiles {
+            profiles::configure(config)?;
+        }
+
+        processors::configure(config)?;
+
+        if !config.quality.is_empty() {
+            quality::configure(config, args.offline)?;
+        }
+
+        if let Some(spec) = &args.openapi {
+            openapi::configure(config, Path::new(spec))?;
+            openapi::write_controllers(config, quiet)?;
+        }
+
+        if args.toolchains {
+            toolchains::write_toolchains(config, args.jdk_home.as_deref(), args.replace_toolchain)?;
+        }
+    }
+
+    if let Some(layout) = config.layout {
+        layout::write(config, layout, &combined_deps)?;
+    }
+
+    // Starting points for the datasource, actuator, messaging, etc.
+    application_yml::write(config, &combined_deps)?;
+
+    // Last, so the template's files win over generated ones
+    if let Some(template) = &template {
+        eprintln!("Applying template {}", template.name);
+        template.apply(config, &variables, false)?;
+    }
+
+    // Record what `regenerate` needs, before the initial commit so it's part of it
+    let locked = ProjectConfig {
+        layered_jar: args.layered || config.layered_jar,
+        ..config.clone()
+    };
+    lock::write(&locked, &url, &combined_deps, &metadata, starter_digest)?;
+
+    // Before the initial commit, so whatever the hooks write is part of it
+    hooks::run(config, Hook::PostInit, Some(&combined_deps), false)?;
+
+    if args.git || args.license.is_some() || args.github.is_some() || config.git.init {
+        git::bootstrap(config, args.license.or(config.git.license))?;
+    }
+    if let Some(github) = &args.github {
+        git::publish_github(config, github, !args.public).await?;
+    }
+
+    let pom = if args.print_pom {
+        print_pom(
+            &mut io::stdout(),
+            fs::read_to_string(config.build_file())?,
+            output,
+        )?
+    } else {
+        None
+    };
+
+    // Make the new project the target of later commands
+    let mut registry = projects::Registry::load(config)?;
+    registry.register(config);
+    registry.save(config)?;
+
+    eprintln!("Project initialization complete");
+    if output.is_json() {
+        output::print_json(&output::InitResult {
+            app_name: config.app_name.clone(),
+            app_dir: config.app_dir().display().to_string(),
+            build_file: config.build_file().display().to_string(),
+            dependencies: combined_deps,
+            url,
+            plugins_synced,
+            pom,
+        })?;
+    }
+    Ok(())
+}
+
+/// Print the build file for `--print-pom`. Under `--output json` it's handed
+/// back for the result's `pom` field instead, so stdout stays parseable.
+fn print_pom(
+    out: &mut impl Write,
+    pom: String,
+    output: OutputFormat,
+) -> io::Result<Option<String>> {
+    if output.is_json() {
+        return Ok(Some(pom));
+    }
+    write!(out, "{}", pom)?;
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn print_pom_goes_to_stdout_as_text_and_into_the_result_as_json() {
+        let pom = "<project></project>\n".to_string();
+
+        let mut out = Vec::new();
+        let field = print_pom(&mut out, pom.clone(), OutputFormat::Text).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), pom);
+        assert_eq!(field, None);
+
+        let mut out = Vec::new();
+        let field = print_pom(&mut out, pom.clone(), OutputFormat::Json).unwrap();
+        assert!(out.is_empty());
+        assert_eq!(field, Some(pom));
+    }
+
+    #[test]
+    fn answers_file_skips_blank_lines_and_comments() {
+        let path = std::env::temp_dir().join(format!("spring-init-answers-{}", std::process::id()));
+        fs::write(
+            &path,
+            "# Dependencies for CI\nweb\n\n  data-jpa  \n# postgresql\n@webstack\n",
+        )
+        .unwrap();
+
+        let answers = read_answers_file(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(answers.unwrap(), ["web", "data-jpa", "@webstack"]);
+    }
+}
@@ -0,0 +1,481 @@
+use crate::http::Http;
+use crate::metadata::{InitializrMetadata, ResolvedDependencies};
+use crate::{pom, progress, ProjectConfig};
+use color_eyre::eyre::Result;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+use tokio::io::AsyncWriteExt;
+use url::Url;
+
+pub const DEFAULT_URL: &str = "https://start.spring.io";
+
+/// Base URL of the configured Initializr instance, without a trailing slash
+pub fn base_url(config: &ProjectConfig) -> &str {
+    config.initializr_url.trim_end_matches('/')
+}
+
+pub fn metadata_url(config: &ProjectConfig) -> String {
+    format!("{}/metadata/client", base_url(config))
+}
+
+/// Replace `${NAME}` with the value of the environment variable NAME
+fn expand_env(value: &str) -> Result<String> {
+    let mut expanded = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + end];
+        let value = env::var(name).map_err(|_| {
+            color_eyre::eyre::eyre!("{} is used in initializr_headers but not set", name)
+        })?;
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&value);
+        rest = &rest[start + end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Headers sent with every Initializr request, e.g. credentials for a private instance
+pub fn headers(config: &ProjectConfig) -> Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    for (name, value) in &config.initializr_headers {
+        let name = HeaderName::try_from(name.as_str())
+            .map_err(|e| color_eyre::eyre::eyre!("Invalid header name {}: {}", name, e))?;
+        let value = HeaderValue::try_from(expand_env(value)?)
+            .map_err(|e| color_eyre::eyre::eyre!("Invalid value for header {}: {}", name, e))?;
+        headers.insert(name, value);
+    }
+    Ok(headers)
+}
+
+/// Client for the Initializr API, start.spring.io unless `initializr_url` says otherwise
+pub struct Client {
+    http: Http,
+    base_url: String,
+    headers: HeaderMap,
+}
+
+impl Client {
+    pub fn new(config: &ProjectConfig) -> Result<Self> {
+        Ok(Self {
+            http: Http::new(config)?,
+            base_url: base_url(config).to_string(),
+            headers: headers(config)?,
+        })
+    }
+
+    /// GET a JSON document
+    async fn get_json<T: DeserializeOwned>(&self, path: &str, query: &[(&str, &str)]) -> Result<T> {
+        let url = format!("{}{}", self.base_url, path);
+        let response = self
+            .http
+            .send(|client| {
+                client
+                    .get(&url)
+                    .headers(self.headers.clone())
+                    .header(reqwest::header::ACCEPT, "application/json")
+                    .query(query)
+            })
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to fetch {}: {}", url, e))?;
+        response
+            .json::<T>()
+            .await
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to parse response: {}", e))
+    }
+
+    /// Client metadata listing dependencies, Boot versions, and Java versions
+    pub async fn metadata(&self) -> Result<InitializrMetadata> {
+        self.get_json("/metadata/client", &[]).await
+    }
+
+    /// Dependencies available for a specific Boot version, keyed by ID
+    pub async fn dependency_catalog(
+        &self,
+        boot_version: &str,
+    ) -> Result<BTreeMap<String, CatalogEntry>> {
+        let resolved: ResolvedDependencies = self
+            .get_json("/dependencies", &[("bootVersion", boot_version)])
+            .await?;
+
+        Ok(resolved
+            .dependencies
+            .into_iter()
+            .map(|(id, dep)| {
+                let entry = CatalogEntry {
+                    coordinates: pom::Coordinates {
+                        group_id: dep.group_id,
+                        artifact_id: dep.artifact_id,
+                        version: dep.version,
+                    },
+                    scope: dep.scope.unwrap_or_else(|| "compile".to_string()),
+                    bom: dep.bom,
+                };
+                (id, entry)
+            })
+            .collect())
+    }
+
+    /// Stream the starter.zip at `url` to `path`
+    pub async fn download_scaffold(&self, url: &str, path: &Path, quiet: bool) -> Result<()> {
+        let mut response = self
+            .http
+            .send(|client| client.get(url).headers(self.headers.clone()))
+            .await
+            .map_err(|e| {
+                color_eyre::eyre::eyre!("Failed to download Spring Boot scaffold: {}", e)
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            // start.spring.io reports errors as JSON with a human readable message
+            let message = serde_json::from_str::<serde_json::Value>(&body)
+                .ok()
+                .and_then(|json| json["message"].as_str().map(String::from))
+                .unwrap_or(body);
+            return Err(color_eyre::eyre::eyre!(
+                "Failed to download Spring Boot scaffold ({}): {}",
+                status,
+                message.trim()
+            ));
+        }
+
+        let bar = progress::download(
+            "Downloading Spring Boot scaffold",
+            response.content_length(),
+            quiet,
+        )?;
+        let mut file = tokio::fs::File::create(path).await?;
+        while let Some(chunk) = response.chunk().await? {
+            file.write_all(&chunk).await?;
+            bar.inc(chunk.len() as u64);
+        }
+        file.flush().await?;
+        bar.finish();
+
+        Ok(())
+    }
+}
+
+/// Query parameters of the starter.zip request for the configured project
+/// with `dependencies`
+pub fn starter_params(
+    config: &ProjectConfig,
+    dependencies: &[String],
+) -> Vec<(&'static str, String)> {
+    let mut params = vec![
+        ("type", config.build_tool.project_type().to_string()),
+        ("language", config.language.as_str().to_string()),
+        ("bootVersion", config.boot_version.clone()),
+        ("baseDir", config.base_dir().to_string()),
+        ("groupId", config.group_id().to_string()),
+        ("artifactId", config.artifact_id().to_string()),
+        ("name", config.app_name.clone()),
+    ];
+    if let Some(description) = &config.description {
+        params.push(("description", description.clone()));
+    }
+    params.extend([
+        ("packageName", config.package_name.clone()),
+        ("packaging", config.packaging.as_str().to_string()),
+        ("javaVersion", config.java_version.clone()),
+        ("version", config.version()),
+        ("dependencies", dependencies.join(",")),
+    ]);
+    params
+}
+
+/// Check a Maven artifactId, or a setting that defaults one, e.g. the app
+/// name
+pub(crate) fn check_artifact(setting: &str, value: &str) -> Result<()> {
+    let valid = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+    if !valid {
+        return Err(color_eyre::eyre::eyre!(
+            "Invalid {} {:?}, use letters, digits, '-', '_', and '.'",
+            setting,
+            value
+        ));
+    }
+    Ok(())
+}
+
+/// Check a Java package name, e.g. com.acme.billing
+pub(crate) fn check_package(package: &str) -> Result<()> {
+    let valid = package.split('.').all(|segment| {
+        let mut chars = segment.chars();
+        chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    });
+    if !valid {
+        return Err(color_eyre::eyre::eyre!(
+            "Invalid package name {:?}, e.g. com.acme.billing",
+            package
+        ));
+    }
+    Ok(())
+}
+
+/// Check a project version, e.g. 1.0.0 or 0.0.1-SNAPSHOT
+fn check_version(version: &str) -> Result<()> {
+    let valid = version.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && version
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' || c == '+');
+    if !valid {
+        return Err(color_eyre::eyre::eyre!(
+            "Invalid version {:?}, e.g. 1.0.0 or 0.0.1-SNAPSHOT",
+            version
+        ));
+    }
+    Ok(())
+}
+
+/// Check the settings sent to the Initializr, which rejects some invalid
+/// values and generates a broken project from others
+pub fn validate(config: &ProjectConfig) -> Result<()> {
+    let valid_group = config.group_id().split('.').all(|segment| {
+        !segment.is_empty()
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    });
+    if !valid_group {
+        return Err(color_eyre::eyre::eyre!(
+            "Invalid group_id {:?}, e.g. com.acme",
+            config.group_id()
+        ));
+    }
+    check_artifact("artifact_id", config.artifact_id())?;
+    check_package(&config.package_name)?;
+    check_version(&config.version())
+}
+
+/// URL of the starter.zip for the configured project with `dependencies`,
+/// with every parameter percent-encoded
+pub fn starter_url(config: &ProjectConfig, dependencies: &[String]) -> Result<String> {
+    validate(config)?;
+    let mut url = Url::parse(&format!("{}/starter.zip", base_url(config))).map_err(|e| {
+        color_eyre::eyre::eyre!("Invalid initializr_url {}: {}", config.initializr_url, e)
+    })?;
+    url.query_pairs_mut()
+        .extend_pairs(starter_params(config, dependencies));
+    Ok(url.to_string())
+}
+
+/// Whether two starter URLs request the same scaffold, however their
+/// parameters are encoded
+pub fn same_starter(a: &str, b: &str) -> bool {
+    match (Url::parse(a), Url::parse(b)) {
+        (Ok(a), Ok(b)) => {
+            a[..url::Position::AfterPath] == b[..url::Position::AfterPath]
+                && a.query_pairs().eq(b.query_pairs())
+        }
+        _ => a == b,
+    }
+}
+
+/// A dependency as resolved by start.spring.io for a specific Boot version
+#[derive(Clone)]
+pub struct CatalogEntry {
+    pub coordinates: pom::Coordinates,
+    /// Initializr scope, e.g. `compile`, `runtime`, or `annotationProcessor`
+    pub scope: String,
+    pub bom: Option<String>,
+}
+
+impl CatalogEntry {
+    pub fn same_artifact(&self, other: &CatalogEntry) -> bool {
+        self.coordinates.group_id == other.coordinates.group_id
+            && self.coordinates.artifact_id == other.coordinates.artifact_id
+    }
+
+    pub fn group_artifact(&self) -> String {
+        format!(
+            "{}:{}",
+            self.coordinates.group_id, self.coordinates.artifact_id
+        )
+    }
+
+    pub fn maven_dependency(&self) -> pom::Dependency {
+        let (scope, optional) = match self.scope.as_str() {
+            "runtime" => (Some("runtime"), false),
+            "test" => (Some("test"), false),
+            "provided" => (Some("provided"), false),
+            "annotationProcessor" | "compileOnly" => (None, true),
+            _ => (None, false),
+        };
+        pom::Dependency {
+            coordinates: self.coordinates.clone(),
+            scope: scope.map(String::from),
+            optional,
+        }
+    }
+
+    pub fn gradle_configurations(&self) -> &'static [&'static str] {
+        match self.scope.as_str() {
+            "runtime" => &["runtimeOnly"],
+            "test" => &["testImplementation"],
+            "provided" | "compileOnly" => &["compileOnly"],
+            "annotationProcessor" => &["compileOnly", "annotationProcessor"],
+            _ => &["implementation"],
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CatalogDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub renamed: Vec<(String, String)>,
+}
+
+/// Compare two dependency catalogs, treating a removed and an added ID that
+/// share the same Maven coordinates as a rename
+pub fn compare_catalogs(
+    old: &BTreeMap<String, CatalogEntry>,
+    new: &BTreeMap<String, CatalogEntry>,
+) -> CatalogDiff {
+    let mut diff = CatalogDiff {
+        added: new
+            .keys()
+            .filter(|id| !old.contains_key(*id))
+            .cloned()
+            .collect(),
+        ..Default::default()
+    };
+
+    for (id, entry) in old {
+        if new.contains_key(id) {
+            continue;
+        }
+        match diff
+            .added
+            .iter()
+            .position(|added| new[added].same_artifact(entry))
+        {
+            Some(pos) => {
+                let renamed_to = diff.added.remove(pos);
+                diff.renamed.push((id.clone(), renamed_to));
+            }
+            None => diff.removed.push(id.clone()),
+        }
+    }
+
+    diff
+}
+
+pub fn extract_scaffold(zip_path: &Path, dest: &Path, quiet: bool) -> Result<()> {
+    let file = fs::File::open(zip_path)?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to read Spring Boot scaffold: {}", e))?;
+
+    let bar = progress::items(
+        "Unzipping Spring Boot scaffold",
+        archive.len() as u64,
+        quiet,
+    )?;
+    for i in 0..archive.len() {
+        let mut entry = archive
+            .by_index(i)
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to unzip Spring Boot scaffold: {}", e))?;
+        // Skip entries that would land outside dest
+        let Some(relative) = entry.enclosed_name() else {
+            continue;
+        };
+        let out_path = dest.join(relative);
+
+        if entry.is_dir() {
+            fs::create_dir_all(&out_path)?;
+        } else {
+            if let Some(parent) = out_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut out = fs::File::create(&out_path)?;
+            std::io::copy(&mut entry, &mut out)?;
+
+            // Keep mvnw and gradlew executable
+            #[cfg(unix)]
+            if let Some(mode) = entry.unix_mode() {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(&out_path, fs::Permissions::from_mode(mode))?;
+            }
+        }
+        bar.inc(1);
+    }
+    bar.finish();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog(entries: &[(&str, &str)]) -> BTreeMap<String, CatalogEntry> {
+        entries
+            .iter()
+            .map(|(id, artifact)| {
+                let entry = CatalogEntry {
+                    coordinates: pom::Coordinates {
+                        group_id: "org.springframework.boot".to_string(),
+                        artifact_id: artifact.to_string(),
+                        version: None,
+                    },
+                    scope: "compile".to_string(),
+                    bom: None,
+                };
+                (id.to_string(), entry)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn compare_finds_added_removed_and_renamed() {
+        let old = catalog(&[
+            ("web", "spring-boot-starter-web"),
+            (
+                "cloud-contract-stub-runner",
+                "spring-cloud-contract-stub-runner",
+            ),
+            ("session", "spring-session-core"),
+        ]);
+        let new = catalog(&[
+            ("web", "spring-boot-starter-web"),
+            ("contract-stub-runner", "spring-cloud-contract-stub-runner"),
+            ("docker-compose", "spring-boot-docker-compose"),
+            ("htmx", "htmx-spring-boot-thymeleaf"),
+        ]);
+
+        let diff = compare_catalogs(&old, &new);
+
+        assert_eq!(diff.added, ["docker-compose", "htmx"]);
+        assert_eq!(diff.removed, ["session"]);
+        assert_eq!(
+            diff.renamed,
+            [(
+                "cloud-contract-stub-runner".to_string(),
+                "contract-stub-runner".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn compare_identical_catalogs() {
+        let old = catalog(&[("web", "spring-boot-starter-web")]);
+        let diff = compare_catalogs(&old, &catalog(&[("web", "spring-boot-starter-web")]));
+        assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.renamed.is_empty());
+    }
+}
@@ -0,0 +1,209 @@
+use crate::http::Http;
+use crate::{progress, ProjectConfig};
+use color_eyre::eyre::Result;
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tokio::io::AsyncWriteExt;
+
+const DISCO_URL: &str = "https://api.foojay.io/disco/v3.0";
+
+#[derive(Deserialize)]
+struct Packages {
+    result: Vec<Package>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    java_version: String,
+    distribution: String,
+    filename: String,
+    links: Links,
+}
+
+#[derive(Deserialize)]
+struct Links {
+    pkg_download_redirect: String,
+}
+
+/// Directory holding the JDKs `jdk install` downloaded, one per major version
+fn jdks_dir() -> Result<PathBuf> {
+    dirs::data_dir()
+        .map(|dir| dir.join("spring-init").join("jdks"))
+        .ok_or_else(|| color_eyre::eyre::eyre!("Could not determine data directory"))
+}
+
+/// The java launcher under a JDK's bin directory
+const JAVA: &str = if cfg!(windows) { "java.exe" } else { "java" };
+
+/// JAVA_HOME inside an extracted JDK, macOS archives nest it under Contents/Home
+fn java_home_in(dir: &Path) -> Option<PathBuf> {
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .flat_map(|path| [path.join("Contents").join("Home"), path])
+        .find(|home| home.join("bin").join(JAVA).exists())
+}
+
+/// JAVA_HOME of the managed JDK for `java_version`, if one is installed
+pub fn managed_home(java_version: &str) -> Option<PathBuf> {
+    java_home_in(&jdks_dir().ok()?.join(java_version))
+}
+
+/// Point `command` at the managed JDK for the project's java_version, so
+/// builds and runs use it instead of whatever Java is on PATH
+pub fn configure(command: &mut Command, config: &ProjectConfig) {
+    let Some(home) = managed_home(&config.java_version) else {
+        return;
+    };
+    let mut paths = vec![home.join("bin")];
+    paths.extend(env::split_paths(&env::var_os("PATH").unwrap_or_default()));
+    command.env("JAVA_HOME", &home);
+    if let Ok(path) = env::join_paths(paths) {
+        command.env("PATH", path);
+    }
+}
+
+/// The `java` to run the app with, the managed JDK's when there is one
+pub fn java(config: &ProjectConfig) -> PathBuf {
+    managed_home(&config.java_version)
+        .map(|home| home.join("bin").join(JAVA))
+        .unwrap_or_else(|| PathBuf::from("java"))
+}
+
+/// Platform names as the Disco API spells them
+fn platform() -> (&'static str, &'static str, &'static str) {
+    let os = match env::consts::OS {
+        "macos" => "macos",
+        "windows" => "windows",
+        _ => "linux",
+    };
+    let arch = match env::consts::ARCH {
+        "aarch64" => "aarch64",
+        _ => "x64",
+    };
+    let archive = if os == "windows" { "zip" } else { "tar.gz" };
+    (os, arch, archive)
+}
+
+async fn find_package(http: &Http, version: &str, distribution: &str) -> Result<Package> {
+    let (os, arch, archive) = platform();
+    let url = format!("{}/packages", DISCO_URL);
+    let response = http
+        .send(|client| {
+            client.get(&url).query(&[
+                ("version", version),
+                ("distribution", distribution),
+                ("operating_system", os),
+                ("architecture", arch),
+                ("archive_type", archive),
+                ("package_type", "jdk"),
+                ("release_status", "ga"),
+                ("latest", "available"),
+            ])
+        })
+        .await?
+        .error_for_status()?;
+    let packages: Packages = response.json().await?;
+    packages.result.into_iter().next().ok_or_else(|| {
+        color_eyre::eyre::eyre!(
+            "No {} JDK {} for {} {} found",
+            distribution,
+            version,
+            os,
+            arch
+        )
+    })
+}
+
+/// Download a JDK matching `version` from the foojay Disco API into the
+/// tool-managed directory, where `build`, `run`, `test`, and `watch` pick it
+/// up for projects with that java_version
+pub async fn install(
+    config: &ProjectConfig,
+    version: &str,
+    distribution: &str,
+    force: bool,
+    quiet: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let dir = jdks_dir()?.join(version);
+    if let Some(home) = java_home_in(&dir) {
+        if !force {
+            println!(
+                "JDK {} is already installed at {}, use --force to reinstall it",
+                version,
+                home.display()
+            );
+            return Ok(());
+        }
+    }
+
+    let http = Http::new(config)?;
+    let package = find_package(&http, version, distribution).await?;
+    if dry_run {
+        println!(
+            "Would install {} {} into {}",
+            package.distribution,
+            package.java_version,
+            dir.display()
+        );
+        return Ok(());
+    }
+
+    let mut response = http
+        .send(|client| client.get(&package.links.pkg_download_redirect))
+        .await?
+        .error_for_status()?;
+    fs::create_dir_all(&dir)?;
+    let archive = dir.join(&package.filename);
+    let bar = progress::download(
+        &format!(
+            "Downloading {} {}",
+            package.distribution, package.java_version
+        ),
+        response.content_length(),
+        quiet,
+    )?;
+    let mut file = tokio::fs::File::create(&archive).await?;
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk).await?;
+        bar.inc(chunk.len() as u64);
+    }
+    file.flush().await?;
+    bar.finish();
+
+    // Replace an earlier install only once the new archive is complete
+    for entry in fs::read_dir(&dir)?.filter_map(|entry| entry.ok()) {
+        if entry.path().is_dir() {
+            fs::remove_dir_all(entry.path())?;
+        }
+    }
+    // tar reads both the .tar.gz archives and, on Windows, the .zip ones
+    let status = Command::new("tar")
+        .arg("-xf")
+        .arg(&package.filename)
+        .current_dir(&dir)
+        .status()
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to run tar: {}", e))?;
+    fs::remove_file(&archive)?;
+    if !status.success() {
+        return Err(color_eyre::eyre::eyre!(
+            "Failed to extract {}",
+            package.filename
+        ));
+    }
+
+    let home = java_home_in(&dir)
+        .ok_or_else(|| color_eyre::eyre::eyre!("No bin/java in {}", package.filename))?;
+    println!(
+        "Installed {} {} at {}",
+        package.distribution,
+        package.java_version,
+        home.display()
+    );
+    Ok(())
+}
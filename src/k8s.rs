@@ -0,0 +1,205 @@
+use crate::ProjectConfig;
+use color_eyre::eyre::Result;
+use std::fs;
+use std::path::PathBuf;
+
+/// Port the app listens on inside the container
+const CONTAINER_PORT: u16 = 8080;
+
+/// Overlays written with `--kustomize` and their replica counts
+const OVERLAYS: &[(&str, u32)] = &[("dev", 1), ("prod", 3)];
+
+fn deployment(config: &ProjectConfig, actuator: bool) -> String {
+    let probe = |path: &str| {
+        if actuator {
+            format!(
+                "httpGet:\n              path: {}\n              port: http",
+                path
+            )
+        } else {
+            "tcpSocket:\n              port: http".to_string()
+        }
+    };
+    format!(
+        "apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: {name}
+  labels:
+    app: {name}
+spec:
+  replicas: 1
+  selector:
+    matchLabels:
+      app: {name}
+  template:
+    metadata:
+      labels:
+        app: {name}
+    spec:
+      containers:
+        - name: {name}
+          image: {image}
+          ports:
+            - name: http
+              containerPort: {port}
+          envFrom:
+            - configMapRef:
+                name: {name}
+          readinessProbe:
+            {readiness}
+            initialDelaySeconds: 10
+          livenessProbe:
+            {liveness}
+            initialDelaySeconds: 30
+",
+        name = config.app_name,
+        image = config.image(),
+        port = CONTAINER_PORT,
+        readiness = probe("/actuator/health/readiness"),
+        liveness = probe("/actuator/health/liveness"),
+    )
+}
+
+fn service(config: &ProjectConfig) -> String {
+    format!(
+        "apiVersion: v1
+kind: Service
+metadata:
+  name: {name}
+spec:
+  selector:
+    app: {name}
+  ports:
+    - name: http
+      port: 80
+      targetPort: http
+",
+        name = config.app_name,
+    )
+}
+
+fn config_map(config: &ProjectConfig) -> String {
+    format!(
+        "apiVersion: v1
+kind: ConfigMap
+metadata:
+  name: {name}
+data:
+  SERVER_PORT: \"{port}\"
+  JAVA_TOOL_OPTIONS: \"-XX:MaxRAMPercentage=75\"
+",
+        name = config.app_name,
+        port = CONTAINER_PORT,
+    )
+}
+
+fn ingress(config: &ProjectConfig, host: &str) -> String {
+    format!(
+        "apiVersion: networking.k8s.io/v1
+kind: Ingress
+metadata:
+  name: {name}
+spec:
+  rules:
+    - host: {host}
+      http:
+        paths:
+          - path: /
+            pathType: Prefix
+            backend:
+              service:
+                name: {name}
+                port:
+                  name: http
+",
+        name = config.app_name,
+    )
+}
+
+/// Manifest file names and contents
+fn manifests(config: &ProjectConfig, ingress_host: Option<&str>) -> Result<Vec<(String, String)>> {
+    let build_file = fs::read_to_string(config.build_file()).map_err(|_| {
+        color_eyre::eyre::eyre!("No {} found, run init first", config.build_file().display())
+    })?;
+    let actuator = build_file.contains("spring-boot-starter-actuator");
+    if !actuator {
+        eprintln!(
+            "Warning: actuator is not a dependency, probes check the port instead of health groups"
+        );
+    }
+
+    let mut files = vec![
+        ("deployment.yaml".to_string(), deployment(config, actuator)),
+        ("service.yaml".to_string(), service(config)),
+        ("configmap.yaml".to_string(), config_map(config)),
+    ];
+    if let Some(host) = ingress_host {
+        files.push(("ingress.yaml".to_string(), ingress(config, host)));
+    }
+    Ok(files)
+}
+
+/// Lay the manifests out as a kustomize base with one overlay per environment
+fn kustomize(config: &ProjectConfig, files: Vec<(String, String)>) -> Vec<(String, String)> {
+    let resources: String = files
+        .iter()
+        .map(|(name, _)| format!("  - {}\n", name))
+        .collect();
+    let mut laid_out: Vec<(String, String)> = files
+        .into_iter()
+        .map(|(name, content)| (format!("base/{}", name), content))
+        .collect();
+    laid_out.push((
+        "base/kustomization.yaml".to_string(),
+        format!("resources:\n{}", resources),
+    ));
+    for (overlay, replicas) in OVERLAYS {
+        laid_out.push((
+            format!("overlays/{}/kustomization.yaml", overlay),
+            format!(
+                "resources:\n  - ../../base\nreplicas:\n  - name: {}\n    count: {}\n",
+                config.app_name, replicas
+            ),
+        ));
+    }
+    laid_out
+}
+
+/// Write Kubernetes manifests under k8s/ in the project
+pub fn init(
+    config: &ProjectConfig,
+    ingress_host: Option<&str>,
+    kustomize_layout: bool,
+    force: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let mut files = manifests(config, ingress_host)?;
+    if kustomize_layout {
+        files = kustomize(config, files);
+    }
+
+    let k8s_dir = config.app_dir().join("k8s");
+    let paths: Vec<PathBuf> = files.iter().map(|(name, _)| k8s_dir.join(name)).collect();
+    if dry_run {
+        for path in &paths {
+            println!("Would write {}", path.display());
+        }
+        return Ok(());
+    }
+    if let Some(existing) = paths.iter().find(|path| path.exists()) {
+        if !force {
+            return Err(color_eyre::eyre::eyre!(
+                "{} already exists, use --force to overwrite it",
+                existing.display()
+            ));
+        }
+    }
+
+    for (path, (_, content)) in paths.iter().zip(&files) {
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, content)?;
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
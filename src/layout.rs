@@ -0,0 +1,337 @@
+use crate::project::Language;
+use crate::ProjectConfig;
+use clap::ValueEnum;
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Package skeleton created under the base package, see `layout`
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Layout {
+    /// controller, service, repository, and model packages
+    Layered,
+    /// domain, application with its ports, and adapter packages
+    Hexagonal,
+    /// One package per feature holding all of its layers
+    Feature,
+}
+
+impl Layout {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Layout::Layered => "layered",
+            Layout::Hexagonal => "hexagonal",
+            Layout::Feature => "feature",
+        }
+    }
+
+    /// Packages relative to the base package, with what belongs in them
+    fn packages(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Layout::Layered => &[
+                ("controller", "Web layer: controllers translating HTTP requests into service calls."),
+                ("service", "Business logic and transaction boundaries."),
+                ("repository", "Data access."),
+                ("model", "Domain model shared by the layers."),
+            ],
+            Layout::Hexagonal => &[
+                ("domain", "Domain model and rules, free of framework and infrastructure code."),
+                ("application", "Use case implementations orchestrating the domain."),
+                ("application.port.in", "Use cases the application offers to driving adapters."),
+                ("application.port.out", "Ports the application needs implemented by driven adapters."),
+                ("adapter.in.web", "Driving adapter exposing the use cases over HTTP."),
+                ("adapter.out.persistence", "Driven adapter implementing the persistence ports."),
+            ],
+            Layout::Feature => &[(
+                "example",
+                "The example feature, with its controller, service, repository, and model side by side.",
+            )],
+        }
+    }
+
+    /// Placeholder classes as package, class name, and source. `{base}` is
+    /// replaced with the base package. Controllers are left out unless the
+    /// project has a web stack to compile them against.
+    fn sources(&self, web: bool) -> Vec<(&'static str, &'static str, &'static str)> {
+        let sources: &[(&str, &str, &str)] = match self {
+            Layout::Layered => &[
+                ("model", "Example", MODEL),
+                ("repository", "ExampleRepository", LAYERED_REPOSITORY),
+                ("service", "ExampleService", LAYERED_SERVICE),
+                ("controller", "ExampleController", LAYERED_CONTROLLER),
+            ],
+            Layout::Hexagonal => &[
+                ("domain", "Example", MODEL),
+                (
+                    "application.port.in",
+                    "GetExampleUseCase",
+                    HEXAGONAL_USE_CASE,
+                ),
+                ("application.port.out", "LoadExamplePort", HEXAGONAL_PORT),
+                ("application", "ExampleService", HEXAGONAL_SERVICE),
+                ("adapter.in.web", "ExampleController", HEXAGONAL_CONTROLLER),
+                (
+                    "adapter.out.persistence",
+                    "ExamplePersistenceAdapter",
+                    HEXAGONAL_ADAPTER,
+                ),
+            ],
+            Layout::Feature => &[
+                ("example", "Example", MODEL),
+                ("example", "ExampleRepository", FEATURE_REPOSITORY),
+                ("example", "ExampleService", FEATURE_SERVICE),
+                ("example", "ExampleController", FEATURE_CONTROLLER),
+            ],
+        };
+        sources
+            .iter()
+            .copied()
+            .filter(|(_, name, _)| web || !name.ends_with("Controller"))
+            .collect()
+    }
+}
+
+const MODEL: &str = "package {package};
+
+public record Example(String message) {}
+";
+
+const LAYERED_REPOSITORY: &str = "package {package};
+
+import {base}.model.Example;
+import org.springframework.stereotype.Repository;
+
+@Repository
+public class ExampleRepository {
+
+    public Example find() {
+        return new Example(\"Hello\");
+    }
+}
+";
+
+const LAYERED_SERVICE: &str = "package {package};
+
+import {base}.model.Example;
+import {base}.repository.ExampleRepository;
+import org.springframework.stereotype.Service;
+
+@Service
+public class ExampleService {
+
+    private final ExampleRepository repository;
+
+    public ExampleService(ExampleRepository repository) {
+        this.repository = repository;
+    }
+
+    public Example getExample() {
+        return repository.find();
+    }
+}
+";
+
+const LAYERED_CONTROLLER: &str = "package {package};
+
+import {base}.model.Example;
+import {base}.service.ExampleService;
+import org.springframework.web.bind.annotation.GetMapping;
+import org.springframework.web.bind.annotation.RestController;
+
+@RestController
+public class ExampleController {
+
+    private final ExampleService service;
+
+    public ExampleController(ExampleService service) {
+        this.service = service;
+    }
+
+    @GetMapping(\"/example\")
+    public Example example() {
+        return service.getExample();
+    }
+}
+";
+
+const HEXAGONAL_USE_CASE: &str = "package {package};
+
+import {base}.domain.Example;
+
+public interface GetExampleUseCase {
+
+    Example getExample();
+}
+";
+
+const HEXAGONAL_PORT: &str = "package {package};
+
+import {base}.domain.Example;
+
+public interface LoadExamplePort {
+
+    Example load();
+}
+";
+
+const HEXAGONAL_SERVICE: &str = "package {package};
+
+import {base}.application.port.in.GetExampleUseCase;
+import {base}.application.port.out.LoadExamplePort;
+import {base}.domain.Example;
+import org.springframework.stereotype.Service;
+
+@Service
+public class ExampleService implements GetExampleUseCase {
+
+    private final LoadExamplePort loadExamplePort;
+
+    public ExampleService(LoadExamplePort loadExamplePort) {
+        this.loadExamplePort = loadExamplePort;
+    }
+
+    @Override
+    public Example getExample() {
+        return loadExamplePort.load();
+    }
+}
+";
+
+const HEXAGONAL_CONTROLLER: &str = "package {package};
+
+import {base}.application.port.in.GetExampleUseCase;
+import {base}.domain.Example;
+import org.springframework.web.bind.annotation.GetMapping;
+import org.springframework.web.bind.annotation.RestController;
+
+@RestController
+public class ExampleController {
+
+    private final GetExampleUseCase getExampleUseCase;
+
+    public ExampleController(GetExampleUseCase getExampleUseCase) {
+        this.getExampleUseCase = getExampleUseCase;
+    }
+
+    @GetMapping(\"/example\")
+    public Example example() {
+        return getExampleUseCase.getExample();
+    }
+}
+";
+
+const HEXAGONAL_ADAPTER: &str = "package {package};
+
+import {base}.application.port.out.LoadExamplePort;
+import {base}.domain.Example;
+import org.springframework.stereotype.Component;
+
+@Component
+public class ExamplePersistenceAdapter implements LoadExamplePort {
+
+    @Override
+    public Example load() {
+        return new Example(\"Hello\");
+    }
+}
+";
+
+const FEATURE_REPOSITORY: &str = "package {package};
+
+import org.springframework.stereotype.Repository;
+
+@Repository
+class ExampleRepository {
+
+    Example find() {
+        return new Example(\"Hello\");
+    }
+}
+";
+
+const FEATURE_SERVICE: &str = "package {package};
+
+import org.springframework.stereotype.Service;
+
+@Service
+public class ExampleService {
+
+    private final ExampleRepository repository;
+
+    ExampleService(ExampleRepository repository) {
+        this.repository = repository;
+    }
+
+    public Example getExample() {
+        return repository.find();
+    }
+}
+";
+
+const FEATURE_CONTROLLER: &str = "package {package};
+
+import org.springframework.web.bind.annotation.GetMapping;
+import org.springframework.web.bind.annotation.RestController;
+
+@RestController
+class ExampleController {
+
+    private final ExampleService service;
+
+    ExampleController(ExampleService service) {
+        this.service = service;
+    }
+
+    @GetMapping(\"/example\")
+    Example example() {
+        return service.getExample();
+    }
+}
+";
+
+/// Create the layout's packages under the base package, each with a
+/// package-info.java, and placeholder classes wiring them together. Existing
+/// files are left alone.
+pub fn write(config: &ProjectConfig, layout: Layout, dependencies: &[String]) -> Result<()> {
+    if config.language != Language::Java {
+        eprintln!("Skipping layout for {} project", config.language.as_str());
+        return Ok(());
+    }
+
+    let base = &config.package_name;
+    let web = dependencies
+        .iter()
+        .any(|dependency| dependency == "web" || dependency == "webflux");
+    let dir_of = |package: &str| {
+        package
+            .split('.')
+            .fold(config.source_dir(), |dir, segment| dir.join(segment))
+    };
+
+    for (package, description) in layout.packages() {
+        let dir = dir_of(package);
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("package-info.java");
+        if !path.exists() {
+            fs::write(
+                &path,
+                format!("/** {} */\npackage {}.{};\n", description, base, package),
+            )?;
+        }
+    }
+    for (package, name, template) in layout.sources(web) {
+        let path = dir_of(package).join(format!("{}.java", name));
+        if path.exists() {
+            continue;
+        }
+        fs::write(
+            &path,
+            template
+                .replace("{package}", &format!("{}.{}", base, package))
+                .replace("{base}", base),
+        )?;
+    }
+    eprintln!("Created {} package layout", layout.as_str());
+    Ok(())
+}
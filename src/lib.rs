@@ -0,0 +1,66 @@
+//! Create and manage Spring Boot projects.
+//!
+//! The `spring-init` binary is a thin CLI over this crate. Other tools can
+//! generate projects with [`InitRequest`], talk to start.spring.io with
+//! [`initializr::Client`], work on a generated [`Project`], and ask an AI
+//! provider for dependencies with [`ai::Suggester`].
+
+pub mod ai;
+mod application_yml;
+pub mod audit;
+pub mod cache;
+pub mod ci;
+pub mod compose;
+pub mod config;
+pub mod deps;
+pub mod devcontainer;
+pub mod docker;
+pub mod doctor;
+pub mod export;
+pub mod external;
+pub mod generate;
+pub mod git;
+mod gradle;
+pub mod hooks;
+pub mod http;
+pub mod ide;
+pub mod import;
+pub mod init;
+pub mod initializr;
+pub mod jdk;
+pub mod k8s;
+pub mod layout;
+pub mod lock;
+pub mod logs;
+pub mod metadata;
+mod modules;
+pub mod native;
+pub mod openapi;
+pub mod outdated;
+pub mod output;
+pub mod plugins;
+mod pom;
+mod processors;
+pub mod profiles;
+mod progress;
+pub mod project;
+pub mod projects;
+pub mod quality;
+pub mod rename;
+pub mod repositories;
+pub mod run;
+pub mod scaffold;
+pub mod template;
+pub mod test;
+pub mod toolchains;
+pub mod upgrade;
+pub mod versions;
+pub mod watch;
+pub mod wizard;
+pub mod workspace;
+pub mod wrapper;
+
+pub use init::InitRequest;
+pub use project::{
+    BuildTool, IncompatiblePolicy, Language, Packaging, Project, ProjectConfig, TokenPrice,
+};
@@ -0,0 +1,353 @@
+use clap::{Args, ValueEnum};
+use std::io::{self, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Severity of a log entry, `--level` shows this level and above
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_uppercase().as_str() {
+            "TRACE" => Some(Level::Trace),
+            "DEBUG" => Some(Level::Debug),
+            "INFO" => Some(Level::Info),
+            "WARN" | "WARNING" => Some(Level::Warn),
+            "ERROR" | "FATAL" => Some(Level::Error),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Level::Trace => "TRACE",
+            Level::Debug => "DEBUG",
+            Level::Info => "INFO",
+            Level::Warn => "WARN",
+            Level::Error => "ERROR",
+        }
+    }
+
+    fn color(&self) -> &'static str {
+        match self {
+            Level::Trace | Level::Debug => "\x1b[2m",
+            Level::Info => "\x1b[32m",
+            Level::Warn => "\x1b[33m",
+            Level::Error => "\x1b[31m",
+        }
+    }
+}
+
+const LOGGER_COLOR: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// Which entries `logs` prints
+#[derive(Args, Default)]
+pub struct LogFilter {
+    /// Only print entries at this level or above
+    #[arg(long, value_enum)]
+    pub level: Option<Level>,
+    /// Only print entries from this logger or the loggers under it, e.g. com.acme
+    #[arg(long)]
+    pub logger: Option<String>,
+    /// Only print entries from the last duration, e.g. 30s, 10m, 2h, or 1d
+    #[arg(long, value_parser = parse_since)]
+    pub since: Option<Duration>,
+}
+
+fn parse_since(value: &str) -> Result<Duration, String> {
+    let error = || {
+        format!(
+            "expected a duration like 30s, 10m, 2h, or 1d, got {}",
+            value
+        )
+    };
+    let split = value.char_indices().last().map_or(0, |(i, _)| i);
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount.parse().map_err(|_| error())?;
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return Err(error()),
+    };
+    Ok(Duration::from_secs(amount * seconds))
+}
+
+impl LogFilter {
+    fn is_empty(&self) -> bool {
+        self.level.is_none() && self.logger.is_none() && self.since.is_none()
+    }
+}
+
+/// A line starting an entry, in Spring Boot's console format or as JSON
+struct Entry {
+    time: Option<SystemTime>,
+    level: Level,
+    logger: Option<String>,
+    /// The line as printed, JSON entries are rendered like console ones
+    text: String,
+}
+
+/// Whether `logger` is `prefix` or below it, comparing segment by segment
+/// so that abbreviated names like `c.a.d.DemoApplication` match `com.acme`
+fn logger_matches(logger: &str, prefix: &str) -> bool {
+    let segments: Vec<&str> = logger.split('.').collect();
+    let wanted: Vec<&str> = prefix.split('.').collect();
+    wanted.len() <= segments.len()
+        && wanted
+            .iter()
+            .zip(&segments)
+            .enumerate()
+            .all(|(i, (want, seg))| {
+                want == seg
+                // Boot shortens all but the last segment to initials
+                || (i + 1 < segments.len() && seg.len() == 1 && want.starts_with(seg))
+            })
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Seconds east of UTC of the local time zone, older Boot versions log
+/// timestamps without an offset
+#[cfg(unix)]
+fn local_offset() -> i64 {
+    // SAFETY: localtime_r only writes to the tm passed in
+    unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        let mut tm: libc::tm = std::mem::zeroed();
+        if libc::localtime_r(&now, &mut tm).is_null() {
+            return 0;
+        }
+        tm.tm_gmtoff as i64
+    }
+}
+
+#[cfg(not(unix))]
+fn local_offset() -> i64 {
+    0
+}
+
+/// Parse `2024-01-15T10:30:45.123+01:00`, `2024-01-15T09:30:45.123Z`, or
+/// `2024-01-15 10:30:45.123` in local time
+fn parse_timestamp(stamp: &str) -> Option<SystemTime> {
+    let (date, time) = stamp.split_once(['T', ' '])?;
+    let mut date = date.splitn(3, '-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+    let (clock, offset) = if let Some(clock) = time.strip_suffix('Z') {
+        (clock, Some(0))
+    } else if let Some(pos) = time.rfind(['+', '-']) {
+        let (clock, offset) = time.split_at(pos);
+        let sign = if offset.starts_with('-') { -1 } else { 1 };
+        let digits: String = offset[1..].chars().filter(|c| *c != ':').collect();
+        let hours: i64 = digits.get(..2)?.parse().ok()?;
+        let minutes: i64 = digits
+            .get(2..)
+            .filter(|m| !m.is_empty())
+            .map_or(Some(0), |m| m.parse().ok())?;
+        (clock, Some(sign * (hours * 3600 + minutes * 60)))
+    } else {
+        (time, None)
+    };
+    let clock = clock.split(['.', ',']).next()?;
+    let mut clock = clock.splitn(3, ':').map(|part| part.parse::<i64>().ok());
+    let (hour, minute, second) = (clock.next()??, clock.next()??, clock.next()??);
+    let local = days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second;
+    let utc = local - offset.unwrap_or_else(local_offset);
+    Some(UNIX_EPOCH + Duration::from_secs(u64::try_from(utc).ok()?))
+}
+
+/// `2024-01-15T10:30:45.123+01:00  INFO 12345 --- [demo] [main] c.a.d.DemoApplication : Started`,
+/// or with `2024-01-15 10:30:45.123  INFO` before Boot 3.2
+fn parse_plain(line: &str) -> Option<Entry> {
+    let mut tokens = line.split_whitespace();
+    let first = tokens.next()?;
+    let bytes = first.as_bytes();
+    if bytes.len() < 10 || !bytes[..4].iter().all(u8::is_ascii_digit) || bytes[4] != b'-' {
+        return None;
+    }
+    let (stamp, level) = if first.contains('T') {
+        (first.to_string(), tokens.next()?)
+    } else {
+        (format!("{} {}", first, tokens.next()?), tokens.next()?)
+    };
+    let level = Level::parse(level)?;
+    let logger = line
+        .find(" : ")
+        .and_then(|end| line[..end].split_whitespace().last())
+        .map(str::to_string);
+    Some(Entry {
+        time: parse_timestamp(&stamp),
+        level,
+        logger,
+        text: line.to_string(),
+    })
+}
+
+/// A JSON line from the logstash encoder or Boot's structured logging, in
+/// the logstash or ECS format
+fn parse_json(line: &str) -> Option<Entry> {
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+    let object = value.as_object()?;
+    let field = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| object.get(*name).and_then(|value| value.as_str()))
+    };
+    let level = Level::parse(field(&["level", "log.level"])?)?;
+    let stamp = field(&["@timestamp", "timestamp"]);
+    let logger = field(&["logger_name", "log.logger", "logger"]);
+    let thread = field(&["thread_name", "process.thread.name"]);
+    let mut text = format!("{} {:>5}", stamp.unwrap_or("-"), level.as_str());
+    if let Some(thread) = thread {
+        text.push_str(&format!(" [{}]", thread));
+    }
+    if let Some(logger) = logger {
+        text.push_str(&format!(" {}", logger));
+    }
+    text.push_str(&format!(" : {}", field(&["message"]).unwrap_or("")));
+    if let Some(stack) = field(&["stack_trace", "error.stack_trace"]) {
+        text.push('\n');
+        text.push_str(stack.trim_end());
+    }
+    Some(Entry {
+        time: stamp.and_then(parse_timestamp),
+        level,
+        logger: logger.map(str::to_string),
+        text,
+    })
+}
+
+/// Filters and colorizes log output fed in chunks, keeping continuation
+/// lines such as stack traces with the entry they belong to
+pub struct Printer {
+    filter: LogFilter,
+    since: Option<SystemTime>,
+    color: bool,
+    /// Whether the entry the next continuation line belongs to is shown
+    showing: bool,
+    /// Incomplete last line of the previous chunk
+    pending: Vec<u8>,
+}
+
+impl Printer {
+    pub fn new(filter: LogFilter, color: bool) -> Self {
+        let since = filter.since.map(|since| SystemTime::now() - since);
+        Printer {
+            showing: filter.is_empty(),
+            filter,
+            since,
+            color,
+            pending: Vec::new(),
+        }
+    }
+
+    fn matches(&self, entry: &Entry) -> bool {
+        self.filter.level.is_none_or(|level| entry.level >= level)
+            && self.filter.logger.as_deref().is_none_or(|prefix| {
+                entry
+                    .logger
+                    .as_deref()
+                    .is_some_and(|logger| logger_matches(logger, prefix))
+            })
+            && self
+                .since
+                .is_none_or(|since| entry.time.is_some_and(|time| time >= since))
+    }
+
+    fn paint(&self, entry: &Entry) -> String {
+        if !self.color {
+            return entry.text.clone();
+        }
+        let (first, rest) = entry
+            .text
+            .split_once('\n')
+            .map_or((entry.text.as_str(), None), |(first, rest)| {
+                (first, Some(rest))
+            });
+        let mut line = first.to_string();
+        if let Some(end) = line.find(" : ") {
+            if let Some(logger) = entry.logger.as_deref() {
+                if let Some(start) = line[..end].rfind(logger) {
+                    line.replace_range(
+                        start..start + logger.len(),
+                        &format!("{}{}{}", LOGGER_COLOR, logger, RESET),
+                    );
+                }
+            }
+        }
+        let level = entry.level.as_str();
+        if let Some(start) = line.find(level) {
+            line.replace_range(
+                start..start + level.len(),
+                &format!("{}{}{}", entry.level.color(), level, RESET),
+            );
+        }
+        match rest {
+            Some(rest) => format!("{}\n{}{}{}", line, entry.level.color(), rest, RESET),
+            None => line,
+        }
+    }
+
+    fn line(&mut self, out: &mut impl Write, line: &str) -> io::Result<()> {
+        let entry = if line.starts_with('{') {
+            parse_json(line)
+        } else {
+            parse_plain(line)
+        };
+        match entry {
+            Some(entry) => {
+                self.showing = self.matches(&entry);
+                if self.showing {
+                    writeln!(out, "{}", self.paint(&entry))?;
+                }
+            }
+            None if self.showing => writeln!(out, "{}", line)?,
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// Print the complete lines of `chunk` that pass the filter
+    pub fn print(&mut self, out: &mut impl Write, chunk: &[u8]) -> io::Result<()> {
+        self.pending.extend_from_slice(chunk);
+        let Some(end) = self.pending.iter().rposition(|b| *b == b'\n') else {
+            return Ok(());
+        };
+        let complete: Vec<u8> = self.pending.drain(..=end).collect();
+        for line in String::from_utf8_lossy(&complete).lines() {
+            self.line(out, line)?;
+        }
+        Ok(())
+    }
+
+    /// Print what's left of a last line without a newline
+    pub fn finish(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let rest = std::mem::take(&mut self.pending);
+        if !rest.is_empty() {
+            self.line(out, &String::from_utf8_lossy(&rest))?;
+        }
+        Ok(())
+    }
+
+    /// Drop a partial line when the log starts over
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.showing = self.filter.is_empty();
+    }
+}
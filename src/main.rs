@@ -1,15 +1,42 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::engine::ArgValueCompleter;
+use clap_complete::env::{CompleteEnv, Shells};
+use clap_complete::Shell;
 use color_eyre::eyre::Result;
-use serde::{Deserialize, Serialize};
-use std::fs;
-use std::path::{Path, PathBuf};
-use std::process::Command;
-mod claude;
+use spring_init::ai::provider::ProviderKind;
+use spring_init::ai::{auth, suggest};
+use spring_init::layout::Layout;
+use spring_init::logs::LogFilter;
+use spring_init::output::OutputFormat;
+use spring_init::profiles::Environment;
+use spring_init::quality::QualityTool;
+use spring_init::{
+    audit, cache, ci, compose, config, deps, devcontainer, docker, doctor, export, external,
+    generate, git, ide, import, init, jdk, k8s, lock, native, outdated, plugins, project, projects,
+    rename, repositories, run, scaffold, template, test, upgrade, versions, watch, wizard,
+    workspace, wrapper, BuildTool, IncompatiblePolicy, InitRequest, Language, Packaging,
+    ProjectConfig,
+};
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(name = "spring-init")]
 #[command(about = "Create and manage Spring Boot projects", long_about = None)]
 struct Cli {
+    /// Suppress informational output
+    #[arg(long, short, global = true)]
+    quiet: bool,
+    /// AI provider used for dependency suggestions
+    #[arg(long, global = true, value_enum)]
+    provider: Option<ProviderKind>,
+    /// Print what init, reset, clean, compose, ci, and k8s init would do without touching the network or filesystem
+    #[arg(long, global = true)]
+    dry_run: bool,
+    /// Output format for info, deps, suggest-deps, init, and run
+    #[arg(long, global = true, value_enum, default_value_t)]
+    output: OutputFormat,
     #[command(subcommand)]
     command: Commands,
 }
@@ -18,387 +45,967 @@ struct Cli {
 enum Commands {
     /// Display project information
     Info,
-    /// Reset the project state
-    Reset,
+    /// Delete the project directory
+    Reset {
+        /// Don't ask for confirmation
+        #[arg(long, short)]
+        force: bool,
+    },
+    /// Delete build output (target/ or build/), keeping the project
+    Clean,
+    /// Check that Java, Maven, start.spring.io, and the AI provider are usable
+    Doctor,
     /// Initialize a new Spring Boot project
-    Init {
-        /// Path to PRD file for automatic dependency selection
+    Init(Box<InitArgs>),
+    /// List the templates registered for init --template
+    Templates,
+    /// Register an existing Maven or Gradle Spring Boot project as the active project
+    Import {
+        /// Directory containing pom.xml, build.gradle, or build.gradle.kts
+        path: PathBuf,
+        /// Name to register the project under, defaults to the artifactId or root project name
         #[arg(long)]
-        prd: Option<String>,
-        /// Additional dependencies to always include
-        #[arg(long, value_delimiter = ',')]
-        include: Option<Vec<String>>,
+        name: Option<String>,
+    },
+    /// Generate a project again from its spring-init.lock
+    Regenerate {
+        /// Lockfile to replay, defaults to the active project's spring-init.lock
+        lockfile: Option<PathBuf>,
+        /// Overwrite the existing project directory
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print a standalone command that downloads the same scaffold as init
+    Export {
+        #[arg(long, value_enum, default_value = "curl")]
+        format: export::Format,
+        /// Additional dependencies, replacing the active project's locked ones
+        #[arg(long, value_delimiter = ',', add = ArgValueCompleter::new(deps::complete_ids))]
+        include: Vec<String>,
+    },
+    /// Move the project to another Spring Boot version and build it
+    Upgrade {
+        /// Target version, e.g. 3.4.3, or 3.4.x for the newest 3.4 release
+        #[arg(long)]
+        boot: String,
+        /// Migrate code and properties with the OpenRewrite Spring Boot upgrade recipe
+        #[arg(long)]
+        openrewrite: bool,
+        /// Skip the confirming build
+        #[arg(long)]
+        no_build: bool,
     },
+    /// Write a CycloneDX SBOM and check its components for known vulnerabilities
+    Audit,
     /// Build the project
-    Build,
-    /// List all available dependency IDs
-    Deps,
+    Build {
+        /// Continue past module failures and report them all at the end
+        #[arg(long, conflicts_with = "native")]
+        keep_going: bool,
+        /// Compile a GraalVM native executable instead of a jar
+        #[arg(long)]
+        native: bool,
+        /// Build with the environment's Maven profile, see --env-profiles
+        #[arg(long, value_enum, conflicts_with = "native")]
+        env: Option<Environment>,
+        /// Run through verify, failing on formatting and Checkstyle violations (Maven only)
+        #[arg(long, conflicts_with = "native")]
+        strict: bool,
+    },
+    /// Run the tests and summarize the failures
+    Test {
+        /// Only run matching tests, e.g. FooServiceTest or FooServiceTest#shouldSave
+        #[arg(long)]
+        filter: Option<String>,
+    },
+    /// Build and run a container image of the project
+    #[command(subcommand)]
+    Docker(DockerCommand),
+    /// Write a devcontainer for VS Code and Codespaces
+    Devcontainer {
+        /// Overwrite existing devcontainer files
+        #[arg(long)]
+        force: bool,
+    },
+    /// Generate code from built-in templates
+    #[command(subcommand)]
+    Scaffold(ScaffoldCommand),
+    /// Write IDE run configurations for running and debugging the app
+    Ide {
+        #[arg(value_enum)]
+        target: ide::Target,
+        /// Spring profiles to activate, e.g. dev or dev,local
+        #[arg(long)]
+        profile: Option<String>,
+        /// JVM option for the app, e.g. --jvm-arg=-Xmx512m (repeatable)
+        #[arg(long = "jvm-arg", allow_hyphen_values = true)]
+        jvm_args: Vec<String>,
+        /// Port of the configuration attaching to `run --debug`
+        #[arg(long, default_value_t = run::DEBUG_PORT)]
+        debug_port: u16,
+        /// Overwrite existing run configurations
+        #[arg(long)]
+        force: bool,
+    },
+    /// Write a CI pipeline for the project
+    Ci {
+        #[arg(value_enum)]
+        target: ci::Target,
+        /// Add a job publishing a container image from the default branch
+        #[arg(long)]
+        publish: bool,
+        /// Overwrite an existing pipeline file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Generate Kubernetes manifests for the project
+    #[command(subcommand)]
+    K8s(K8sCommand),
+    /// Write docker-compose.yml with the backing services the project depends on
+    Compose {
+        /// Overwrite an existing docker-compose.yml
+        #[arg(long)]
+        force: bool,
+    },
+    /// Run the built application jar
+    Run(run::RunArgs),
+    /// Stop an app started with `run --detach`
+    Stop,
+    /// Show whether an app started with `run --detach` is running
+    Status,
+    /// Print the output of an app started with `run --detach`
+    Logs {
+        /// Keep printing new output
+        #[arg(long, short)]
+        follow: bool,
+        #[command(flatten)]
+        filter: LogFilter,
+    },
+    /// Run the app, recompiling and restarting it when sources change
+    Watch {
+        /// Spring profiles to activate, e.g. dev or dev,local
+        #[arg(long)]
+        profile: Option<String>,
+        /// Milliseconds to wait for changes to settle before compiling
+        #[arg(long, default_value_t = 500)]
+        debounce: u64,
+    },
+    /// Add or update configured Maven plugins, BOMs, and dependencies in the existing project's pom.xml
+    SyncPlugins,
+    /// Manage the project's Maven wrapper
+    #[command(subcommand)]
+    Wrapper(WrapperCommand),
+    /// Manage JDKs used to build and run projects
+    #[command(subcommand)]
+    Jdk(JdkCommand),
+    /// Manage private Maven repositories and their credentials
+    #[command(subcommand)]
+    Repos(ReposCommand),
+    /// Add dependencies to the existing project by initializr ID
+    AddDep {
+        /// Dependency IDs, e.g. data-jpa, or `@name` for a preset
+        #[arg(required = true, add = ArgValueCompleter::new(deps::complete_ids))]
+        ids: Vec<String>,
+    },
+    /// Remove dependencies from the existing project by initializr ID
+    RemoveDep {
+        /// Dependency IDs, e.g. security, or `@name` for a preset
+        #[arg(required = true, add = ArgValueCompleter::new(deps::complete_ids))]
+        ids: Vec<String>,
+    },
+    /// List, search, and inspect available dependencies
+    Deps {
+        #[command(subcommand)]
+        command: Option<DepsCommand>,
+        /// Download fresh metadata instead of using the cached copy
+        #[arg(long, global = true)]
+        refresh: bool,
+    },
+    /// List the Spring Boot versions offered by start.spring.io
+    Versions {
+        /// Download fresh metadata instead of using the cached copy
+        #[arg(long)]
+        refresh: bool,
+    },
     /// Suggest dependencies based on PRD
     SuggestDeps {
         /// Path to PRD file
         #[arg(long)]
         prd: String,
+        /// Ask the AI provider again instead of reusing cached suggestions
+        #[arg(long)]
+        no_cache: bool,
     },
+    /// Generate application code with the AI provider
+    #[command(subcommand)]
+    Generate(GenerateCommand),
+    /// Manage the projects generated under projects_dir or imported
+    #[command(subcommand)]
+    Projects(ProjectsCommand),
+    /// Generate, build, and compose the services listed in a workspace manifest
+    Workspace {
+        #[command(subcommand)]
+        command: WorkspaceCommand,
+        /// Workspace manifest
+        #[arg(long, global = true, default_value = "workspace.yaml")]
+        file: PathBuf,
+    },
+    /// Create, show, or edit the config file
+    #[command(subcommand)]
+    Config(ConfigCommand),
+    /// Store or remove AI provider API keys in the OS keyring
+    #[command(subcommand)]
+    Auth(AuthCommand),
+    /// Rename the app and/or move its sources to another base package
+    Rename {
+        /// New app name, renames the project directory and sets name and artifactId
+        #[arg(long, required_unless_present = "package")]
+        app: Option<String>,
+        /// New base package, moves the sources and sets groupId
+        #[arg(long)]
+        package: Option<String>,
+    },
+    /// Print a shell completion script, e.g. `source <(spring-init completions bash)`
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    /// Runs `spring-init-<name>` from PATH
+    #[command(external_subcommand)]
+    External(Vec<String>),
 }
 
-#[derive(Serialize, Deserialize)]
-struct ProjectConfig {
-    boot_version: String,
-    java_version: String,
-    app_name: String,
-    app_version: String,
-    package_name: String,
-    projects_dir: String,
-    maven_plugins: Vec<String>,
-    include_deps: Vec<String>,
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Write a starter config file
+    Init {
+        /// Write the user-wide config instead of the one in the current directory
+        #[arg(long)]
+        global: bool,
+    },
+    /// Print the effective config and the files it was loaded from
+    Show,
+    /// Set a config value, e.g. `ai.provider openai` or `include_deps '["web"]'`
+    Set {
+        /// Dotted key, e.g. ai.model
+        key: String,
+        /// Value, parsed as JSON if possible
+        value: String,
+        /// Edit the user-wide config instead of the one in the current directory
+        #[arg(long)]
+        global: bool,
+    },
 }
 
-impl ProjectConfig {
-    fn new() -> Result<Self> {
-        let config_str = fs::read_to_string("config.json")?;
-        let config: ProjectConfig = serde_json::from_str(&config_str)?;
-        Ok(config)
-    }
+#[derive(Subcommand)]
+enum AuthCommand {
+    /// Store an API key or GitHub token, read without echo or from stdin
+    Login {
+        #[arg(value_enum)]
+        service: AuthService,
+    },
+    /// Remove a stored API key or GitHub token
+    Logout {
+        #[arg(value_enum)]
+        service: AuthService,
+    },
+}
 
-    fn app_dir(&self) -> PathBuf {
-        PathBuf::from(&self.projects_dir).join(&self.app_name)
-    }
+/// What `auth` stores a credential for, an AI provider or GitHub
+#[derive(Clone, Copy, ValueEnum)]
+enum AuthService {
+    Anthropic,
+    #[value(name = "openai")]
+    OpenAi,
+    Ollama,
+    Github,
+}
 
-    fn jar_path(&self) -> PathBuf {
-        self.app_dir()
-            .join("target")
-            .join(format!("{}-{}.jar", self.app_name, self.app_version))
+impl AuthService {
+    fn provider(self) -> Option<ProviderKind> {
+        match self {
+            AuthService::Anthropic => Some(ProviderKind::Anthropic),
+            AuthService::OpenAi => Some(ProviderKind::OpenAi),
+            AuthService::Ollama => Some(ProviderKind::Ollama),
+            AuthService::Github => None,
+        }
     }
 }
 
-async fn suggest_dependencies(prd_path: &str) -> Result<()> {
-    // Read the PRD file
-    let prd_content = fs::read_to_string(prd_path)?;
-
-    // Read the dependencies metadata
-    let deps_content = fs::read_to_string("client.json")?;
-    let deps: serde_json::Value = serde_json::from_str(&deps_content)?;
-
-    // Create a system prompt that includes the dependencies data
-    let system_prompt = format!(
-        "You are an expert in Spring Boot applications. Your task is to analyze a PRD (Product Requirements Document) \
-        and suggest the most appropriate Spring Boot dependencies from the available options. Here is the list of \
-        available dependencies with their descriptions:\n\n{}\n\nAnalyze the following PRD and respond with a list \
-        of recommended dependency IDs, along with a brief explanation of why each dependency is needed. Only include \
-        dependencies that are directly relevant to the requirements.",
-        serde_json::to_string_pretty(&deps["dependencies"]["values"])?
-    );
+#[derive(Subcommand)]
+enum DepsCommand {
+    /// List all available dependency IDs (the default)
+    List,
+    /// Find dependencies by ID, name, or description, tolerating typos
+    Search {
+        /// Search term, e.g. kafka
+        query: String,
+    },
+    /// Print everything the metadata knows about a dependency
+    Show {
+        /// Dependency ID, e.g. data-jpa
+        id: String,
+    },
+    /// Show dependencies added, removed, or renamed between two Boot versions
+    Compare {
+        /// Boot version to compare from, e.g. 3.3.0
+        from: String,
+        /// Boot version to compare to, e.g. 3.4.0
+        to: String,
+    },
+    /// Show the project's dependencies and plugins with newer versions on Maven Central
+    Outdated,
+    /// Bump a dependency or plugin declared with an explicit version
+    Upgrade {
+        /// group:artifact or artifactId, e.g. lombok
+        artifact: String,
+        /// Version to use instead of the newest release
+        #[arg(long)]
+        version: Option<String>,
+    },
+}
 
-    // Initialize Claude client
-    let claude = claude::ClaudeClient::new()?;
+#[derive(Subcommand)]
+enum GenerateCommand {
+    /// Initialize a project from a PRD, then generate entities, repositories,
+    /// services, controllers, and application.yml for it
+    App(Box<InitArgs>),
+    /// Generate JPA entities and repositories for the existing project
+    Entities {
+        /// PRD describing the domain
+        #[arg(long, required_unless_present = "schema", conflicts_with = "schema")]
+        prd: Option<String>,
+        /// SQL DDL file with the tables to map
+        #[arg(long)]
+        schema: Option<String>,
+        /// Add Lombok and use it instead of hand-written accessors
+        #[arg(long)]
+        lombok: bool,
+    },
+    /// Generate JUnit 5 tests for the existing project's sources and run them
+    Tests {
+        /// Fully qualified class to test, e.g. com.acme.FooService (default: all classes)
+        #[arg(long)]
+        class: Option<String>,
+    },
+}
 
-    // Get dependency suggestions
-    let response = claude.send_message(&system_prompt, &prd_content).await?;
-    println!("{}", response);
+#[derive(Subcommand)]
+enum DockerCommand {
+    /// Build an image from a generated Dockerfile or with Cloud Native Buildpacks
+    Build {
+        #[arg(long, value_enum, default_value_t)]
+        strategy: docker::Strategy,
+        /// Image name and tag, overrides image
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    /// Run the built image
+    Run {
+        /// Image name and tag, overrides image
+        #[arg(long)]
+        tag: Option<String>,
+        /// Host port to publish the app on
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+    },
+}
 
-    Ok(())
+#[derive(Subcommand)]
+enum K8sCommand {
+    /// Write Deployment, Service, ConfigMap, and optional Ingress manifests under k8s/
+    Init {
+        /// Also write an Ingress routing this host to the app
+        #[arg(long)]
+        ingress: Option<String>,
+        /// Write a kustomize base with dev and prod overlays
+        #[arg(long)]
+        kustomize: bool,
+        /// Overwrite existing manifests
+        #[arg(long)]
+        force: bool,
+    },
 }
 
-async fn list_dependencies() -> Result<()> {
-    println!("Fetching available dependencies from start.spring.io...");
-    let client = reqwest::Client::new();
-    let response = client
-        .get("https://start.spring.io/metadata/client")
-        .send()
-        .await
-        .map_err(|e| color_eyre::eyre::eyre!("Failed to fetch dependencies: {}", e))?
-        .json::<serde_json::Value>()
-        .await
-        .map_err(|e| color_eyre::eyre::eyre!("Failed to parse response: {}", e))?;
+#[derive(Subcommand)]
+enum WrapperCommand {
+    /// Install or update the Maven wrapper, pinning the Maven version it downloads
+    Update {
+        /// Maven version for the wrapper to run
+        #[arg(long, default_value = wrapper::MAVEN_VERSION)]
+        maven: String,
+    },
+}
 
-    let mut dep_list: Vec<(String, String)> = Vec::new();
+#[derive(Subcommand)]
+enum JdkCommand {
+    /// Download a JDK from the foojay Disco API for build, run, test, and watch to use
+    Install {
+        /// Java major version, defaults to java_version
+        version: Option<String>,
+        /// Distribution, e.g. temurin, zulu, or corretto
+        #[arg(long, default_value = "temurin")]
+        distribution: String,
+        /// Reinstall a JDK that is already installed
+        #[arg(long)]
+        force: bool,
+    },
+}
 
-    // Process nested dependencies
-    if let Some(categories) = response["dependencies"]["values"].as_array() {
-        for category in categories {
-            if let Some(deps) = category["values"].as_array() {
-                for dep in deps {
-                    if let (Some(id), Some(name), Some(description)) = (
-                        dep["id"].as_str(),
-                        dep["name"].as_str(),
-                        dep["description"].as_str(),
-                    ) {
-                        dep_list.push((id.to_string(), format!("{} - {}", name, description)));
-                    }
-                }
-            }
-        }
-    }
+#[derive(Subcommand)]
+enum ScaffoldCommand {
+    /// Generate an entity, repository, service, REST controller, DTOs, and tests
+    Crud {
+        /// Entity class name, e.g. Customer
+        entity: String,
+        /// Fields as name:Type, e.g. "name:String,email:String,createdAt:Instant"
+        #[arg(long)]
+        fields: String,
+        /// Overwrite existing files
+        #[arg(long)]
+        force: bool,
+    },
+}
 
-    // Sort by ID
-    dep_list.sort_by(|a, b| a.0.cmp(&b.0));
+#[derive(Subcommand)]
+enum ReposCommand {
+    /// Write maven_repositories and maven_mirrors into the project's .mvn/settings.xml or pom.xml
+    Sync,
+    /// Store a repository or mirror password in the OS keyring, read without echo or from stdin
+    Login {
+        /// Repository or mirror ID
+        id: String,
+    },
+    /// Remove a stored repository or mirror password
+    Logout {
+        /// Repository or mirror ID
+        id: String,
+    },
+}
 
-    // Sort dependencies by ID
-    dep_list.sort_by(|a, b| a.0.cmp(&b.0));
+#[derive(Subcommand)]
+enum ProjectsCommand {
+    /// List generated projects, marking the active one
+    List,
+    /// Make a project the target of build, add-dep, and other commands
+    Use {
+        /// Project (app) name
+        name: String,
+    },
+    /// Forget a project
+    Remove {
+        /// Project (app) name
+        name: String,
+        /// Also delete the project directory
+        #[arg(long)]
+        delete: bool,
+    },
+}
 
-    // Print in a formatted table
-    println!("Available Spring Boot Dependencies\n");
-    println!("{:<40} {}", "ID", "Description");
-    println!("{:-<120}", "");
+#[derive(Subcommand)]
+enum WorkspaceCommand {
+    /// Generate every service in the manifest under projects_dir
+    Init {
+        /// Accept suggested dependencies without prompting
+        #[arg(long, short)]
+        yes: bool,
+        /// Overwrite existing service directories
+        #[arg(long)]
+        force: bool,
+    },
+    /// Build every service, reporting all failures at the end
+    Build,
+    /// Write a docker-compose.yml running every service and its backing services
+    Compose {
+        /// Overwrite an existing docker-compose.yml
+        #[arg(long)]
+        force: bool,
+    },
+}
 
-    for (id, desc) in dep_list {
-        // Wrap description text
-        let wrapped_desc = textwrap::fill(&desc, 70);
-        let mut lines = wrapped_desc.lines();
+#[derive(Args)]
+struct InitArgs {
+    /// Application name, overrides app_name
+    #[arg(long)]
+    app_name: Option<String>,
+    /// Application version, overrides app_version
+    #[arg(long)]
+    app_version: Option<String>,
+    /// Version qualifier appended to the app version, e.g. RC1, overrides version_qualifier
+    #[arg(long)]
+    version_qualifier: Option<String>,
+    /// Base package, overrides package_name
+    #[arg(long = "package")]
+    package_name: Option<String>,
+    /// groupId, overrides group_id (defaults to the package)
+    #[arg(long)]
+    group_id: Option<String>,
+    /// artifactId, overrides artifact_id (defaults to the app name)
+    #[arg(long)]
+    artifact_id: Option<String>,
+    /// Project description, overrides description
+    #[arg(long)]
+    description: Option<String>,
+    /// Project directory name under projects_dir, overrides base_dir (defaults to the app name)
+    #[arg(long)]
+    base_dir: Option<String>,
+    /// Java version, overrides java_version
+    #[arg(long = "java")]
+    java_version: Option<String>,
+    /// Spring Boot version, overrides boot_version
+    #[arg(long)]
+    boot_version: Option<String>,
+    /// Directory projects are created in, overrides projects_dir
+    #[arg(long)]
+    projects_dir: Option<String>,
+    /// Maven plugin coordinates, overrides maven_plugins
+    #[arg(long, value_delimiter = ',')]
+    plugins: Option<Vec<String>>,
+    /// Generate a multi-module Maven project, the first module is the runnable app, overrides modules
+    #[arg(long, value_delimiter = ',')]
+    modules: Option<Vec<String>>,
+    /// Add formatter and static analysis plugins, e.g. spotless,checkstyle, overrides quality
+    #[arg(long, value_enum, value_delimiter = ',')]
+    quality: Option<Vec<QualityTool>>,
+    /// What to do when a dependency doesn't support the Boot version, overrides incompatible_deps
+    #[arg(long, value_enum)]
+    incompatible_deps: Option<IncompatiblePolicy>,
+    /// Path to PRD file for automatic dependency selection
+    #[arg(long)]
+    prd: Option<String>,
+    /// Ask the AI provider again instead of reusing cached suggestions
+    #[arg(long, requires = "prd")]
+    no_cache: bool,
+    /// Prompt for project settings and dependencies
+    #[arg(long, conflicts_with = "prd")]
+    interactive: bool,
+    /// Accept AI-suggested dependencies and skip confirmation prompts
+    #[arg(long, short)]
+    yes: bool,
+    /// File with pre-selected dependency IDs, one per line
+    #[arg(long, conflicts_with = "prd")]
+    answers_file: Option<String>,
+    /// Additional dependencies to always include, `@name` adds a preset
+    #[arg(long, value_delimiter = ',', add = ArgValueCompleter::new(deps::complete_ids))]
+    include: Option<Vec<String>>,
+    /// Drop dependency IDs that aren't in the metadata instead of failing
+    #[arg(long)]
+    skip_unknown: bool,
+    /// Check that configured Maven plugins exist on Maven Central
+    #[arg(long)]
+    verify_plugins: bool,
+    /// Print the resulting build file once initialization completes, in the `pom` field with --output json
+    #[arg(long)]
+    print_pom: bool,
+    /// Relative directory under projects_dir to place the project in
+    #[arg(long)]
+    subdir: Option<String>,
+    /// Build tool for the generated project
+    #[arg(long, value_enum)]
+    build_tool: Option<BuildTool>,
+    /// Language for the generated project
+    #[arg(long, value_enum)]
+    language: Option<Language>,
+    /// Package the application as a jar or a war, overrides packaging
+    #[arg(long, value_enum)]
+    packaging: Option<Packaging>,
+    /// Enable layered jars in the spring-boot-maven-plugin configuration
+    #[arg(long)]
+    layered: bool,
+    /// Add dev and prod Maven profiles and application-{env}.yml files
+    #[arg(long)]
+    env_profiles: bool,
+    /// Add Lombok with its annotation processor and lombok.config
+    #[arg(long)]
+    lombok: bool,
+    /// Add MapStruct with its annotation processor, ordered after Lombok's
+    #[arg(long)]
+    mapstruct: bool,
+    /// Create a package skeleton with placeholder classes under the base package
+    #[arg(long, value_enum)]
+    layout: Option<Layout>,
+    /// Registered template to apply on top of the starter, see `templates`
+    #[arg(long)]
+    template: Option<String>,
+    /// Value for a template variable as NAME=VALUE, skips its prompt (repeatable)
+    #[arg(long = "var", value_parser = parse_variable, requires = "template")]
+    variables: Vec<(String, String)>,
+    /// Initialize a git repository and make an initial commit
+    #[arg(long)]
+    git: bool,
+    /// License to add to the repository, implies --git
+    #[arg(long, value_enum)]
+    license: Option<git::License>,
+    /// Create this GitHub repository (owner/name) and push to it, implies --git
+    #[arg(long)]
+    github: Option<String>,
+    /// Make the GitHub repository public instead of private
+    #[arg(long, requires = "github")]
+    public: bool,
+    /// OpenAPI spec to generate API interfaces and controllers from
+    #[arg(long)]
+    openapi: Option<String>,
+    /// Register the configured Java version in ~/.m2/toolchains.xml
+    #[arg(long)]
+    toolchains: bool,
+    /// JDK installation to register, defaults to JAVA_HOME
+    #[arg(long, requires = "toolchains")]
+    jdk_home: Option<String>,
+    /// Replace an existing ~/.m2/toolchains.xml entry for the Java version
+    #[arg(long, requires = "toolchains")]
+    replace_toolchain: bool,
+    /// Overwrite an existing project directory
+    #[arg(long)]
+    force: bool,
+    /// Extract over an existing project directory, keeping files that aren't in the scaffold
+    #[arg(long, conflicts_with = "force")]
+    merge: bool,
+    /// Use cached metadata and a starter downloaded by an earlier init with the same settings
+    #[arg(long, conflicts_with_all = ["github", "verify_plugins"])]
+    offline: bool,
+}
 
-        if let Some(first_line) = lines.next() {
-            println!("{:<40} {}", id, first_line);
-            for line in lines {
-                println!("{:<40} {}", "", line);
-            }
+impl From<InitArgs> for InitRequest {
+    fn from(args: InitArgs) -> Self {
+        InitRequest {
+            app_name: args.app_name,
+            app_version: args.app_version,
+            version_qualifier: args.version_qualifier,
+            package_name: args.package_name,
+            group_id: args.group_id,
+            artifact_id: args.artifact_id,
+            description: args.description,
+            base_dir: args.base_dir,
+            java_version: args.java_version,
+            boot_version: args.boot_version,
+            projects_dir: args.projects_dir,
+            plugins: args.plugins,
+            modules: args.modules,
+            quality: args.quality,
+            incompatible_deps: args.incompatible_deps,
+            prd: args.prd,
+            no_cache: args.no_cache,
+            interactive: args.interactive,
+            yes: args.yes,
+            answers_file: args.answers_file,
+            dependencies: None,
+            include: args.include.unwrap_or_default(),
+            skip_unknown: args.skip_unknown,
+            verify_plugins: args.verify_plugins,
+            print_pom: args.print_pom,
+            subdir: args.subdir,
+            build_tool: args.build_tool,
+            language: args.language,
+            packaging: args.packaging,
+            layered: args.layered,
+            env_profiles: args.env_profiles,
+            lombok: args.lombok,
+            mapstruct: args.mapstruct,
+            layout: args.layout,
+            template: args.template,
+            variables: args.variables.into_iter().collect(),
+            git: args.git,
+            license: args.license,
+            github: args.github,
+            public: args.public,
+            openapi: args.openapi,
+            toolchains: args.toolchains,
+            jdk_home: args.jdk_home,
+            replace_toolchain: args.replace_toolchain,
+            force: args.force,
+            merge: args.merge,
+            offline: args.offline,
         }
     }
+}
 
-    Ok(())
+fn parse_variable(value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('=')
+        .filter(|(name, _)| !name.trim().is_empty())
+        .map(|(name, value)| (name.trim().to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected NAME=VALUE, got {}", value))
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    // Answers completion requests from the script `completions` prints
+    CompleteEnv::with_factory(Cli::command).complete();
     color_eyre::install()?;
     let cli = Cli::parse();
-    let config = ProjectConfig::new()?;
-
-    match cli.command {
-        Commands::Info => show_info(&config),
-        Commands::Reset => reset(&config)?,
-        Commands::Init { prd, include } => init_project(&config, prd.as_deref(), include).await?,
-        Commands::Build => build_project(&config)?,
-        Commands::Deps => list_dependencies().await?,
-        Commands::SuggestDeps { prd } => suggest_dependencies(&prd).await?,
+    if let Commands::Config(command) = &cli.command {
+        return match command {
+            ConfigCommand::Init { global } => config::init(*global),
+            ConfigCommand::Show => config::show(cli.output),
+            ConfigCommand::Set { key, value, global } => config::set(key, value, *global),
+        };
     }
-
-    Ok(())
-}
-
-fn show_info(config: &ProjectConfig) {
-    println!("     APP NAME: {}", config.app_name);
-    println!("  APP VERSION: {}", config.app_version);
-    println!(" PACKAGE NAME: {}", config.package_name);
-    println!(" JAVA VERSION: {}", config.java_version);
-    println!(" BOOT VERSION: {}", config.boot_version);
-    println!(" PROJECTS DIR: {}", config.projects_dir);
-    println!("      APP DIR: {}", config.app_dir().display());
-    println!("     JAR PATH: {}", config.jar_path().display());
-}
-
-fn reset(config: &ProjectConfig) -> Result<()> {
-    // Remove spring.zip if it exists
-    if Path::new("spring.zip").exists() {
-        fs::remove_file("spring.zip")?;
+    if let Commands::Auth(command) = &cli.command {
+        return match command {
+            AuthCommand::Login { service } => match service.provider() {
+                Some(provider) => auth::login(provider),
+                None => git::login(),
+            },
+            AuthCommand::Logout { service } => match service.provider() {
+                Some(provider) => auth::logout(provider),
+                None => git::logout(),
+            },
+        };
     }
-
-    // Remove app directory if it exists
-    if config.app_dir().exists() {
-        fs::remove_dir_all(config.app_dir())?;
+    match &cli.command {
+        Commands::Repos(ReposCommand::Login { id }) => return repositories::login(id),
+        Commands::Repos(ReposCommand::Logout { id }) => return repositories::logout(id),
+        _ => {}
     }
-
-    println!("Project reset complete");
-    Ok(())
-}
-
-async fn init_project(
-    config: &ProjectConfig,
-    prd_path: Option<&str>,
-    include: Option<Vec<String>>,
-) -> Result<()> {
-    // Get dependencies from PRD if provided
-    let mut all_deps = if let Some(prd_path) = prd_path {
-        // Read the PRD file
-        let prd_content = fs::read_to_string(prd_path)?;
-
-        // Read the dependencies metadata
-        let deps_content = fs::read_to_string("client.json")?;
-        let deps: serde_json::Value = serde_json::from_str(&deps_content)?;
-
-        // Create a system prompt that includes the dependencies data
-        let system_prompt = format!(
-            "You are an expert in Spring Boot applications. Your task is to analyze a PRD (Product Requirements Document) \
-            and suggest the most appropriate Spring Boot dependencies from the available options. Here is the list of \
-            available dependencies with their descriptions:\n\n{}\n\nAnalyze the following PRD and respond ONLY with a \
-            comma-separated list of dependency IDs. Do not include any explanations or other text.",
-            serde_json::to_string_pretty(&deps["dependencies"]["values"])?
-        );
-
-        // Initialize Claude client
-        let claude = claude::ClaudeClient::new()?;
-
-        // Get dependency suggestions
-        claude.send_message(&system_prompt, &prd_content).await?
-    } else {
-        String::from("web")
-    };
-
-    // Add included dependencies from both config and command line
-    let prd_deps: Vec<&str> = all_deps.split(',').map(|s| s.trim()).collect();
-    let mut combined_deps: Vec<String> = prd_deps.iter().map(|&s| s.to_string()).collect();
-
-    // Add dependencies from config
-    combined_deps.extend(config.include_deps.clone());
-
-    // Add dependencies from command line
-    if let Some(included) = include {
-        combined_deps.extend(included);
+    if let Commands::Completions { shell } = cli.command {
+        return print_completions(shell);
     }
 
-    combined_deps.sort();
-    combined_deps.dedup();
-    all_deps = combined_deps.join(",");
-
-    // First reset
-    reset(config)?;
-
-    // Download Spring Boot scaffold
-    let url = format!(
-        "https://start.spring.io/starter.zip?type=maven-project&language=java&bootVersion={}&baseDir={}&groupId={}&artifactId={}&name={}&packageName={}&packaging=jar&javaVersion={}&version={}&dependencies={}",
-        config.boot_version, config.app_name, config.package_name, config.app_name, config.app_name, config.package_name, config.java_version, config.app_version, all_deps.trim()
-    );
-
-    println!("Using dependencies: {}", all_deps.trim());
-    println!("Full URL: {}", url);
-
-    println!("Downloading Spring Boot scaffold...");
-    let status = Command::new("curl")
-        .arg(url)
-        .arg("-o")
-        .arg("spring.zip")
-        .status()?;
-
-    if !status.success() {
-        return Err(color_eyre::eyre::eyre!(
-            "Failed to download Spring Boot scaffold"
-        ));
+    // Only commands that act on a project need a config file
+    let mut config = match cli.command {
+        Commands::Deps { .. }
+        | Commands::Versions { .. }
+        | Commands::Doctor
+        | Commands::Jdk(_)
+        | Commands::Templates
+        | Commands::External(_)
+        | Commands::Export { .. } => ProjectConfig::load_or_default()?,
+        _ => ProjectConfig::load()?,
+    };
+    if let Some(provider) = cli.provider {
+        if provider != config.ai.provider {
+            // The configured model belongs to a different provider
+            config.ai.model = None;
+        }
+        config.ai.provider = provider;
     }
-
-    // Unzip the scaffold
-    println!("Unzipping Spring Boot scaffold...");
-    let status = Command::new("unzip")
-        .arg("spring.zip")
-        .arg("-d")
-        .arg(&config.projects_dir)
-        .status()?;
-
-    if !status.success() {
-        return Err(color_eyre::eyre::eyre!(
-            "Failed to unzip Spring Boot scaffold"
-        ));
+    if !matches!(
+        cli.command,
+        Commands::Init(_)
+            | Commands::Import { .. }
+            | Commands::Generate(GenerateCommand::App(_))
+            | Commands::Projects(_)
+            | Commands::Workspace { .. }
+    ) {
+        projects::Registry::load(&config)?.apply_active(&mut config);
     }
 
-    // Clean up zip file
-    fs::remove_file("spring.zip")?;
-
-    // Get project version from pom.xml using Maven
-    let output = Command::new("./mvnw")
-        .current_dir(&config.app_dir())
-        .arg("help:evaluate")
-        .arg("-Dexpression=project.version")
-        .arg("-q")
-        .arg("-DforceStdout")
-        .output()?;
-
-    if !output.status.success() {
-        return Err(color_eyre::eyre::eyre!(
-            "Failed to get project version from pom.xml"
-        ));
+    match cli.command {
+        Commands::Info => project::show_info(&config, cli.output)?,
+        Commands::Reset { force } => {
+            project::check_reset(&config)?;
+            if !cli.dry_run && !force && !confirm_reset(&config)? {
+                println!("Reset cancelled");
+                return Ok(());
+            }
+            project::reset(&config, cli.dry_run)?
+        }
+        Commands::Clean => project::clean(&config, cli.dry_run)?,
+        Commands::Doctor => doctor::run(&config).await?,
+        Commands::Init(args) => {
+            let request = InitRequest::from(*args);
+            init::init_project(&config, &request, cli.quiet, cli.dry_run, cli.output).await?
+        }
+        Commands::Templates => template::list(&config)?,
+        Commands::Rename { app, package } => {
+            rename::rename(&config, app.as_deref(), package.as_deref(), cli.dry_run)?
+        }
+        Commands::External(args) => external::run(&config, &args, cli.quiet, cli.dry_run)?,
+        Commands::Import { path, name } => import::import(&config, &path, name.as_deref()).await?,
+        Commands::Upgrade {
+            boot,
+            openrewrite,
+            no_build,
+        } => upgrade::upgrade(&config, &boot, openrewrite, !no_build, cli.dry_run).await?,
+        Commands::Audit => audit::audit(&config, cli.quiet, cli.output).await?,
+        Commands::Export { format, include } => export::export(&config, format, &include)?,
+        Commands::Regenerate { lockfile, force } => {
+            let lockfile = lockfile.unwrap_or_else(|| config.app_dir().join(lock::FILE_NAME));
+            lock::regenerate(&config, &lockfile, force, cli.quiet, cli.dry_run).await?
+        }
+        Commands::Workspace { command, file } => match command {
+            WorkspaceCommand::Init { yes, force } => {
+                workspace::init(&config, &file, yes, force, cli.quiet, cli.dry_run).await?
+            }
+            WorkspaceCommand::Build => workspace::build(&config, &file)?,
+            WorkspaceCommand::Compose { force } => {
+                workspace::compose(&config, &file, force, cli.dry_run)?
+            }
+        },
+        Commands::Generate(GenerateCommand::App(args)) => {
+            let request = InitRequest::from(*args);
+            let prd = request
+                .prd
+                .clone()
+                .ok_or_else(|| color_eyre::eyre::eyre!("generate app requires --prd"))?;
+            init::init_project(&config, &request, cli.quiet, cli.dry_run, cli.output).await?;
+            request.configure(&mut config)?;
+            if cli.dry_run {
+                println!("Would generate entities, repositories, services, controllers, and application.yml");
+            } else {
+                generate::app(&config, &prd, cli.quiet).await?;
+            }
+        }
+        Commands::Generate(GenerateCommand::Entities {
+            prd,
+            schema,
+            lombok,
+        }) => {
+            generate::entities(
+                &config,
+                prd.as_deref(),
+                schema.as_deref(),
+                lombok,
+                cli.quiet,
+            )
+            .await?
+        }
+        Commands::Generate(GenerateCommand::Tests { class }) => {
+            generate::tests(&config, class.as_deref(), cli.quiet).await?
+        }
+        Commands::Build { native: true, .. } => native::build(&config)?,
+        Commands::Build {
+            keep_going,
+            env,
+            strict,
+            ..
+        } => project::build(&config, keep_going, env, strict)?,
+        Commands::Test { filter } => test::run(&config, filter.as_deref(), cli.quiet, cli.output)?,
+        Commands::Docker(command) => match command {
+            DockerCommand::Build { strategy, tag } => {
+                docker::build(&config, strategy, tag.as_deref())?
+            }
+            DockerCommand::Run { tag, port } => docker::run(&config, tag.as_deref(), port)?,
+        },
+        Commands::K8s(K8sCommand::Init {
+            ingress,
+            kustomize,
+            force,
+        }) => k8s::init(&config, ingress.as_deref(), kustomize, force, cli.dry_run)?,
+        Commands::Devcontainer { force } => devcontainer::write(&config, force, cli.dry_run)?,
+        Commands::Scaffold(ScaffoldCommand::Crud {
+            entity,
+            fields,
+            force,
+        }) => scaffold::crud(&config, &entity, &fields, force, cli.dry_run)?,
+        Commands::Ide {
+            target,
+            profile,
+            jvm_args,
+            debug_port,
+            force,
+        } => ide::write(
+            &config,
+            target,
+            profile.as_deref(),
+            &jvm_args,
+            debug_port,
+            force,
+            cli.dry_run,
+        )?,
+        Commands::Ci {
+            target,
+            publish,
+            force,
+        } => ci::write(&config, target, publish, force, cli.dry_run)?,
+        Commands::Compose { force } => compose::write(&config, force, cli.dry_run)?,
+        Commands::Run(args) => run::run(&config, &args, cli.output).await?,
+        Commands::Stop => run::stop(&config)?,
+        Commands::Status => run::status(&config)?,
+        Commands::Logs { follow, filter } => run::logs(&config, follow, filter)?,
+        Commands::Watch { profile, debounce } => {
+            watch::watch(&config, profile.as_deref(), Duration::from_millis(debounce)).await?
+        }
+        Commands::SyncPlugins => plugins::sync_project_plugins(&config)?,
+        Commands::Wrapper(WrapperCommand::Update { maven }) => {
+            wrapper::update(&config, &maven, cli.dry_run)?
+        }
+        Commands::Repos(ReposCommand::Sync) => repositories::sync(&config, cli.dry_run)?,
+        Commands::Jdk(JdkCommand::Install {
+            version,
+            distribution,
+            force,
+        }) => {
+            let version = version.unwrap_or_else(|| config.java_version.clone());
+            jdk::install(
+                &config,
+                &version,
+                &distribution,
+                force,
+                cli.quiet,
+                cli.dry_run,
+            )
+            .await?
+        }
+        Commands::AddDep { ids } => deps::add_dependencies(&config, &ids).await?,
+        Commands::RemoveDep { ids } => deps::remove_dependencies(&config, &ids).await?,
+        Commands::Deps { command, refresh } => match command.unwrap_or(DepsCommand::List) {
+            DepsCommand::List => deps::list_dependencies(&config, refresh, cli.output).await?,
+            DepsCommand::Search { query } => {
+                deps::search_dependencies(&config, &query, refresh, cli.output).await?
+            }
+            DepsCommand::Show { id } => {
+                deps::show_dependency(&config, &id, refresh, cli.output).await?
+            }
+            DepsCommand::Compare { from, to } => {
+                deps::compare_dependencies(&config, &from, &to, cli.output).await?
+            }
+            DepsCommand::Outdated => outdated::outdated(&config, cli.quiet, cli.output).await?,
+            DepsCommand::Upgrade { artifact, version } => {
+                outdated::upgrade(&config, &artifact, version.as_deref()).await?
+            }
+        },
+        Commands::Versions { refresh } => {
+            versions::list(&cache::metadata(&config, refresh).await?, cli.output)?
+        }
+        Commands::SuggestDeps { prd, no_cache } => {
+            suggest::suggest_dependencies(&config, &prd, cli.quiet, no_cache, cli.output).await?
+        }
+        Commands::Config(_)
+        | Commands::Auth(_)
+        | Commands::Repos(_)
+        | Commands::Completions { .. } => unreachable!(),
+        Commands::Projects(command) => match command {
+            ProjectsCommand::List => projects::list(&config)?,
+            ProjectsCommand::Use { name } => projects::use_project(&config, &name)?,
+            ProjectsCommand::Remove { name, delete } => projects::remove(&config, &name, delete)?,
+        },
     }
 
-    // Sync plugins from config.json to pom.xml
-    sync_plugins(config)?;
-
-    println!("Project initialization complete");
     Ok(())
 }
 
-fn build_project(config: &ProjectConfig) -> Result<()> {
-    println!("Building project...");
-    let status = Command::new("mvn")
-        .arg("package")
-        .current_dir(config.app_dir())
-        .status()?;
-
-    if !status.success() {
-        return Err(color_eyre::eyre::eyre!("Failed to build project"));
+/// Ask before `reset` deletes an existing project, refusing without a terminal
+fn confirm_reset(config: &ProjectConfig) -> Result<bool> {
+    let app_dir = config.app_dir();
+    if !app_dir.exists() {
+        return Ok(true);
     }
-
-    println!("Build complete");
-    Ok(())
-}
-
-fn sync_plugins(config: &ProjectConfig) -> Result<()> {
-    // Read existing pom.xml content
-    let pom_path = config.app_dir().join("pom.xml");
-    let pom_content = fs::read_to_string(&pom_path)?;
-
-    // For each plugin in config.json
-    for plugin in &config.maven_plugins {
-        // Check if plugin is already in pom.xml
-        if !pom_content.contains(plugin) {
-            println!("Adding plugin: {}", plugin);
-
-            // Extract group:artifact:version from plugin string
-            let parts: Vec<&str> = plugin.split(":").collect();
-            if parts.len() != 3 {
-                return Err(color_eyre::eyre::eyre!("Invalid plugin format: {}", plugin));
-            }
-
-            // Extract plugin coordinates
-            let parts: Vec<&str> = plugin.split(":").collect();
-            let (group_id, artifact_id, version) = (parts[0], parts[1], parts[2]);
-
-            // Read current pom.xml
-            let mut pom_content = fs::read_to_string(&pom_path)?;
-
-            // Check if build and plugins sections exist, if not add them
-            if !pom_content.contains("<build>") {
-                let insert_pos = pom_content.find("</project>").ok_or_else(|| {
-                    color_eyre::eyre::eyre!("Could not find </project> tag in pom.xml")
-                })?;
-                pom_content.insert_str(
-                    insert_pos,
-                    "
-    <build>
-        <plugins>
-        </plugins>
-    </build>
-",
-                );
-            } else if !pom_content.contains("<plugins>") {
-                let insert_pos = pom_content.find("</build>").ok_or_else(|| {
-                    color_eyre::eyre::eyre!("Could not find </build> tag in pom.xml")
-                })?;
-                pom_content.insert_str(
-                    insert_pos,
-                    "
-        <plugins>
-        </plugins>
-",
-                );
-            }
-
-            // Add plugin configuration
-            let plugin_xml = format!(
-                "
-            <plugin>
-                <groupId>{}</groupId>
-                <artifactId>{}</artifactId>
-                <version>{}</version>
-            </plugin>",
-                group_id, artifact_id, version
-            );
-
-            let plugins_end_pos = pom_content.find("</plugins>").ok_or_else(|| {
-                color_eyre::eyre::eyre!("Could not find </plugins> tag in pom.xml")
-            })?;
-            pom_content.insert_str(plugins_end_pos, &plugin_xml);
-
-            // Write updated pom.xml
-            fs::write(&pom_path, pom_content)?;
-        }
+    if !std::io::stdin().is_terminal() {
+        return Err(color_eyre::eyre::eyre!(
+            "Refusing to delete {} without confirmation, use --force",
+            app_dir.display()
+        ));
     }
+    wizard::confirm(
+        &format!(
+            "Delete {} ({} files)?",
+            app_dir.display(),
+            project::count_files(&app_dir)?
+        ),
+        false,
+    )
+}
 
+/// Print the script that registers completions for `shell`. Completions are
+/// computed by calling back into spring-init, so dependency IDs come from the
+/// cached metadata.
+fn print_completions(shell: Shell) -> Result<()> {
+    let shells = Shells::builtins();
+    let completer = shells
+        .completer(&shell.to_string())
+        .ok_or_else(|| color_eyre::eyre::eyre!("Completions aren't supported for {}", shell))?;
+    let name = Cli::command().get_name().to_string();
+    completer.write_registration("COMPLETE", &name, &name, &name, &mut std::io::stdout())?;
     Ok(())
 }
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Client metadata served by start.spring.io, limited to the parts
+/// spring-init uses
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializrMetadata {
+    #[serde(default)]
+    pub dependencies: DependencyCategories,
+    #[serde(default)]
+    pub boot_version: SingleSelect<BootVersion>,
+    #[serde(default)]
+    pub java_version: SingleSelect<JavaVersion>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct DependencyCategories {
+    #[serde(default)]
+    pub values: Vec<DependencyCategory>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DependencyCategory {
+    pub name: String,
+    #[serde(default)]
+    pub values: Vec<Dependency>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Dependency {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Boot versions the dependency supports, e.g. "[3.3.0,3.5.0-M1)"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_range: Option<String>,
+    /// Documentation and guides, keyed by relation such as `reference`
+    #[serde(default, rename = "_links", skip_serializing_if = "BTreeMap::is_empty")]
+    pub links: BTreeMap<String, Links>,
+}
+
+/// A relation holds either a single link or a list of them
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum Links {
+    One(Link),
+    Many(Vec<Link>),
+}
+
+impl Links {
+    pub fn iter(&self) -> impl Iterator<Item = &Link> {
+        match self {
+            Links::One(link) => std::slice::from_ref(link).iter(),
+            Links::Many(links) => links.iter(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Link {
+    pub href: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// A metadata field with a fixed set of values and a default
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SingleSelect<T> {
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(default = "Vec::new")]
+    pub values: Vec<T>,
+}
+
+impl<T> Default for SingleSelect<T> {
+    fn default() -> Self {
+        Self {
+            default: None,
+            values: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BootVersion {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct JavaVersion {
+    pub id: String,
+    pub name: String,
+}
+
+impl InitializrMetadata {
+    /// Every dependency along with the category it is listed in
+    pub fn dependencies(&self) -> impl Iterator<Item = (&DependencyCategory, &Dependency)> {
+        self.dependencies
+            .values
+            .iter()
+            .flat_map(|category| category.values.iter().map(move |dep| (category, dep)))
+    }
+
+    pub fn dependency(&self, id: &str) -> Option<(&DependencyCategory, &Dependency)> {
+        self.dependencies().find(|(_, dep)| dep.id == id)
+    }
+
+    pub fn dependency_ids(&self) -> Vec<String> {
+        self.dependencies().map(|(_, dep)| dep.id.clone()).collect()
+    }
+}
+
+/// Response of the dependencies endpoint, resolved for one Boot version
+#[derive(Deserialize)]
+pub struct ResolvedDependencies {
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, ResolvedDependency>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedDependency {
+    #[serde(default)]
+    pub group_id: String,
+    #[serde(default)]
+    pub artifact_id: String,
+    pub version: Option<String>,
+    /// Initializr scope, e.g. `compile`, `runtime`, or `annotationProcessor`
+    pub scope: Option<String>,
+    pub bom: Option<String>,
+}
@@ -0,0 +1,132 @@
+use crate::pom::{self, Pom};
+use crate::ProjectConfig;
+use color_eyre::eyre::Result;
+use std::fs;
+
+/// Elements of the scaffold pom that only belong in the aggregator
+const AGGREGATOR_ONLY: [&str; 8] = [
+    "groupId",
+    "version",
+    "url",
+    "licenses",
+    "developers",
+    "scm",
+    "properties",
+    "description",
+];
+
+/// `spring-boot:run` skips every module but the runnable one, so it can be
+/// run from the aggregator
+const RUN_SKIP: &str = "spring-boot.run.skip";
+
+fn library_pom(group_id: &str, parent: &str, version: &str, module: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+	xsi:schemaLocation="http://maven.apache.org/POM/4.0.0 https://maven.apache.org/xsd/maven-4.0.0.xsd">
+	<modelVersion>4.0.0</modelVersion>
+	<parent>
+		<groupId>{group_id}</groupId>
+		<artifactId>{parent}</artifactId>
+		<version>{version}</version>
+	</parent>
+	<artifactId>{module}</artifactId>
+	<name>{module}</name>
+	<dependencies>
+		<dependency>
+			<groupId>org.springframework.boot</groupId>
+			<artifactId>spring-boot-starter</artifactId>
+		</dependency>
+	</dependencies>
+</project>
+"#
+    )
+}
+
+/// Package of a library module, below the base package
+fn module_package(config: &ProjectConfig, module: &str) -> String {
+    format!(
+        "{}.{}",
+        config.package_name,
+        module.replace('-', "").to_lowercase()
+    )
+}
+
+/// Turn the extracted scaffold into an aggregator pom with one directory per
+/// module. The first module is the runnable application: it gets the
+/// scaffold's sources, starters, and Boot plugin, and depends on the others.
+pub fn split(config: &ProjectConfig) -> Result<()> {
+    let Some((runnable, libraries)) = config.modules.split_first() else {
+        return Ok(());
+    };
+    let app_dir = config.app_dir();
+    let root_path = app_dir.join("pom.xml");
+    let mut root = Pom::load(&root_path)?;
+    let group_id = root
+        .text(&["groupId"])?
+        .ok_or_else(|| color_eyre::eyre::eyre!("The scaffold pom.xml has no groupId"))?;
+    let artifact_id = root
+        .text(&["artifactId"])?
+        .ok_or_else(|| color_eyre::eyre::eyre!("The scaffold pom.xml has no artifactId"))?;
+    let version = root
+        .text(&["version"])?
+        .ok_or_else(|| color_eyre::eyre::eyre!("The scaffold pom.xml has no version"))?;
+
+    // The runnable module keeps the scaffold's dependencies and build, with the aggregator as parent
+    let mut module = Pom::load(&root_path)?;
+    module.set_text(&["parent", "groupId"], &group_id)?;
+    module.set_text(&["parent", "artifactId"], &artifact_id)?;
+    module.set_text(&["parent", "version"], &version)?;
+    module.remove_path(&["parent", "relativePath"])?;
+    for name in AGGREGATOR_ONLY {
+        module.remove_path(&[name])?;
+    }
+    module.set_text(&["artifactId"], runnable)?;
+    module.set_text(&["name"], runnable)?;
+    module.set_text(&["properties", RUN_SKIP], "false")?;
+    for library in libraries {
+        module.upsert_dependency(&pom::Dependency {
+            coordinates: pom::Coordinates {
+                group_id: group_id.clone(),
+                artifact_id: library.clone(),
+                version: Some("${project.version}".to_string()),
+            },
+            scope: None,
+            optional: false,
+        })?;
+    }
+    let runnable_dir = app_dir.join(runnable);
+    fs::create_dir_all(&runnable_dir)?;
+    module.save(&runnable_dir.join("pom.xml"))?;
+    fs::rename(app_dir.join("src"), runnable_dir.join("src"))?;
+
+    for library in libraries {
+        let dir = app_dir.join(library);
+        let package_dir = module_package(config, library)
+            .split('.')
+            .fold(dir.join("src").join("main").join("java"), |dir, segment| {
+                dir.join(segment)
+            });
+        fs::create_dir_all(&package_dir)?;
+        fs::write(
+            dir.join("pom.xml"),
+            library_pom(&group_id, &artifact_id, &version, library),
+        )?;
+        fs::write(
+            package_dir.join("package-info.java"),
+            format!("package {};\n", module_package(config, library)),
+        )?;
+    }
+
+    root.remove_path(&["dependencies"])?;
+    root.remove_path(&["build"])?;
+    root.set_text(&["packaging"], "pom")?;
+    root.set_text(&["properties", RUN_SKIP], "true")?;
+    for module in &config.modules {
+        root.append_text(&["modules"], "module", module)?;
+    }
+    root.save(&root_path)?;
+
+    eprintln!("Created modules: {}", config.modules.join(", "));
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use crate::{gradle, pom, wrapper, BuildTool, ProjectConfig};
+use color_eyre::eyre::Result;
+use std::fs;
+use std::process::Command;
+
+const MAVEN_PLUGIN_GROUP: &str = "org.graalvm.buildtools";
+const MAVEN_PLUGIN_ARTIFACT: &str = "native-maven-plugin";
+const GRADLE_PLUGIN_ID: &str = "org.graalvm.buildtools.native";
+/// Native Build Tools release applied to Gradle builds, Maven builds get the
+/// version managed by the Spring Boot parent
+const GRADLE_PLUGIN_VERSION: &str = "0.10.6";
+
+/// Whether the build file already has the GraalVM Native Build Tools plugin
+pub fn is_configured(build_file: &str) -> bool {
+    build_file.contains(MAVEN_PLUGIN_ARTIFACT) || build_file.contains(GRADLE_PLUGIN_ID)
+}
+
+/// Add the Native Build Tools plugin to the build file if it's missing
+fn configure(config: &ProjectConfig) -> Result<()> {
+    let build_file = config.build_file();
+    if config.build_tool.is_gradle() {
+        let content = fs::read_to_string(&build_file)?;
+        if let Some(updated) = gradle::add_plugin(
+            &content,
+            config.build_tool == BuildTool::GradleKotlin,
+            GRADLE_PLUGIN_ID,
+            GRADLE_PLUGIN_VERSION,
+        )? {
+            println!("Adding plugin: {}", GRADLE_PLUGIN_ID);
+            fs::write(&build_file, updated)?;
+        }
+    } else {
+        let mut pom = pom::Pom::load(&build_file)?;
+        // The parent's native profile configures the plugin once it's declared
+        if pom.upsert_plugin(&pom::Coordinates {
+            group_id: MAVEN_PLUGIN_GROUP.to_string(),
+            artifact_id: MAVEN_PLUGIN_ARTIFACT.to_string(),
+            version: None,
+        })? == pom::Change::Added
+        {
+            println!(
+                "Adding plugin: {}:{}",
+                MAVEN_PLUGIN_GROUP, MAVEN_PLUGIN_ARTIFACT
+            );
+            pom.save(&build_file)?;
+        }
+    }
+    Ok(())
+}
+
+/// Compile the project to a native executable with GraalVM
+pub fn build(config: &ProjectConfig) -> Result<()> {
+    if !config.build_file().exists() {
+        return Err(color_eyre::eyre::eyre!(
+            "No {} found, run init first",
+            config.build_file().display()
+        ));
+    }
+    configure(config)?;
+
+    println!("Building native executable...");
+    let program = wrapper::program(config);
+    let args = if config.build_tool.is_gradle() {
+        vec!["nativeCompile"]
+    } else {
+        vec!["-Pnative", "native:compile"]
+    };
+    let status = Command::new(&program)
+        .args(args)
+        .current_dir(config.app_dir())
+        .status()
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to run {}: {}", program, e))?;
+    if !status.success() {
+        return Err(color_eyre::eyre::eyre!(
+            "Failed to build native executable, `spring-init doctor` checks for GraalVM"
+        ));
+    }
+
+    println!("Built {}", config.native_path().display());
+    Ok(())
+}
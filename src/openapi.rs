@@ -0,0 +1,162 @@
+use crate::{pom, progress, wrapper, BuildTool, Language, ProjectConfig};
+use color_eyre::eyre::Result;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+const GENERATOR_GROUP: &str = "org.openapitools";
+const GENERATOR_ARTIFACT: &str = "openapi-generator-maven-plugin";
+const GENERATOR_VERSION: &str = "7.10.0";
+
+/// Dependencies the generated interfaces and models compile against
+pub const DEPENDENCIES: &[&str] = &["web", "validation"];
+
+/// Options for the `spring` generator. Interfaces get default methods
+/// answering 501, so controllers implementing them build before any
+/// endpoint is written.
+const CONFIG_OPTIONS: &[(&str, &str)] = &[
+    ("interfaceOnly", "true"),
+    ("useSpringBoot3", "true"),
+    ("useTags", "true"),
+    ("openApiNullable", "false"),
+    ("documentationProvider", "none"),
+    ("annotationLibrary", "none"),
+];
+
+/// Fail early for projects the generator setup doesn't support
+pub fn check(config: &ProjectConfig, spec: &Path) -> Result<()> {
+    if config.build_tool != BuildTool::Maven || config.language != Language::Java {
+        return Err(color_eyre::eyre::eyre!(
+            "--openapi is only supported for Maven projects in Java"
+        ));
+    }
+    if !spec.is_file() {
+        return Err(color_eyre::eyre::eyre!(
+            "OpenAPI spec not found: {}",
+            spec.display()
+        ));
+    }
+    Ok(())
+}
+
+fn api_package(config: &ProjectConfig) -> String {
+    format!("{}.api", config.package_name)
+}
+
+/// Copy the spec into src/main/resources and configure the generator plugin
+/// to build interfaces and models from it
+pub fn configure(config: &ProjectConfig, spec: &Path) -> Result<()> {
+    let file_name = spec
+        .file_name()
+        .ok_or_else(|| color_eyre::eyre::eyre!("Invalid OpenAPI spec path: {}", spec.display()))?
+        .to_string_lossy()
+        .into_owned();
+    let resources = config
+        .module_dir()
+        .join("src")
+        .join("main")
+        .join("resources");
+    fs::create_dir_all(&resources)?;
+    fs::copy(spec, resources.join(&file_name))?;
+    eprintln!("Copied {} to src/main/resources", file_name);
+
+    let pom_path = config.build_file();
+    let mut pom = pom::Pom::load(&pom_path)?;
+    pom.upsert_plugin(&pom::Coordinates {
+        group_id: GENERATOR_GROUP.to_string(),
+        artifact_id: GENERATOR_ARTIFACT.to_string(),
+        version: Some(GENERATOR_VERSION.to_string()),
+    })?;
+
+    let execution = ["executions", "execution"];
+    let set = |pom: &mut pom::Pom, path: &[&str], value: &str| {
+        let path: Vec<&str> = execution.iter().chain(path).copied().collect();
+        pom.set_plugin_value(GENERATOR_GROUP, GENERATOR_ARTIFACT, &path, value)
+    };
+    set(&mut pom, &["goals", "goal"], "generate")?;
+    set(
+        &mut pom,
+        &["configuration", "inputSpec"],
+        &format!("${{project.basedir}}/src/main/resources/{}", file_name),
+    )?;
+    set(&mut pom, &["configuration", "generatorName"], "spring")?;
+    set(
+        &mut pom,
+        &["configuration", "apiPackage"],
+        &api_package(config),
+    )?;
+    set(
+        &mut pom,
+        &["configuration", "modelPackage"],
+        &format!("{}.model", config.package_name),
+    )?;
+    for (name, value) in CONFIG_OPTIONS {
+        set(&mut pom, &["configuration", "configOptions", *name], value)?;
+    }
+    pom.save(&pom_path)?;
+    eprintln!("Added {} {}", GENERATOR_ARTIFACT, GENERATOR_VERSION);
+    Ok(())
+}
+
+/// Run the generator and write a controller implementing each generated API
+/// interface, leaving existing controllers alone
+pub fn write_controllers(config: &ProjectConfig, quiet: bool) -> Result<()> {
+    let spinner = progress::spinner("Generating API interfaces...", quiet)?;
+    let output = Command::new(wrapper::maven(config))
+        .current_dir(config.app_dir())
+        .args(["-q", "generate-sources"])
+        .output()?;
+    spinner.finish_and_clear();
+    if !output.status.success() {
+        eprint!("{}", String::from_utf8_lossy(&output.stdout));
+        return Err(color_eyre::eyre::eyre!(
+            "Failed to generate sources from the OpenAPI spec"
+        ));
+    }
+
+    let root = config
+        .module_dir()
+        .join("target")
+        .join("generated-sources")
+        .join("openapi")
+        .join("src")
+        .join("main")
+        .join("java");
+    let api_dir = api_package(config)
+        .split('.')
+        .fold(root, |dir, segment| dir.join(segment));
+    let web_dir = config.source_dir().join("web");
+
+    let mut interfaces: Vec<String> = fs::read_dir(&api_dir)?
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().to_string_lossy().into_owned();
+            let interface = name.strip_suffix(".java")?;
+            interface.ends_with("Api").then(|| interface.to_string())
+        })
+        .collect();
+    interfaces.sort();
+
+    for interface in interfaces {
+        let resource = interface.strip_suffix("Api").unwrap_or(&interface);
+        let controller = format!("{}Controller", resource);
+        let path = web_dir.join(format!("{}.java", controller));
+        if path.exists() {
+            continue;
+        }
+        fs::create_dir_all(&web_dir)?;
+        fs::write(
+            &path,
+            format!(
+                "package {package}.web;\n\n\
+                import {api}.{interface};\n\
+                import org.springframework.web.bind.annotation.RestController;\n\n\
+                @RestController\n\
+                public class {controller} implements {interface} {{\n}}\n",
+                package = config.package_name,
+                api = api_package(config),
+            ),
+        )?;
+        eprintln!("Wrote {}", path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,334 @@
+use crate::http::Http;
+use crate::output::{self, OutputFormat};
+use crate::pom::{self, Pom};
+use crate::{import, progress, versions, ProjectConfig};
+use color_eyre::eyre::Result;
+use std::cmp::Ordering;
+use std::path::PathBuf;
+
+const MAVEN_CENTRAL_URL: &str = "https://repo1.maven.org/maven2";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    Dependency,
+    Plugin,
+    Bom,
+    Parent,
+}
+
+impl Kind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Kind::Dependency => "dependency",
+            Kind::Plugin => "plugin",
+            Kind::Bom => "bom",
+            Kind::Parent => "parent",
+        }
+    }
+
+    /// Where the declaration lives below `<project>`, and its element name
+    fn location(self) -> (&'static [&'static str], &'static str) {
+        match self {
+            Kind::Dependency => (&["dependencies"], "dependency"),
+            Kind::Plugin => (&["build", "plugins"], "plugin"),
+            Kind::Bom => (&["dependencyManagement", "dependencies"], "dependency"),
+            Kind::Parent => (&[], "parent"),
+        }
+    }
+}
+
+/// A dependency or plugin declared with an explicit version
+pub(crate) struct Declared {
+    pub pom: PathBuf,
+    pub kind: Kind,
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: String,
+    /// Property the version is taken from, e.g. `lombok.version`
+    pub property: Option<String>,
+}
+
+impl Declared {
+    pub fn group_artifact(&self) -> String {
+        format!("{}:{}", self.group_id, self.artifact_id)
+    }
+}
+
+/// pom.xml files of the project, the aggregator first
+fn poms(config: &ProjectConfig) -> Vec<PathBuf> {
+    std::iter::once(config.app_dir().join("pom.xml"))
+        .chain(
+            config
+                .module_dirs()
+                .into_iter()
+                .map(|dir| dir.join("pom.xml")),
+        )
+        .filter(|path| path.exists())
+        .collect()
+}
+
+/// Everything the project's poms declare with an explicit version. Versions
+/// managed by Spring Boot or shared between modules are left out.
+pub(crate) fn declared(config: &ProjectConfig) -> Result<Vec<Declared>> {
+    if config.build_tool.is_gradle() {
+        return Err(color_eyre::eyre::eyre!(
+            "Checking versions is only supported for Maven projects"
+        ));
+    }
+    let paths = poms(config);
+    if paths.is_empty() {
+        return Err(color_eyre::eyre::eyre!(
+            "No pom.xml found at {}, run init first",
+            config.build_file().display()
+        ));
+    }
+    let root = Pom::load(&paths[0])?;
+
+    let mut declared: Vec<Declared> = Vec::new();
+    for path in &paths {
+        let pom = Pom::load(path)?;
+        let mut found = Vec::new();
+        for kind in [Kind::Dependency, Kind::Bom, Kind::Plugin] {
+            let (location, name) = kind.location();
+            for coordinates in pom.coordinates(location, name)? {
+                found.push((kind, coordinates));
+            }
+        }
+        if path == &paths[0] {
+            if let (Some(group_id), Some(artifact_id), version) = (
+                pom.text(&["parent", "groupId"])?,
+                pom.text(&["parent", "artifactId"])?,
+                pom.text(&["parent", "version"])?,
+            ) {
+                let parent = pom::Coordinates {
+                    group_id,
+                    artifact_id,
+                    version,
+                };
+                found.push((Kind::Parent, parent));
+            }
+        }
+
+        for (kind, coordinates) in found {
+            let Some(raw) = coordinates.version else {
+                continue;
+            };
+            if raw.contains("project.") {
+                continue;
+            }
+            let property = raw
+                .strip_prefix("${")
+                .and_then(|rest| rest.strip_suffix('}'))
+                .map(str::to_string);
+            // Modules inherit the aggregator's properties
+            let version = match import::resolve(&pom, Some(raw.clone()))? {
+                Some(version) if !version.starts_with("${") => Some(version),
+                _ => import::resolve(&root, Some(raw))?,
+            };
+            let Some(version) = version.filter(|version| !version.starts_with("${")) else {
+                continue;
+            };
+            let duplicate = declared.iter().any(|other| {
+                other.group_id == coordinates.group_id
+                    && other.artifact_id == coordinates.artifact_id
+            });
+            if !duplicate {
+                declared.push(Declared {
+                    pom: path.clone(),
+                    kind,
+                    group_id: coordinates.group_id,
+                    artifact_id: coordinates.artifact_id,
+                    version,
+                    property,
+                });
+            }
+        }
+    }
+    Ok(declared)
+}
+
+/// Milestones, release candidates, betas, and snapshots
+fn is_prerelease(version: &str) -> bool {
+    let version = version.to_lowercase();
+    [
+        "alpha",
+        "beta",
+        "rc",
+        "cr",
+        "snapshot",
+        "preview",
+        "milestone",
+        "-ea",
+    ]
+    .iter()
+    .any(|marker| version.contains(marker))
+        || version
+            .split(['.', '-'])
+            .any(|part| part.len() > 1 && part.starts_with('m') && part[1..].parse::<u32>().is_ok())
+}
+
+/// Newest release of `group:artifact` on Maven Central, skipping pre-releases
+/// unless `current` is one
+async fn latest_version(http: &Http, declared: &Declared) -> Result<Option<String>> {
+    let url = format!(
+        "{}/{}/{}/maven-metadata.xml",
+        MAVEN_CENTRAL_URL,
+        declared.group_id.replace('.', "/"),
+        declared.artifact_id
+    );
+    let response = http.send(|client| client.get(&url)).await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let metadata = response.error_for_status()?.text().await?;
+
+    let allow_prerelease = is_prerelease(&declared.version);
+    Ok(metadata
+        .split("<version>")
+        .skip(1)
+        .filter_map(|rest| {
+            rest.split_once("</version>")
+                .map(|(version, _)| version.trim())
+        })
+        .filter(|version| allow_prerelease || !is_prerelease(version))
+        .max_by(|a, b| versions::compare(a, b))
+        .map(str::to_string))
+}
+
+/// Show the declared dependencies and plugins with newer versions on Maven Central
+pub async fn outdated(config: &ProjectConfig, quiet: bool, output: OutputFormat) -> Result<()> {
+    let declared = declared(config)?;
+    let http = Http::new(config)?;
+
+    let bar = progress::items(
+        "Checking Maven Central",
+        declared.len() as u64,
+        quiet || output.is_json(),
+    )?;
+    let mut outdated = Vec::new();
+    for entry in &declared {
+        match latest_version(&http, entry).await {
+            Ok(Some(latest)) if versions::compare(&latest, &entry.version) == Ordering::Greater => {
+                outdated.push(output::OutdatedDependency {
+                    group_id: entry.group_id.clone(),
+                    artifact_id: entry.artifact_id.clone(),
+                    kind: entry.kind.as_str().to_string(),
+                    current: entry.version.clone(),
+                    latest,
+                });
+            }
+            Ok(_) => {}
+            Err(e) => bar.suspend(|| {
+                eprintln!("Warning: could not check {}: {}", entry.group_artifact(), e)
+            }),
+        }
+        bar.inc(1);
+    }
+    bar.finish_and_clear();
+
+    if output.is_json() {
+        return output::print_json(&outdated);
+    }
+    if outdated.is_empty() {
+        println!(
+            "All {} explicitly versioned dependencies and plugins are up to date",
+            declared.len()
+        );
+        return Ok(());
+    }
+
+    let width = outdated
+        .iter()
+        .map(|entry| entry.group_id.len() + entry.artifact_id.len() + 1)
+        .max()
+        .unwrap_or(0)
+        .max("ARTIFACT".len());
+    println!(
+        "{:<width$}  {:<10}  {:<14}  LATEST",
+        "ARTIFACT", "KIND", "CURRENT"
+    );
+    for entry in &outdated {
+        println!(
+            "{:<width$}  {:<10}  {:<14}  {}",
+            format!("{}:{}", entry.group_id, entry.artifact_id),
+            entry.kind,
+            entry.current,
+            entry.latest
+        );
+    }
+    println!("\nApply a bump with `spring-init deps upgrade <artifact>`");
+    Ok(())
+}
+
+/// Bump `artifact`, given as `group:artifact` or just the artifactId, to
+/// `version` or the newest release on Maven Central
+pub async fn upgrade(config: &ProjectConfig, artifact: &str, version: Option<&str>) -> Result<()> {
+    let declared = declared(config)?;
+    let entry = declared
+        .iter()
+        .find(|entry| entry.group_artifact() == artifact || entry.artifact_id == artifact)
+        .ok_or_else(|| {
+            color_eyre::eyre::eyre!(
+                "{} isn't declared with an explicit version in the project",
+                artifact
+            )
+        })?;
+    if entry.kind == Kind::Parent {
+        return Err(color_eyre::eyre::eyre!(
+            "{} is the Spring Boot parent, use `spring-init upgrade --boot <version>` to change it",
+            entry.artifact_id
+        ));
+    }
+
+    let target = match version {
+        Some(version) => version.to_string(),
+        None => latest_version(&Http::new(config)?, entry)
+            .await?
+            .ok_or_else(|| {
+                color_eyre::eyre::eyre!("{} isn't on Maven Central", entry.group_artifact())
+            })?,
+    };
+    if target == entry.version {
+        println!("{} is already at {}", entry.group_artifact(), target);
+        return Ok(());
+    }
+
+    match &entry.property {
+        // The property may be defined by the aggregator rather than the declaring pom
+        Some(property) => {
+            let path = poms(config)
+                .into_iter()
+                .find(|path| {
+                    Pom::load(path)
+                        .and_then(|pom| pom.text(&["properties", property]))
+                        .is_ok_and(|value| value.is_some())
+                })
+                .unwrap_or_else(|| entry.pom.clone());
+            let mut pom = Pom::load(&path)?;
+            pom.set_property(property, &target)?;
+            pom.save(&path)?;
+        }
+        None => {
+            let mut pom = Pom::load(&entry.pom)?;
+            if entry.kind == Kind::Plugin {
+                pom.upsert_plugin(&pom::Coordinates {
+                    group_id: entry.group_id.clone(),
+                    artifact_id: entry.artifact_id.clone(),
+                    version: Some(target.clone()),
+                })?;
+            } else {
+                let (location, _) = entry.kind.location();
+                pom.set_dependency_version(location, &entry.group_id, &entry.artifact_id, &target)?;
+            }
+            pom.save(&entry.pom)?;
+        }
+    }
+
+    println!(
+        "Updated {} from {} to {}",
+        entry.group_artifact(),
+        entry.version,
+        target
+    );
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use clap::ValueEnum;
+use color_eyre::eyre::Result;
+use serde::Serialize;
+
+use crate::ai::provider::Usage;
+use crate::ai::suggest::DependencySuggestion;
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl OutputFormat {
+    pub fn is_json(self) -> bool {
+        self == OutputFormat::Json
+    }
+}
+
+/// Print `value` as pretty JSON on stdout
+pub fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Output of `info`
+#[derive(Serialize)]
+pub struct ProjectInfo {
+    pub app_name: String,
+    pub group_id: String,
+    pub artifact_id: String,
+    pub app_version: String,
+    pub package_name: String,
+    pub java_version: String,
+    pub boot_version: String,
+    pub projects_dir: String,
+    pub app_dir: String,
+    pub source_dir: String,
+    pub jar_path: String,
+    /// Whether the build file exists, the values above come from it when it does
+    pub generated: bool,
+    /// When the jar was last built, in seconds since the Unix epoch
+    pub jar_built: Option<u64>,
+    /// Whether the jar is newer than the sources and build files
+    pub jar_up_to_date: bool,
+    pub dependencies: Vec<String>,
+    pub plugins: Vec<String>,
+    /// Settings where the config and the project on disk disagree
+    pub mismatches: Vec<Mismatch>,
+}
+
+/// Output of `run`, printed once the app is started
+#[derive(Serialize)]
+pub struct RunInfo {
+    pub app_name: String,
+    /// Port the server listens on, when it's known
+    pub port: Option<u16>,
+    /// Pid of an app running in the background
+    pub pid: Option<u32>,
+    /// Health endpoint that reported UP, with --wait-healthy
+    pub health: Option<String>,
+    /// Port the JDWP agent listens on, with --debug
+    pub debug_port: Option<u16>,
+    /// Log of an app running in the background
+    pub log: Option<String>,
+}
+
+/// A setting with different values in the config and the build file
+#[derive(Serialize)]
+pub struct Mismatch {
+    pub setting: String,
+    pub config: String,
+    pub project: String,
+}
+
+/// One entry of `deps`
+#[derive(Serialize)]
+pub struct DependencyInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+}
+
+/// Output of `deps`
+#[derive(Serialize)]
+pub struct DependencyList {
+    pub count: usize,
+    pub dependencies: Vec<DependencyInfo>,
+}
+
+/// Output of `deps --compare`
+#[derive(Serialize)]
+pub struct DependencyChanges {
+    pub from: String,
+    pub to: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub renamed: Vec<Rename>,
+}
+
+#[derive(Serialize)]
+pub struct Rename {
+    pub from: String,
+    pub to: String,
+}
+
+/// One entry of `deps outdated`
+#[derive(Serialize)]
+pub struct OutdatedDependency {
+    pub group_id: String,
+    pub artifact_id: String,
+    /// dependency, plugin, bom, or parent
+    pub kind: String,
+    pub current: String,
+    pub latest: String,
+}
+
+/// Output of `audit`
+#[derive(Serialize)]
+pub struct AuditReport {
+    pub sbom: String,
+    pub components: usize,
+    pub vulnerabilities: Vec<Vulnerability>,
+}
+
+#[derive(Serialize)]
+pub struct Vulnerability {
+    pub id: String,
+    pub aliases: Vec<String>,
+    /// CRITICAL, HIGH, MODERATE, LOW, or UNKNOWN
+    pub severity: String,
+    pub summary: String,
+    /// `group:artifact@version`
+    pub component: String,
+    /// How the project pulls the component in, outermost first
+    pub path: Vec<String>,
+}
+
+/// Output of `suggest-deps`
+#[derive(Serialize)]
+pub struct Suggestion {
+    pub model: String,
+    pub suggestions: Vec<DependencySuggestion>,
+    pub usage: Usage,
+    pub estimated_cost: Option<f64>,
+}
+
+/// Output of `init`
+#[derive(Serialize)]
+pub struct InitResult {
+    pub app_name: String,
+    pub app_dir: String,
+    pub build_file: String,
+    pub dependencies: Vec<String>,
+    pub url: String,
+    pub plugins_synced: Vec<String>,
+    /// Build file content, set with --print-pom
+    pub pom: Option<String>,
+}
+
+/// Output of `test`
+#[derive(Serialize)]
+pub struct TestSummary {
+    pub tests: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub time: f64,
+    pub failures: Vec<TestFailure>,
+}
+
+#[derive(Serialize)]
+pub struct TestFailure {
+    pub class: String,
+    pub method: String,
+    pub message: String,
+}
@@ -0,0 +1,342 @@
+use crate::http::Http;
+use crate::{pom, ProjectConfig};
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const MAVEN_CENTRAL_SEARCH_URL: &str = "https://search.maven.org/solrsearch/select";
+
+/// Entry in maven_plugins, either coordinates alone or a table adding the
+/// plugin's `<configuration>` and `<executions>`
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum MavenPlugin {
+    /// `group:artifact` or `group:artifact:version`
+    Coordinates(String),
+    Configured {
+        coordinates: String,
+        /// Inline XML for the inside of `<configuration>`, or a table
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        configuration: Option<serde_json::Value>,
+        /// Inline XML for the inside of `<executions>`, or a list of tables
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        executions: Option<serde_json::Value>,
+    },
+}
+
+impl MavenPlugin {
+    pub fn coordinates(&self) -> &str {
+        match self {
+            MavenPlugin::Coordinates(coordinates) => coordinates,
+            MavenPlugin::Configured { coordinates, .. } => coordinates,
+        }
+    }
+
+    /// The `<configuration>` and `<executions>` elements to set on the plugin
+    fn elements(&self) -> Result<Vec<pom::Element>> {
+        let MavenPlugin::Configured {
+            configuration,
+            executions,
+            ..
+        } = self
+        else {
+            return Ok(Vec::new());
+        };
+
+        let mut elements = Vec::new();
+        for (name, value) in [("configuration", configuration), ("executions", executions)] {
+            let element = match value {
+                Some(serde_json::Value::String(xml)) => pom::Element::parse_fragment(xml)?
+                    .into_iter()
+                    .fold(pom::Element::new(name), pom::Element::child),
+                Some(value) => pom::Element::from_value(name, value),
+                None => continue,
+            };
+            elements.push(element);
+        }
+        Ok(elements)
+    }
+}
+
+impl fmt::Display for MavenPlugin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.coordinates())
+    }
+}
+
+/// Configure `<layers><enabled>true</enabled></layers>` on the Spring Boot plugin
+pub fn enable_layered_jar(config: &ProjectConfig) -> Result<()> {
+    let pom_path = config.build_file();
+    let mut pom = pom::Pom::load(&pom_path)?;
+
+    if enable_layers(&mut pom)? {
+        eprintln!("Enabling layered jar");
+        pom.save(&pom_path)?;
+    }
+    Ok(())
+}
+
+/// Returns whether the pom changed
+fn enable_layers(pom: &mut pom::Pom) -> Result<bool> {
+    pom.set_plugin_value(
+        "org.springframework.boot",
+        "spring-boot-maven-plugin",
+        &["configuration", "layers", "enabled"],
+        "true",
+    )
+}
+
+/// Warning for a plugin the Maven Central search response `body` has no
+/// match for
+fn missing_from_central(plugin: &MavenPlugin, body: &serde_json::Value) -> Option<String> {
+    (body["response"]["numFound"].as_u64().unwrap_or(0) == 0)
+        .then(|| format!("plugin {} was not found on Maven Central", plugin))
+}
+
+pub async fn verify_plugins(config: &ProjectConfig) -> Result<()> {
+    eprintln!("Verifying Maven plugins against Maven Central...");
+    let http = Http::new(config)?;
+
+    for plugin in &config.maven_plugins {
+        let coordinates = pom::Coordinates::parse(plugin.coordinates())?;
+        let mut query = format!(
+            "g:\"{}\" AND a:\"{}\"",
+            coordinates.group_id, coordinates.artifact_id
+        );
+        if let Some(version) = &coordinates.version {
+            query.push_str(&format!(" AND v:\"{}\"", version));
+        }
+
+        let body = match http
+            .send(|client| {
+                client.get(MAVEN_CENTRAL_SEARCH_URL).query(&[
+                    ("q", query.as_str()),
+                    ("rows", "1"),
+                    ("wt", "json"),
+                ])
+            })
+            .await
+        {
+            Ok(response) => match response.error_for_status() {
+                Ok(response) => response.json::<serde_json::Value>().await?,
+                Err(e) => {
+                    eprintln!("Warning: could not verify plugin {}: {}", plugin, e);
+                    continue;
+                }
+            },
+            Err(e) => {
+                eprintln!("Warning: could not verify plugin {}: {}", plugin, e);
+                continue;
+            }
+        };
+
+        if let Some(warning) = missing_from_central(plugin, &body) {
+            eprintln!("Warning: {}", warning);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn sync_project_plugins(config: &ProjectConfig) -> Result<()> {
+    if config.build_tool.is_gradle() {
+        return Err(color_eyre::eyre::eyre!(
+            "Plugin sync is only supported for Maven projects"
+        ));
+    }
+
+    let pom_path = config.build_file();
+    if !pom_path.exists() {
+        return Err(color_eyre::eyre::eyre!(
+            "No pom.xml found at {}, run init first",
+            pom_path.display()
+        ));
+    }
+
+    let added = sync_plugins(config)?;
+    let dependencies = sync_dependencies(config)?;
+    if added.is_empty() && dependencies.is_empty() {
+        println!("All configured plugins and dependencies are already up to date");
+    } else {
+        println!(
+            "Synced {} plugin(s) and {} dependency or BOM entries in {}",
+            added.len(),
+            dependencies.len(),
+            pom_path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Add or update configured plugins in pom.xml, returning the ones changed
+pub fn sync_plugins(config: &ProjectConfig) -> Result<Vec<String>> {
+    let pom_path = config.build_file();
+    let mut pom = pom::Pom::load(&pom_path)?;
+    let changed = apply_plugins(&mut pom, &config.maven_plugins)?;
+    if !changed.is_empty() {
+        pom.save(&pom_path)?;
+    }
+    Ok(changed)
+}
+
+/// Add or update `plugins` in `pom`, returning the ones changed
+fn apply_plugins(pom: &mut pom::Pom, plugins: &[MavenPlugin]) -> Result<Vec<String>> {
+    let mut changed = Vec::new();
+    for plugin in plugins {
+        let coordinates = pom::Coordinates::parse(plugin.coordinates())?;
+        let mut change = pom.upsert_plugin(&coordinates)?;
+        for element in plugin.elements()? {
+            let updated =
+                pom.set_plugin_element(&coordinates.group_id, &coordinates.artifact_id, &element)?;
+            if updated && change == pom::Change::Unchanged {
+                change = pom::Change::Updated;
+            }
+        }
+        match change {
+            pom::Change::Added => eprintln!("Adding plugin: {}", plugin),
+            pom::Change::Updated => eprintln!("Updating plugin: {}", plugin),
+            pom::Change::Unchanged => continue,
+        }
+        changed.push(plugin.to_string());
+    }
+    Ok(changed)
+}
+
+/// Add or update configured BOM imports and dependencies in pom.xml,
+/// returning the ones changed
+pub fn sync_dependencies(config: &ProjectConfig) -> Result<Vec<String>> {
+    let pom_path = config.build_file();
+    let mut pom = pom::Pom::load(&pom_path)?;
+    let mut changed = Vec::new();
+
+    for bom in &config.maven_boms {
+        let coordinates = pom::Coordinates::parse(bom)?;
+        if coordinates.version.is_none() {
+            return Err(color_eyre::eyre::eyre!("BOM {} needs a version", bom));
+        }
+        match pom.upsert_bom(&coordinates)? {
+            pom::Change::Added => eprintln!("Importing BOM: {}", bom),
+            pom::Change::Updated => eprintln!("Updating BOM: {}", bom),
+            pom::Change::Unchanged => continue,
+        }
+        changed.push(bom.clone());
+    }
+
+    for dependency in &config.maven_dependencies {
+        match pom.upsert_dependency(&pom::Dependency::parse(dependency)?)? {
+            pom::Change::Added => eprintln!("Adding dependency: {}", dependency),
+            pom::Change::Updated => eprintln!("Updating dependency: {}", dependency),
+            pom::Change::Unchanged => continue,
+        }
+        changed.push(dependency.clone());
+    }
+
+    if !changed.is_empty() {
+        pom.save(&pom_path)?;
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_plugin_missing_from_central() {
+        let plugin = MavenPlugin::Coordinates("com.bogus:no-such-plugin:9.9.9".to_string());
+        let not_found = serde_json::json!({
+            "responseHeader": {"status": 0},
+            "response": {"numFound": 0, "start": 0, "docs": []}
+        });
+        let warning = missing_from_central(&plugin, &not_found).unwrap();
+        assert!(warning.contains("com.bogus:no-such-plugin:9.9.9"));
+
+        let found = serde_json::json!({
+            "response": {"numFound": 1, "start": 0, "docs": [{"id": "com.bogus:no-such-plugin:9.9.9"}]}
+        });
+        assert!(missing_from_central(&plugin, &found).is_none());
+    }
+
+    const POM: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project>
+    <modelVersion>4.0.0</modelVersion>
+    <groupId>com.example</groupId>
+    <artifactId>demo</artifactId>
+    <build>
+        <plugins>
+            <plugin>
+                <groupId>org.springframework.boot</groupId>
+                <artifactId>spring-boot-maven-plugin</artifactId>
+            </plugin>
+        </plugins>
+    </build>
+</project>
+"#;
+
+    #[test]
+    fn synced_plugin_is_in_the_pom() {
+        let mut pom = pom::Pom::parse(POM.to_string()).unwrap();
+        let plugins = [MavenPlugin::Coordinates(
+            "org.jacoco:jacoco-maven-plugin:0.8.12".to_string(),
+        )];
+
+        let changed = apply_plugins(&mut pom, &plugins).unwrap();
+
+        assert_eq!(changed, ["org.jacoco:jacoco-maven-plugin:0.8.12"]);
+        let content = pom.content();
+        assert!(content.contains("<artifactId>jacoco-maven-plugin</artifactId>"));
+        assert!(content.contains("<version>0.8.12</version>"));
+        assert!(content.contains("<artifactId>spring-boot-maven-plugin</artifactId>"));
+    }
+
+    #[test]
+    fn sync_adds_only_the_missing_plugin() {
+        let mut pom = pom::Pom::parse(POM.to_string()).unwrap();
+        let jacoco = MavenPlugin::Coordinates("org.jacoco:jacoco-maven-plugin:0.8.12".to_string());
+        apply_plugins(&mut pom, std::slice::from_ref(&jacoco)).unwrap();
+
+        let plugins = [
+            jacoco,
+            MavenPlugin::Coordinates(
+                "com.diffplug.spotless:spotless-maven-plugin:2.43.0".to_string(),
+            ),
+        ];
+        let changed = apply_plugins(&mut pom, &plugins).unwrap();
+
+        assert_eq!(
+            changed,
+            ["com.diffplug.spotless:spotless-maven-plugin:2.43.0"]
+        );
+        let content = pom.content();
+        assert_eq!(
+            content
+                .matches("<artifactId>jacoco-maven-plugin</artifactId>")
+                .count(),
+            1
+        );
+        assert_eq!(
+            content
+                .matches("<artifactId>spotless-maven-plugin</artifactId>")
+                .count(),
+            1
+        );
+        assert!(apply_plugins(&mut pom, &plugins).unwrap().is_empty());
+    }
+
+    #[test]
+    fn layered_jar_configures_the_boot_plugin() {
+        let classic = pom::Pom::parse(POM.to_string()).unwrap();
+        assert!(!classic.content().contains("<layers>"));
+
+        let mut layered = pom::Pom::parse(POM.to_string()).unwrap();
+        assert!(enable_layers(&mut layered).unwrap());
+        let content = layered.content().to_string();
+        let plugin = &content[content.find("spring-boot-maven-plugin").unwrap()..];
+        let layers = &plugin[plugin.find("<layers>").unwrap()..plugin.find("</layers>").unwrap()];
+        assert!(layers.contains("<enabled>true</enabled>"));
+        assert!(plugin.find("</configuration>").unwrap() > plugin.find("</layers>").unwrap());
+
+        assert!(!enable_layers(&mut layered).unwrap());
+        assert_eq!(layered.content().matches("<layers>").count(), 1);
+    }
+}
@@ -0,0 +1,788 @@
+use color_eyre::eyre::Result;
+use std::fs;
+use std::path::Path;
+
+/// Maven's implied groupId for plugins that don't declare one
+const DEFAULT_PLUGIN_GROUP: &str = "org.apache.maven.plugins";
+
+/// Position of an element as the indexes of its element ancestors, starting
+/// below the root `<project>`. Appending children never shifts an existing
+/// address, so addresses stay valid across edits that only add elements.
+type Address = Vec<usize>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coordinates {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: Option<String>,
+}
+
+impl Coordinates {
+    /// Parse `group:artifact` or `group:artifact:version`
+    pub fn parse(coordinates: &str) -> Result<Self> {
+        let parts: Vec<&str> = coordinates.split(':').collect();
+        match parts.as_slice() {
+            [group_id, artifact_id] => Ok(Self {
+                group_id: group_id.to_string(),
+                artifact_id: artifact_id.to_string(),
+                version: None,
+            }),
+            [group_id, artifact_id, version] => Ok(Self {
+                group_id: group_id.to_string(),
+                artifact_id: artifact_id.to_string(),
+                version: Some(version.to_string()),
+            }),
+            _ => Err(color_eyre::eyre::eyre!(
+                "Invalid Maven coordinates: {}",
+                coordinates
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    pub coordinates: Coordinates,
+    pub scope: Option<String>,
+    pub optional: bool,
+}
+
+impl Dependency {
+    /// Parse `group:artifact[:version]` with an optional `@scope` suffix,
+    /// e.g. `org.projectlombok:lombok@provided`
+    pub fn parse(spec: &str) -> Result<Self> {
+        let (coordinates, scope) = match spec.split_once('@') {
+            Some((coordinates, scope)) => (coordinates, Some(scope.to_string())),
+            None => (spec, None),
+        };
+        Ok(Self {
+            coordinates: Coordinates::parse(coordinates)?,
+            scope,
+            optional: false,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Added,
+    Updated,
+    Unchanged,
+}
+
+/// Element to be rendered into the pom
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Element {
+    name: String,
+    text: Option<String>,
+    children: Vec<Element>,
+}
+
+impl Element {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            text: None,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    pub fn child(mut self, child: Element) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Parse an XML fragment, e.g. the inside of a `<configuration>`, into
+    /// the elements it lists
+    pub fn parse_fragment(xml: &str) -> Result<Vec<Element>> {
+        let wrapped = format!("<fragment>{}</fragment>", xml);
+        let doc = roxmltree::Document::parse(&wrapped)
+            .map_err(|e| color_eyre::eyre::eyre!("Invalid XML {:?}: {}", xml.trim(), e))?;
+        Ok(element_children(doc.root_element())
+            .map(Element::from_node)
+            .collect())
+    }
+
+    /// Build an element from a config value. Tables become child elements
+    /// and lists repeat the singular of `name`, so `goals = ["check"]`
+    /// renders `<goals><goal>check</goal></goals>`
+    pub fn from_value(name: &str, value: &serde_json::Value) -> Element {
+        match value {
+            serde_json::Value::Object(map) => map
+                .iter()
+                .fold(Element::new(name), |element, (key, value)| {
+                    element.child(Element::from_value(key, value))
+                }),
+            serde_json::Value::Array(items) => {
+                let item = singular(name);
+                items.iter().fold(Element::new(name), |element, value| {
+                    element.child(Element::from_value(&item, value))
+                })
+            }
+            serde_json::Value::String(text) => Element::new(name).text(text),
+            serde_json::Value::Null => Element::new(name),
+            other => Element::new(name).text(&other.to_string()),
+        }
+    }
+
+    fn from_node(node: roxmltree::Node) -> Element {
+        let children: Vec<Element> = element_children(node).map(Element::from_node).collect();
+        Element {
+            name: node.tag_name().name().to_string(),
+            text: node
+                .text()
+                .map(str::trim)
+                .filter(|text| children.is_empty() && !text.is_empty())
+                .map(String::from),
+            children,
+        }
+    }
+
+    pub fn render(&self, indent: &str, unit: &str) -> String {
+        if self.children.is_empty() {
+            return format!(
+                "{}<{}>{}</{}>",
+                indent,
+                self.name,
+                escape(self.text.as_deref().unwrap_or_default()),
+                self.name
+            );
+        }
+
+        let child_indent = format!("{}{}", indent, unit);
+        let children: Vec<String> = self
+            .children
+            .iter()
+            .map(|child| child.render(&child_indent, unit))
+            .collect();
+        format!(
+            "{}<{}>\n{}\n{}</{}>",
+            indent,
+            self.name,
+            children.join("\n"),
+            indent,
+            self.name
+        )
+    }
+}
+
+/// Name of the elements in a Maven list, e.g. `goal` in `<goals>`
+fn singular(name: &str) -> String {
+    match name.strip_suffix("ies") {
+        Some(stem) => format!("{}y", stem),
+        None => name.strip_suffix('s').unwrap_or(name).to_string(),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn coordinates_element(name: &str, coordinates: &Coordinates) -> Element {
+    let mut element = Element::new(name)
+        .child(Element::new("groupId").text(&coordinates.group_id))
+        .child(Element::new("artifactId").text(&coordinates.artifact_id));
+    if let Some(version) = &coordinates.version {
+        element = element.child(Element::new("version").text(version));
+    }
+    element
+}
+
+/// A pom.xml edited in place, so formatting and comments outside the
+/// touched elements are preserved
+pub struct Pom {
+    content: String,
+}
+
+impl Pom {
+    pub fn parse(content: String) -> Result<Self> {
+        roxmltree::Document::parse(&content)
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to parse pom.xml: {}", e))?;
+        Ok(Self { content })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::parse(fs::read_to_string(path)?)
+    }
+
+    /// The pom.xml as it would be saved
+    #[cfg(test)]
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, &self.content)?;
+        Ok(())
+    }
+
+    fn document(&self) -> Result<roxmltree::Document<'_>> {
+        roxmltree::Document::parse(&self.content)
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to parse pom.xml: {}", e))
+    }
+
+    /// Trimmed text of the element at `path` below `<project>`
+    pub fn text(&self, path: &[&str]) -> Result<Option<String>> {
+        let Some(address) = self.find_path(&[], path)? else {
+            return Ok(None);
+        };
+        let doc = self.document()?;
+        Ok(node_at(&doc, &address)?
+            .text()
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(str::to_string))
+    }
+
+    /// Coordinates of each `name` element in the element at `path`, e.g. the
+    /// dependencies in `<dependencies>`
+    pub fn coordinates(&self, path: &[&str], name: &str) -> Result<Vec<Coordinates>> {
+        let Some(address) = self.find_path(&[], path)? else {
+            return Ok(Vec::new());
+        };
+        let doc = self.document()?;
+        Ok(element_children(node_at(&doc, &address)?)
+            .filter(|child| child.tag_name().name() == name)
+            .filter_map(|child| {
+                let default_group = (name == "plugin").then_some(DEFAULT_PLUGIN_GROUP);
+                Some(Coordinates {
+                    group_id: child_text(child, "groupId").or(default_group)?.to_string(),
+                    artifact_id: child_text(child, "artifactId")?.to_string(),
+                    version: child_text(child, "version").map(str::to_string),
+                })
+            })
+            .collect())
+    }
+
+    /// Add a plugin to `<build><plugins>`, or update the version of an
+    /// existing declaration
+    pub fn upsert_plugin(&mut self, plugin: &Coordinates) -> Result<Change> {
+        let plugins = self.ensure_path(&["build", "plugins"])?;
+        self.upsert_coordinates(&plugins, "plugin", plugin, |_, _| Ok(()))
+    }
+
+    /// Add a dependency to `<dependencies>`, or update the version and scope
+    /// of an existing declaration
+    pub fn upsert_dependency(&mut self, dependency: &Dependency) -> Result<Change> {
+        let dependencies = self.ensure_path(&["dependencies"])?;
+        self.upsert_coordinates(
+            &dependencies,
+            "dependency",
+            &dependency.coordinates,
+            |pom, address| {
+                if let Some(scope) = &dependency.scope {
+                    pom.set_child_text(address, "scope", scope)?;
+                }
+                if dependency.optional {
+                    pom.set_child_text(address, "optional", "true")?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    /// Import a BOM in `<dependencyManagement>`, or update the version of an
+    /// existing import
+    pub fn upsert_bom(&mut self, bom: &Coordinates) -> Result<Change> {
+        let dependencies = self.ensure_path(&["dependencyManagement", "dependencies"])?;
+        self.upsert_coordinates(&dependencies, "dependency", bom, |pom, address| {
+            pom.set_child_text(address, "type", "pom")?;
+            pom.set_child_text(address, "scope", "import")?;
+            Ok(())
+        })
+    }
+
+    /// Add a `<profile>` setting `properties` unless one with the same id
+    /// exists, returning whether it was added
+    pub fn add_profile(
+        &mut self,
+        id: &str,
+        active_by_default: bool,
+        properties: &[(&str, &str)],
+    ) -> Result<bool> {
+        if self.has_profile(id)? {
+            return Ok(false);
+        }
+        let mut profile = Element::new("profile").child(Element::new("id").text(id));
+        if active_by_default {
+            profile = profile.child(
+                Element::new("activation").child(Element::new("activeByDefault").text("true")),
+            );
+        }
+        let properties = properties
+            .iter()
+            .fold(Element::new("properties"), |element, (name, value)| {
+                element.child(Element::new(name).text(value))
+            });
+        let profiles = self.ensure_path(&["profiles"])?;
+        self.append_child(&profiles, &profile.child(properties))?;
+        Ok(true)
+    }
+
+    /// Whether `<profiles>` declares a profile with this id
+    pub fn has_profile(&self, id: &str) -> Result<bool> {
+        let doc = self.document()?;
+        let found = element_children(doc.root_element())
+            .filter(|child| child.tag_name().name() == "profiles")
+            .flat_map(element_children)
+            .any(|profile| child_text(profile, "id") == Some(id));
+        Ok(found)
+    }
+
+    /// Add `element` to the `list` element, e.g. a `<repository>` to
+    /// `<repositories>`, replacing the entry with the same `<id>`
+    pub fn upsert_by_id(&mut self, list: &str, element: &Element) -> Result<Change> {
+        let id = element
+            .children
+            .iter()
+            .find(|child| child.name == "id")
+            .and_then(|child| child.text.clone())
+            .unwrap_or_default();
+        let parent = self.ensure_path(&[list])?;
+        let doc = self.document()?;
+        let existing = element_children(node_at(&doc, &parent)?)
+            .enumerate()
+            .find(|(_, child)| child_text(*child, "id") == Some(id.as_str()))
+            .map(|(index, child)| (index, Element::from_node(child)));
+        drop(doc);
+
+        match existing {
+            Some((_, current)) if current == *element => Ok(Change::Unchanged),
+            Some((index, _)) => {
+                let mut address = parent.clone();
+                address.push(index);
+                self.remove(&address)?;
+                self.append_child(&parent, element)?;
+                Ok(Change::Updated)
+            }
+            None => {
+                self.append_child(&parent, element)?;
+                Ok(Change::Added)
+            }
+        }
+    }
+
+    /// Remove a dependency from `<dependencies>`, returning whether it was present
+    pub fn remove_dependency(&mut self, group_id: &str, artifact_id: &str) -> Result<bool> {
+        let Some(dependencies) = self.find_path(&[], &["dependencies"])? else {
+            return Ok(false);
+        };
+        match self.find_coordinates(&dependencies, "dependency", group_id, artifact_id)? {
+            Some(address) => {
+                self.remove(&address)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Set the text of the element at `path` below `<project>`, creating it
+    /// and its parents if needed, returning whether the pom changed
+    pub fn set_text(&mut self, path: &[&str], value: &str) -> Result<bool> {
+        let (leaf, parents) = path
+            .split_last()
+            .ok_or_else(|| color_eyre::eyre::eyre!("Empty pom path"))?;
+        let parent = self.ensure_path(parents)?;
+        self.set_child_text(&parent, leaf, value)
+    }
+
+    /// Append a `name` element with `text` to the element at `path`, e.g. a
+    /// `<module>` to `<modules>`
+    pub fn append_text(&mut self, path: &[&str], name: &str, text: &str) -> Result<()> {
+        let parent = self.ensure_path(path)?;
+        self.append_child(&parent, &Element::new(name).text(text))?;
+        Ok(())
+    }
+
+    /// Remove the element at `path` below `<project>`, returning whether it was present
+    pub fn remove_path(&mut self, path: &[&str]) -> Result<bool> {
+        match self.find_path(&[], path)? {
+            Some(address) => {
+                self.remove(&address)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Set a value in `<properties>`, returning whether the pom changed
+    pub fn set_property(&mut self, name: &str, value: &str) -> Result<bool> {
+        let properties = self.ensure_path(&["properties"])?;
+        self.set_child_text(&properties, name, value)
+    }
+
+    /// Set the version of a dependency declared in the element at `path`,
+    /// e.g. a BOM in `["dependencyManagement", "dependencies"]`, returning
+    /// whether the pom changed
+    pub fn set_dependency_version(
+        &mut self,
+        path: &[&str],
+        group_id: &str,
+        artifact_id: &str,
+        version: &str,
+    ) -> Result<bool> {
+        let dependency = self
+            .find_path(&[], path)?
+            .map(|dependencies| {
+                self.find_coordinates(&dependencies, "dependency", group_id, artifact_id)
+            })
+            .transpose()?
+            .flatten()
+            .ok_or_else(|| {
+                color_eyre::eyre::eyre!("Could not find dependency {} in pom.xml", artifact_id)
+            })?;
+        self.set_child_text(&dependency, "version", version)
+    }
+
+    /// Set a nested value inside a declared build plugin, e.g.
+    /// `["configuration", "layers", "enabled"]`, returning whether the pom changed
+    pub fn set_plugin_value(
+        &mut self,
+        group_id: &str,
+        artifact_id: &str,
+        path: &[&str],
+        value: &str,
+    ) -> Result<bool> {
+        let plugin = self.find_plugin(group_id, artifact_id)?;
+        let (leaf, parents) = path
+            .split_last()
+            .ok_or_else(|| color_eyre::eyre::eyre!("Empty plugin setting path"))?;
+        let mut address = plugin;
+        for name in parents {
+            address = self.ensure_child(&address, name)?;
+        }
+        self.set_child_text(&address, leaf, value)
+    }
+
+    /// Replace the child of a declared build plugin named like `element`,
+    /// e.g. its `<configuration>`, returning whether the pom changed
+    pub fn set_plugin_element(
+        &mut self,
+        group_id: &str,
+        artifact_id: &str,
+        element: &Element,
+    ) -> Result<bool> {
+        self.set_plugin_child(group_id, artifact_id, &[], element)
+    }
+
+    /// Like `set_plugin_element`, for an element nested under `parents`,
+    /// e.g. the `<annotationProcessorPaths>` of the `<configuration>`, leaving
+    /// its siblings alone
+    pub fn set_plugin_child(
+        &mut self,
+        group_id: &str,
+        artifact_id: &str,
+        parents: &[&str],
+        element: &Element,
+    ) -> Result<bool> {
+        let mut parent = self.find_plugin(group_id, artifact_id)?;
+        for name in parents {
+            parent = self.ensure_child(&parent, name)?;
+        }
+        if let Some(address) = self.find_path(&parent, &[&element.name])? {
+            let doc = self.document()?;
+            if Element::from_node(node_at(&doc, &address)?) == *element {
+                return Ok(false);
+            }
+            drop(doc);
+            self.remove(&address)?;
+        }
+        self.append_child(&parent, element)?;
+        Ok(true)
+    }
+
+    fn find_plugin(&self, group_id: &str, artifact_id: &str) -> Result<Address> {
+        self.find_path(&[], &["build", "plugins"])?
+            .map(|plugins| self.find_coordinates(&plugins, "plugin", group_id, artifact_id))
+            .transpose()?
+            .flatten()
+            .ok_or_else(|| {
+                color_eyre::eyre::eyre!("Could not find plugin {} in pom.xml", artifact_id)
+            })
+    }
+
+    fn upsert_coordinates(
+        &mut self,
+        parent: &[usize],
+        name: &str,
+        coordinates: &Coordinates,
+        update: impl Fn(&mut Self, &[usize]) -> Result<()>,
+    ) -> Result<Change> {
+        let existing = self.find_coordinates(
+            parent,
+            name,
+            &coordinates.group_id,
+            &coordinates.artifact_id,
+        )?;
+
+        match existing {
+            Some(address) => {
+                let before = self.content.clone();
+                if let Some(version) = &coordinates.version {
+                    self.set_child_text(&address, "version", version)?;
+                }
+                update(self, &address)?;
+                Ok(if before == self.content {
+                    Change::Unchanged
+                } else {
+                    Change::Updated
+                })
+            }
+            None => {
+                let address = self.append_child(parent, &coordinates_element(name, coordinates))?;
+                update(self, &address)?;
+                Ok(Change::Added)
+            }
+        }
+    }
+
+    fn find_coordinates(
+        &self,
+        parent: &[usize],
+        name: &str,
+        group_id: &str,
+        artifact_id: &str,
+    ) -> Result<Option<Address>> {
+        let doc = self.document()?;
+        let parent_node = node_at(&doc, parent)?;
+
+        for (index, child) in element_children(parent_node).enumerate() {
+            if child.tag_name().name() != name {
+                continue;
+            }
+            let child_group = child_text(child, "groupId").unwrap_or(if name == "plugin" {
+                DEFAULT_PLUGIN_GROUP
+            } else {
+                ""
+            });
+            if child_group == group_id && child_text(child, "artifactId") == Some(artifact_id) {
+                let mut address = parent.to_vec();
+                address.push(index);
+                return Ok(Some(address));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Find a descendant of `parent` by following element names
+    fn find_path(&self, parent: &[usize], path: &[&str]) -> Result<Option<Address>> {
+        let doc = self.document()?;
+        let mut address = parent.to_vec();
+        let mut node = node_at(&doc, parent)?;
+
+        for name in path {
+            let found = element_children(node)
+                .enumerate()
+                .find(|(_, child)| child.tag_name().name() == *name);
+            match found {
+                Some((index, child)) => {
+                    address.push(index);
+                    node = child;
+                }
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(address))
+    }
+
+    /// Find or create the element at `path` below `<project>`
+    fn ensure_path(&mut self, path: &[&str]) -> Result<Address> {
+        let mut address = Vec::new();
+        for name in path {
+            address = self.ensure_child(&address, name)?;
+        }
+        Ok(address)
+    }
+
+    fn ensure_child(&mut self, parent: &[usize], name: &str) -> Result<Address> {
+        match self.find_path(parent, &[name])? {
+            Some(address) => Ok(address),
+            None => self.append_child(parent, &Element::new(name)),
+        }
+    }
+
+    /// Set the text of the `name` child of `parent`, creating it if needed
+    fn set_child_text(&mut self, parent: &[usize], name: &str, value: &str) -> Result<bool> {
+        let Some(address) = self.find_path(parent, &[name])? else {
+            self.append_child(parent, &Element::new(name).text(value))?;
+            return Ok(true);
+        };
+
+        let doc = self.document()?;
+        let node = node_at(&doc, &address)?;
+        if node.text().map(str::trim) == Some(value) && element_children(node).next().is_none() {
+            return Ok(false);
+        }
+
+        let (open_end, close_start) = inner_range(&self.content, node);
+        let range = node.range();
+        drop(doc);
+
+        match close_start {
+            Some(close_start) => self
+                .content
+                .replace_range(open_end..close_start, &escape(value)),
+            // Self-closing element
+            None => self
+                .content
+                .replace_range(range, &format!("<{}>{}</{}>", name, escape(value), name)),
+        }
+        Ok(true)
+    }
+
+    /// Append `element` as the last child of `parent`, returning its address
+    fn append_child(&mut self, parent: &[usize], element: &Element) -> Result<Address> {
+        let doc = self.document()?;
+        let parent_node = node_at(&doc, parent)?;
+        let child_count = element_children(parent_node).count();
+        let unit = indent_unit(&self.content, &doc);
+        let parent_indent = line_indent(&self.content, parent_node.range().start).to_string();
+        let (_, close_start) = inner_range(&self.content, parent_node);
+        let parent_range = parent_node.range();
+        let parent_name = parent_node.tag_name().name().to_string();
+        drop(doc);
+
+        let child_indent = format!("{}{}", parent_indent, unit);
+        let rendered = element.render(&child_indent, &unit);
+
+        match close_start {
+            Some(close_start) => {
+                let line_start = self.content[..close_start].rfind('\n').map(|i| i + 1);
+                match line_start {
+                    // Closing tag on its own line, insert above it
+                    Some(line_start) if self.content[line_start..close_start].trim().is_empty() => {
+                        self.content
+                            .insert_str(line_start, &format!("{}\n", rendered));
+                    }
+                    _ => self
+                        .content
+                        .insert_str(close_start, &format!("\n{}\n{}", rendered, parent_indent)),
+                }
+            }
+            None => {
+                // Expand the self-closing parent around the new child
+                let tag = &self.content[parent_range.clone()];
+                let open = format!("{}>", tag.trim_end_matches("/>").trim_end());
+                self.content.replace_range(
+                    parent_range,
+                    &format!(
+                        "{}\n{}\n{}</{}>",
+                        open, rendered, parent_indent, parent_name
+                    ),
+                );
+            }
+        }
+
+        let mut address = parent.to_vec();
+        address.push(child_count);
+        Ok(address)
+    }
+
+    /// Remove an element along with the whitespace of its line, a comment
+    /// trailing it on the line, and a blank line it leaves doubled
+    fn remove(&mut self, address: &[usize]) -> Result<()> {
+        let doc = self.document()?;
+        let range = node_at(&doc, address)?.range();
+        drop(doc);
+
+        let mut start = range.start;
+        let line_start = self.content[..start]
+            .rfind('\n')
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        if self.content[line_start..start].trim().is_empty() {
+            start = line_start;
+        }
+        let mut end = range.end;
+        let rest = self.content[end..].trim_start_matches([' ', '\t']);
+        if rest.starts_with("<!--") {
+            if let Some(close) = rest.find("-->") {
+                end = self.content.len() - rest.len() + close + 3;
+            }
+        }
+        if self.content[end..].starts_with('\n') {
+            end += 1;
+        }
+        let blank_before = self.content[..start].ends_with("\n\n");
+        let blank_after = self.content[end..]
+            .trim_start_matches([' ', '\t'])
+            .starts_with('\n');
+        if start == line_start && blank_before && blank_after {
+            end += self.content[end..].find('\n').map(|i| i + 1).unwrap_or(0);
+        }
+
+        self.content.replace_range(start..end, "");
+        Ok(())
+    }
+}
+
+fn element_children<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+) -> impl Iterator<Item = roxmltree::Node<'a, 'input>> {
+    node.children().filter(|child| child.is_element())
+}
+
+fn child_text<'a>(node: roxmltree::Node<'a, '_>, name: &str) -> Option<&'a str> {
+    element_children(node)
+        .find(|child| child.tag_name().name() == name)
+        .and_then(|child| child.text())
+        .map(str::trim)
+}
+
+fn node_at<'a, 'input>(
+    doc: &'a roxmltree::Document<'input>,
+    address: &[usize],
+) -> Result<roxmltree::Node<'a, 'input>> {
+    let mut node = doc.root_element();
+    for index in address {
+        node = element_children(node)
+            .nth(*index)
+            .ok_or_else(|| color_eyre::eyre::eyre!("pom.xml changed while editing"))?;
+    }
+    Ok(node)
+}
+
+/// End of the start tag and start of the end tag, the latter being `None`
+/// for self-closing elements
+fn inner_range(content: &str, node: roxmltree::Node) -> (usize, Option<usize>) {
+    let range = node.range();
+    let element = &content[range.clone()];
+    if element.ends_with("/>") {
+        return (range.end, None);
+    }
+
+    let close_start = range.start + element.rfind("</").unwrap_or(element.len());
+    let open_end = node
+        .first_child()
+        .map(|child| child.range().start)
+        .unwrap_or(close_start);
+    (open_end, Some(close_start))
+}
+
+/// Whitespace between the start of the line and `pos`
+fn line_indent(content: &str, pos: usize) -> &str {
+    let line_start = content[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let indent = &content[line_start..pos];
+    if indent.trim().is_empty() {
+        indent
+    } else {
+        ""
+    }
+}
+
+/// Indentation used by the document, taken from the first child of `<project>`
+fn indent_unit(content: &str, doc: &roxmltree::Document) -> String {
+    element_children(doc.root_element())
+        .next()
+        .map(|child| line_indent(content, child.range().start))
+        .filter(|indent| !indent.is_empty())
+        .unwrap_or("    ")
+        .to_string()
+}
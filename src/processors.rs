@@ -0,0 +1,133 @@
+use crate::project::Language;
+use crate::{pom, ProjectConfig};
+use color_eyre::eyre::Result;
+use std::fs;
+
+const COMPILER_PLUGIN: (&str, &str) = ("org.apache.maven.plugins", "maven-compiler-plugin");
+const BOOT_PLUGIN: (&str, &str) = ("org.springframework.boot", "spring-boot-maven-plugin");
+const LOMBOK: (&str, &str) = ("org.projectlombok", "lombok");
+const MAPSTRUCT_VERSION: &str = "1.6.3";
+const LOMBOK_MAPSTRUCT_BINDING_VERSION: &str = "0.2.0";
+/// Written to the project root, marks generated code so coverage tools skip it
+const LOMBOK_CONFIG: &str =
+    "config.stopBubbling = true\nlombok.addLombokGeneratedAnnotation = true\n";
+
+fn coordinates(group_id: &str, artifact_id: &str, version: Option<&str>) -> pom::Coordinates {
+    pom::Coordinates {
+        group_id: group_id.to_string(),
+        artifact_id: artifact_id.to_string(),
+        version: version.map(String::from),
+    }
+}
+
+fn path(coordinates: &pom::Coordinates) -> pom::Element {
+    pom::Element::new("path")
+        .child(pom::Element::new("groupId").text(&coordinates.group_id))
+        .child(pom::Element::new("artifactId").text(&coordinates.artifact_id))
+        .child(
+            pom::Element::new("version").text(coordinates.version.as_deref().unwrap_or_default()),
+        )
+}
+
+/// Processor paths in the order javac must run them: Lombok first, so
+/// MapStruct sees the getters and setters it generates, with the binding
+/// between them
+fn processor_paths(config: &ProjectConfig) -> pom::Element {
+    let mut paths = Vec::new();
+    if config.lombok {
+        // The Boot parent manages lombok.version
+        paths.push(coordinates(LOMBOK.0, LOMBOK.1, Some("${lombok.version}")));
+    }
+    if config.lombok && config.mapstruct {
+        paths.push(coordinates(
+            LOMBOK.0,
+            "lombok-mapstruct-binding",
+            Some(LOMBOK_MAPSTRUCT_BINDING_VERSION),
+        ));
+    }
+    if config.mapstruct {
+        paths.push(coordinates(
+            "org.mapstruct",
+            "mapstruct-processor",
+            Some("${mapstruct.version}"),
+        ));
+    }
+    paths.iter().fold(
+        pom::Element::new("annotationProcessorPaths"),
+        |list, coordinates| list.child(path(coordinates)),
+    )
+}
+
+/// Add Lombok and MapStruct with their annotation processors wired into
+/// maven-compiler-plugin, keep Lombok out of the executable jar, and write
+/// lombok.config
+pub fn configure(config: &ProjectConfig) -> Result<()> {
+    if !config.lombok && !config.mapstruct {
+        return Ok(());
+    }
+    if config.language != Language::Java {
+        eprintln!(
+            "Skipping lombok and mapstruct for {} project",
+            config.language.as_str()
+        );
+        return Ok(());
+    }
+
+    let pom_path = config.build_file();
+    let mut pom = pom::Pom::load(&pom_path)?;
+    let mut changed = false;
+    if config.lombok {
+        let lombok = pom::Dependency {
+            coordinates: coordinates(LOMBOK.0, LOMBOK.1, None),
+            scope: None,
+            optional: true,
+        };
+        changed |= pom.upsert_dependency(&lombok)? != pom::Change::Unchanged;
+        // Lombok is only needed at compile time
+        let excludes = pom::Element::new("excludes").child(
+            pom::Element::new("exclude")
+                .child(pom::Element::new("groupId").text(LOMBOK.0))
+                .child(pom::Element::new("artifactId").text(LOMBOK.1)),
+        );
+        changed |=
+            pom.set_plugin_child(BOOT_PLUGIN.0, BOOT_PLUGIN.1, &["configuration"], &excludes)?;
+    }
+    if config.mapstruct {
+        changed |= pom.set_property("mapstruct.version", MAPSTRUCT_VERSION)?;
+        let mapstruct = pom::Dependency::parse("org.mapstruct:mapstruct:${mapstruct.version}")?;
+        changed |= pom.upsert_dependency(&mapstruct)? != pom::Change::Unchanged;
+    }
+
+    // The Boot parent manages the compiler plugin's version
+    let compiler = coordinates(COMPILER_PLUGIN.0, COMPILER_PLUGIN.1, None);
+    changed |= pom.upsert_plugin(&compiler)? != pom::Change::Unchanged;
+    changed |= pom.set_plugin_child(
+        COMPILER_PLUGIN.0,
+        COMPILER_PLUGIN.1,
+        &["configuration"],
+        &processor_paths(config),
+    )?;
+    if config.mapstruct {
+        // Generated mappers become Spring beans that can be injected
+        let args = pom::Element::new("compilerArgs")
+            .child(pom::Element::new("arg").text("-Amapstruct.defaultComponentModel=spring"));
+        changed |= pom.set_plugin_child(
+            COMPILER_PLUGIN.0,
+            COMPILER_PLUGIN.1,
+            &["configuration"],
+            &args,
+        )?;
+    }
+    if changed {
+        eprintln!("Configuring annotation processors");
+        pom.save(&pom_path)?;
+    }
+
+    if config.lombok {
+        let path = config.app_dir().join("lombok.config");
+        if !path.exists() {
+            fs::write(&path, LOMBOK_CONFIG)?;
+        }
+    }
+    Ok(())
+}
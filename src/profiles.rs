@@ -0,0 +1,62 @@
+use crate::{application_yml, pom, ProjectConfig};
+use clap::ValueEnum;
+use color_eyre::eyre::Result;
+
+/// Property the Maven profiles set, filtered into application.yml
+const ACTIVE_PROFILES: &str = "spring.profiles.active";
+
+/// Environment with a Maven profile of the same name and an
+/// application-{env}.yml, see `env_profiles`
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Environment {
+    Dev,
+    Prod,
+}
+
+impl Environment {
+    pub const ALL: [Environment; 2] = [Environment::Dev, Environment::Prod];
+
+    /// Maven profile and Spring profile name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Environment::Dev => "dev",
+            Environment::Prod => "prod",
+        }
+    }
+}
+
+/// Add dev and prod profiles to pom.xml, dev being active by default, and
+/// write the matching application-{env}.yml files
+pub fn configure(config: &ProjectConfig) -> Result<()> {
+    let pom_path = config.build_file();
+    let mut pom = pom::Pom::load(&pom_path)?;
+    let mut added = false;
+    for environment in Environment::ALL {
+        added |= pom.add_profile(
+            environment.as_str(),
+            environment == Environment::Dev,
+            &[(ACTIVE_PROFILES, environment.as_str())],
+        )?;
+    }
+    if added {
+        eprintln!("Adding dev and prod profiles");
+        pom.save(&pom_path)?;
+    }
+    application_yml::write_environments(config)
+}
+
+/// Fail unless the project declares the environment's Maven profile
+pub fn check(config: &ProjectConfig, environment: Environment) -> Result<()> {
+    if config.build_tool.is_gradle() {
+        return Err(color_eyre::eyre::eyre!(
+            "--env is only supported for Maven projects"
+        ));
+    }
+    if !pom::Pom::load(&config.build_file())?.has_profile(environment.as_str())? {
+        return Err(color_eyre::eyre::eyre!(
+            "No {} profile in pom.xml, generate the project with --env-profiles",
+            environment.as_str()
+        ));
+    }
+    Ok(())
+}
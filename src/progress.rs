@@ -0,0 +1,43 @@
+use color_eyre::eyre::Result;
+use indicatif::{ProgressBar, ProgressStyle};
+use std::time::Duration;
+
+/// Byte progress for a download, a spinner when the length is unknown
+pub fn download(message: &str, len: Option<u64>, quiet: bool) -> Result<ProgressBar> {
+    if quiet {
+        return Ok(ProgressBar::hidden());
+    }
+    let bar = match len {
+        Some(len) => ProgressBar::new(len).with_style(ProgressStyle::with_template(
+            "{msg} [{bar:40}] {bytes}/{total_bytes} ({bytes_per_sec})",
+        )?),
+        None => ProgressBar::new_spinner()
+            .with_style(ProgressStyle::with_template("{spinner} {msg} {bytes}")?),
+    };
+    bar.set_message(message.to_string());
+    Ok(bar)
+}
+
+/// Progress over a known number of items, e.g. files in an archive
+pub fn items(message: &str, len: u64, quiet: bool) -> Result<ProgressBar> {
+    if quiet {
+        return Ok(ProgressBar::hidden());
+    }
+    let bar = ProgressBar::new(len).with_style(ProgressStyle::with_template(
+        "{msg} [{bar:40}] {pos}/{len}",
+    )?);
+    bar.set_message(message.to_string());
+    Ok(bar)
+}
+
+/// Spinner for a step with no measurable progress
+pub fn spinner(message: &str, quiet: bool) -> Result<ProgressBar> {
+    if quiet {
+        return Ok(ProgressBar::hidden());
+    }
+    let bar =
+        ProgressBar::new_spinner().with_style(ProgressStyle::with_template("{spinner} {msg}")?);
+    bar.set_message(message.to_string());
+    bar.enable_steady_tick(Duration::from_millis(100));
+    Ok(bar)
+}
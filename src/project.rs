@@ -0,0 +1,1011 @@
+use crate::ai::provider::{AiConfig, Usage};
+use crate::hooks::{self, Hook, HooksConfig};
+use crate::http::{HttpConfig, NetworkConfig};
+use crate::import::{self, BuildSettings};
+use crate::layout::Layout;
+use crate::output::{self, OutputFormat};
+use crate::plugins::MavenPlugin;
+use crate::profiles::{self, Environment};
+use crate::quality::QualityTool;
+use crate::repositories::{self, MavenMirror, MavenRepository};
+use crate::{ai, config, deps, git, initializr, jdk, run, wrapper};
+use clap::ValueEnum;
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum BuildTool {
+    #[default]
+    Maven,
+    Gradle,
+    GradleKotlin,
+}
+
+impl BuildTool {
+    /// Project type understood by start.spring.io
+    pub fn project_type(&self) -> &'static str {
+        match self {
+            BuildTool::Maven => "maven-project",
+            BuildTool::Gradle => "gradle-project",
+            BuildTool::GradleKotlin => "gradle-project-kotlin",
+        }
+    }
+
+    pub fn is_gradle(&self) -> bool {
+        matches!(self, BuildTool::Gradle | BuildTool::GradleKotlin)
+    }
+}
+
+/// Archive the application is packaged as
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Packaging {
+    /// Executable jar with an embedded server
+    #[default]
+    Jar,
+    /// War that also runs with `java -jar` or deploys to a servlet container
+    War,
+}
+
+impl Packaging {
+    /// Name used by start.spring.io and as the archive extension
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Packaging::Jar => "jar",
+            Packaging::War => "war",
+        }
+    }
+}
+
+/// What init does when a dependency doesn't support the chosen Boot version
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum IncompatiblePolicy {
+    /// Refuse to generate the project
+    #[default]
+    Fail,
+    /// Generate the project anyway
+    Warn,
+    /// Switch to the newest Boot version all dependencies support
+    Substitute,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Language {
+    #[default]
+    Java,
+    Kotlin,
+    Groovy,
+}
+
+impl Language {
+    /// Name used by start.spring.io and as the source directory under src/main
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Language::Java => "java",
+            Language::Kotlin => "kotlin",
+            Language::Groovy => "groovy",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProjectConfig {
+    pub boot_version: String,
+    pub java_version: String,
+    pub app_name: String,
+    pub app_version: String,
+    /// Appended to app_version as `{app_version}-{qualifier}`, e.g. `RC1`
+    #[serde(default)]
+    pub version_qualifier: Option<String>,
+    pub package_name: String,
+    pub projects_dir: String,
+    pub maven_plugins: Vec<MavenPlugin>,
+    pub include_deps: Vec<String>,
+    /// Maven repositories generated projects resolve from, e.g. an internal
+    /// Nexus, see `repos sync`
+    #[serde(default)]
+    pub maven_repositories: Vec<MavenRepository>,
+    /// Mirrors written to the project's .mvn/settings.xml
+    #[serde(default)]
+    pub maven_mirrors: Vec<MavenMirror>,
+    /// Declare maven_repositories in pom.xml instead of .mvn/settings.xml
+    #[serde(default)]
+    pub repositories_in_pom: bool,
+    /// Maven dependencies added to every generated pom, as
+    /// `group:artifact[:version][@scope]`
+    #[serde(default)]
+    pub maven_dependencies: Vec<String>,
+    /// BOMs imported in every generated pom's `<dependencyManagement>`, as
+    /// `group:artifact:version`
+    #[serde(default)]
+    pub maven_boms: Vec<String>,
+    /// Named dependency bundles, used as `@name` wherever dependency IDs are
+    /// accepted
+    #[serde(default)]
+    pub presets: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    pub build_tool: BuildTool,
+    #[serde(default)]
+    pub language: Language,
+    #[serde(default)]
+    pub packaging: Packaging,
+    /// Custom system prompt for dependency suggestion, `{dependencies}` is
+    /// replaced with the available dependency metadata
+    #[serde(default)]
+    pub prompt_template: Option<String>,
+    /// groupId of generated projects, defaults to package_name
+    #[serde(default)]
+    pub group_id: Option<String>,
+    /// artifactId of generated projects, defaults to app_name
+    #[serde(default)]
+    pub artifact_id: Option<String>,
+    /// Project description in the build file, Initializr's when unset
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Directory the project is extracted into, defaults to app_name
+    #[serde(default)]
+    pub base_dir: Option<String>,
+    /// Relative directory under projects_dir the project is nested in
+    #[serde(default)]
+    pub subdir: Option<String>,
+    /// Directory of a project living outside projects_dir, set for imported
+    /// projects
+    #[serde(default)]
+    pub project_dir: Option<String>,
+    /// Maven modules of a multi-module project, the first is the runnable
+    /// application and depends on the others
+    #[serde(default)]
+    pub modules: Vec<String>,
+    /// Enable layered jars in the spring-boot-maven-plugin configuration
+    #[serde(default)]
+    pub layered_jar: bool,
+    /// Add dev and prod Maven profiles and application-{env}.yml files, see
+    /// `build --env`
+    #[serde(default)]
+    pub env_profiles: bool,
+    /// Formatter and static analysis plugins added to generated Maven
+    /// projects, see `build --strict`
+    #[serde(default)]
+    pub quality: Vec<QualityTool>,
+    /// Add Lombok with its annotation processor
+    #[serde(default)]
+    pub lombok: bool,
+    /// Add MapStruct with its annotation processor, after Lombok's
+    #[serde(default)]
+    pub mapstruct: bool,
+    /// Package skeleton created under the base package
+    #[serde(default)]
+    pub layout: Option<Layout>,
+    /// Template directories by name, see `init --template`
+    #[serde(default)]
+    pub templates: BTreeMap<String, String>,
+    /// Base URL of the Spring Initializr instance, e.g. a company one with
+    /// internal starters
+    #[serde(default = "default_initializr_url")]
+    pub initializr_url: String,
+    /// Headers sent to the Initializr, e.g. `Authorization`, `${VAR}` is
+    /// replaced with the environment variable VAR
+    #[serde(default)]
+    pub initializr_headers: BTreeMap<String, String>,
+    /// AI provider and model used for dependency suggestions
+    #[serde(default)]
+    pub ai: AiConfig,
+    /// Per-model token prices used to estimate the cost of suggestion calls
+    #[serde(default)]
+    pub token_prices: HashMap<String, TokenPrice>,
+    /// How long cached start.spring.io metadata is considered fresh
+    #[serde(default = "default_metadata_cache_ttl_hours")]
+    pub metadata_cache_ttl_hours: u64,
+    /// What to do when a dependency doesn't support boot_version
+    #[serde(default)]
+    pub incompatible_deps: IncompatiblePolicy,
+    /// Container image name and tag, defaults to app_name:version
+    #[serde(default)]
+    pub image: Option<String>,
+    /// Repository setup after init
+    #[serde(default)]
+    pub git: git::GitConfig,
+    /// Commands run after init and around builds
+    #[serde(default)]
+    pub hooks: HooksConfig,
+    /// Timeouts and retries for network requests
+    #[serde(default)]
+    pub http: HttpConfig,
+    /// Proxy and extra root certificates for network requests
+    #[serde(default)]
+    pub network: NetworkConfig,
+}
+
+fn default_initializr_url() -> String {
+    initializr::DEFAULT_URL.to_string()
+}
+
+fn default_metadata_cache_ttl_hours() -> u64 {
+    24
+}
+
+/// Price in USD per million tokens
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct TokenPrice {
+    pub input: f64,
+    pub output: f64,
+}
+
+impl TokenPrice {
+    pub fn cost(&self, usage: &Usage) -> f64 {
+        (usage.input_tokens as f64 * self.input + usage.output_tokens as f64 * self.output)
+            / 1_000_000.0
+    }
+}
+
+/// Built-in prices for models the tool uses out of the box
+pub fn default_token_price(model: &str) -> Option<TokenPrice> {
+    match model {
+        ai::claude::DEFAULT_MODEL => Some(TokenPrice {
+            input: 3.0,
+            output: 15.0,
+        }),
+        ai::openai::DEFAULT_MODEL => Some(TokenPrice {
+            input: 2.5,
+            output: 10.0,
+        }),
+        _ => None,
+    }
+}
+
+impl Default for ProjectConfig {
+    fn default() -> Self {
+        Self {
+            boot_version: "3.4.3".to_string(),
+            java_version: "21".to_string(),
+            app_name: "demo".to_string(),
+            app_version: "0.0.1-SNAPSHOT".to_string(),
+            version_qualifier: None,
+            package_name: "com.example.demo".to_string(),
+            projects_dir: "./projects".to_string(),
+            maven_plugins: Vec::new(),
+            include_deps: Vec::new(),
+            maven_repositories: Vec::new(),
+            maven_mirrors: Vec::new(),
+            repositories_in_pom: false,
+            maven_dependencies: Vec::new(),
+            maven_boms: Vec::new(),
+            presets: BTreeMap::new(),
+            build_tool: BuildTool::default(),
+            language: Language::default(),
+            packaging: Packaging::default(),
+            prompt_template: None,
+            group_id: None,
+            artifact_id: None,
+            description: None,
+            base_dir: None,
+            subdir: None,
+            project_dir: None,
+            modules: Vec::new(),
+            layered_jar: false,
+            env_profiles: false,
+            quality: Vec::new(),
+            lombok: false,
+            mapstruct: false,
+            layout: None,
+            templates: BTreeMap::new(),
+            initializr_url: default_initializr_url(),
+            initializr_headers: BTreeMap::new(),
+            ai: AiConfig::default(),
+            token_prices: HashMap::new(),
+            metadata_cache_ttl_hours: default_metadata_cache_ttl_hours(),
+            incompatible_deps: IncompatiblePolicy::default(),
+            image: None,
+            git: git::GitConfig::default(),
+            hooks: HooksConfig::default(),
+            http: HttpConfig::default(),
+            network: NetworkConfig::default(),
+        }
+    }
+}
+
+impl ProjectConfig {
+    /// Load the global config overlaid with the project-local one
+    pub fn load() -> Result<Self> {
+        let value = config::load()?.ok_or_else(|| {
+            color_eyre::eyre::eyre!(
+                "No config file found, run `spring-init config init` to create one"
+            )
+        })?;
+        Self::from_value(value)
+    }
+
+    /// Like `load`, but falls back to the defaults when no config file exists
+    pub fn load_or_default() -> Result<Self> {
+        match config::load()? {
+            Some(value) => Self::from_value(value),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn from_value(mut value: serde_json::Value) -> Result<Self> {
+        // metadata_fetch_retries predates the http settings
+        if let Some(retries) = value.get("metadata_fetch_retries").cloned() {
+            if value["http"]["retries"].is_null() {
+                value["http"]["retries"] = retries;
+            }
+        }
+        let config: ProjectConfig = serde_json::from_value(value)
+            .map_err(|e| color_eyre::eyre::eyre!("Invalid config: {}", e))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if let Some(base_dir) = &self.base_dir {
+            let mut components = Path::new(base_dir).components();
+            let single = matches!(components.next(), Some(Component::Normal(_)))
+                && components.next().is_none();
+            if !single {
+                return Err(color_eyre::eyre::eyre!(
+                    "base_dir must be a single directory name: {}",
+                    base_dir
+                ));
+            }
+        }
+        if let Some(subdir) = &self.subdir {
+            let escapes = Path::new(subdir).components().any(|c| {
+                matches!(
+                    c,
+                    Component::ParentDir | Component::RootDir | Component::Prefix(_)
+                )
+            });
+            if escapes {
+                return Err(color_eyre::eyre::eyre!(
+                    "subdir must be a relative path inside projects_dir: {}",
+                    subdir
+                ));
+            }
+        }
+        if !self.modules.is_empty()
+            && (self.build_tool != BuildTool::Maven || self.language != Language::Java)
+        {
+            return Err(color_eyre::eyre::eyre!(
+                "modules are only supported for Java projects built with Maven"
+            ));
+        }
+        for (i, module) in self.modules.iter().enumerate() {
+            let valid = !module.is_empty()
+                && module
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if !valid {
+                return Err(color_eyre::eyre::eyre!(
+                    "Invalid module name {:?}, use letters, digits, '-', and '_'",
+                    module
+                ));
+            }
+            if self.modules[..i].contains(module) || module == &self.app_name {
+                return Err(color_eyre::eyre::eyre!(
+                    "Module {} is listed twice or named after the app",
+                    module
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Directory the scaffold is extracted into
+    pub fn scaffold_dir(&self) -> PathBuf {
+        let projects_dir = PathBuf::from(&self.projects_dir);
+        match &self.subdir {
+            // Written with / in the config, split so Windows paths get its separator
+            Some(subdir) => subdir
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .fold(projects_dir, |dir, segment| dir.join(segment)),
+            None => projects_dir,
+        }
+    }
+
+    pub fn app_dir(&self) -> PathBuf {
+        match &self.project_dir {
+            Some(dir) => PathBuf::from(dir),
+            None => self.scaffold_dir().join(self.base_dir()),
+        }
+    }
+
+    pub fn group_id(&self) -> &str {
+        self.group_id.as_deref().unwrap_or(&self.package_name)
+    }
+
+    /// Version of the built artifact, app_version with version_qualifier
+    pub fn version(&self) -> String {
+        match &self.version_qualifier {
+            Some(qualifier) => format!("{}-{}", self.app_version, qualifier),
+            None => self.app_version.clone(),
+        }
+    }
+
+    pub fn artifact_id(&self) -> &str {
+        self.artifact_id.as_deref().unwrap_or(&self.app_name)
+    }
+
+    /// Name of the project directory under projects_dir and subdir
+    pub fn base_dir(&self) -> &str {
+        self.base_dir.as_deref().unwrap_or(&self.app_name)
+    }
+
+    /// Directory of the runnable application, the project directory unless
+    /// the project has modules
+    pub fn module_dir(&self) -> PathBuf {
+        match self.modules.first() {
+            Some(module) => self.app_dir().join(module),
+            None => self.app_dir(),
+        }
+    }
+
+    /// Directories with their own sources and build output, one per module
+    pub fn module_dirs(&self) -> Vec<PathBuf> {
+        if self.modules.is_empty() {
+            return vec![self.app_dir()];
+        }
+        self.modules
+            .iter()
+            .map(|module| self.app_dir().join(module))
+            .collect()
+    }
+
+    /// Artifact name of the runnable application
+    fn artifact_name(&self) -> &str {
+        self.modules
+            .first()
+            .map(String::as_str)
+            .unwrap_or(self.artifact_id())
+    }
+
+    /// Executable archive produced by `build`, a jar or war depending on
+    /// packaging
+    pub fn jar_path(&self) -> PathBuf {
+        let output_dir = if self.build_tool.is_gradle() {
+            self.module_dir().join("build").join("libs")
+        } else {
+            self.module_dir().join("target")
+        };
+        output_dir.join(format!(
+            "{}-{}.{}",
+            self.artifact_name(),
+            self.version(),
+            self.packaging.as_str()
+        ))
+    }
+
+    /// Executable produced by `build --native`
+    pub fn native_path(&self) -> PathBuf {
+        let output_dir = if self.build_tool.is_gradle() {
+            self.module_dir()
+                .join("build")
+                .join("native")
+                .join("nativeCompile")
+        } else {
+            self.module_dir().join("target")
+        };
+        output_dir.join(format!(
+            "{}{}",
+            self.artifact_name(),
+            env::consts::EXE_SUFFIX
+        ))
+    }
+
+    /// Directory holding the main application sources for the base package
+    pub fn source_dir(&self) -> PathBuf {
+        let mut dir = self
+            .module_dir()
+            .join("src")
+            .join("main")
+            .join(self.language.as_str());
+        for segment in self.package_name.split('.') {
+            dir.push(segment);
+        }
+        dir
+    }
+
+    pub fn image(&self) -> String {
+        self.image
+            .clone()
+            .unwrap_or_else(|| format!("{}:{}", self.app_name, self.version()))
+    }
+
+    /// Build file of the runnable application, where its dependencies and
+    /// plugins are declared
+    pub fn build_file(&self) -> PathBuf {
+        self.module_dir().join(match self.build_tool {
+            BuildTool::Maven => "pom.xml",
+            BuildTool::Gradle => "build.gradle",
+            BuildTool::GradleKotlin => "build.gradle.kts",
+        })
+    }
+}
+
+/// A generated project, the entry point for driving it from other tools
+pub struct Project {
+    config: ProjectConfig,
+}
+
+impl Project {
+    pub fn new(config: ProjectConfig) -> Result<Self> {
+        config.validate()?;
+        Ok(Self { config })
+    }
+
+    /// The project described by the global and project-local config files
+    pub fn load() -> Result<Self> {
+        Self::new(ProjectConfig::load()?)
+    }
+
+    pub fn config(&self) -> &ProjectConfig {
+        &self.config
+    }
+
+    pub fn dir(&self) -> PathBuf {
+        self.config.app_dir()
+    }
+
+    /// Whether the project has been generated
+    pub fn exists(&self) -> bool {
+        self.config.build_file().exists()
+    }
+
+    pub fn build(&self) -> Result<()> {
+        build(&self.config, false, None, false)
+    }
+
+    pub async fn add_dependencies(&self, ids: &[String]) -> Result<()> {
+        deps::add_dependencies(&self.config, ids).await
+    }
+
+    pub async fn remove_dependencies(&self, ids: &[String]) -> Result<()> {
+        deps::remove_dependencies(&self.config, ids).await
+    }
+
+    /// Delete the build output
+    pub fn clean(&self) -> Result<()> {
+        clean(&self.config, false)
+    }
+
+    /// Delete the project directory
+    pub fn reset(&self) -> Result<()> {
+        reset(&self.config, false)
+    }
+}
+
+/// Settings where the build file disagrees with the config
+fn mismatches(config: &ProjectConfig, build: &BuildSettings) -> Vec<output::Mismatch> {
+    [
+        (
+            "artifact_id",
+            &config.artifact_id().to_string(),
+            Some(&build.artifact_id),
+        ),
+        (
+            "group_id",
+            &config.group_id().to_string(),
+            build.group_id.as_ref(),
+        ),
+        ("app_version", &config.version(), build.version.as_ref()),
+        (
+            "java_version",
+            &config.java_version,
+            build.java_version.as_ref(),
+        ),
+        (
+            "boot_version",
+            &config.boot_version,
+            build.boot_version.as_ref(),
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(setting, configured, actual)| {
+        let actual = actual?;
+        (actual != configured).then(|| output::Mismatch {
+            setting: setting.to_string(),
+            config: configured.clone(),
+            project: actual.clone(),
+        })
+    })
+    .collect()
+}
+
+/// How long ago `time` was, e.g. "5 minutes ago"
+fn format_age(time: SystemTime) -> String {
+    let secs = SystemTime::now()
+        .duration_since(time)
+        .unwrap_or_default()
+        .as_secs();
+    let (count, unit) = match secs {
+        0..60 => return "just now".to_string(),
+        60..3_600 => (secs / 60, "minute"),
+        3_600..86_400 => (secs / 3_600, "hour"),
+        _ => (secs / 86_400, "day"),
+    };
+    format!(
+        "{} {}{} ago",
+        count,
+        unit,
+        if count == 1 { "" } else { "s" }
+    )
+}
+
+/// Artifact IDs of `group:artifact` entries, or the entries themselves
+fn short_names(coordinates: &[String]) -> String {
+    if coordinates.is_empty() {
+        return "none".to_string();
+    }
+    coordinates
+        .iter()
+        .map(|entry| entry.rsplit(':').next().unwrap_or(entry))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Print the project as it is on disk, falling back to the config for a
+/// project that hasn't been generated, and flag where the two disagree
+pub fn show_info(config: &ProjectConfig, output: OutputFormat) -> Result<()> {
+    let generated = config.build_file().exists();
+    let build = if generated {
+        import::read_build_settings(config.build_tool, &config.app_dir())
+            .map_err(|e| eprintln!("Warning: showing configured values, {}", e))
+            .ok()
+    } else {
+        None
+    };
+    let mismatches = build
+        .as_ref()
+        .map(|build| mismatches(config, build))
+        .unwrap_or_default();
+    let actual = |setting: &str, configured: &String| {
+        mismatches
+            .iter()
+            .find(|mismatch| mismatch.setting == setting)
+            .map(|mismatch| mismatch.project.clone())
+            .unwrap_or_else(|| configured.clone())
+    };
+    let jar_path = config.jar_path();
+    let jar_built = fs::metadata(&jar_path)
+        .and_then(|meta| meta.modified())
+        .ok();
+    let jar_up_to_date = run::is_current(config, &jar_path);
+    let read = build.is_some();
+    // A multi-module project declares its dependencies in the runnable module
+    let build = match build {
+        Some(_) if !config.modules.is_empty() => {
+            import::read_build_settings(config.build_tool, &config.module_dir()).ok()
+        }
+        build => build,
+    };
+    let (dependencies, plugins) = build
+        .map(|build| (build.dependencies, build.plugins))
+        .unwrap_or_default();
+
+    if output.is_json() {
+        return output::print_json(&output::ProjectInfo {
+            app_name: config.app_name.clone(),
+            group_id: actual("group_id", &config.group_id().to_string()),
+            artifact_id: actual("artifact_id", &config.artifact_id().to_string()),
+            app_version: actual("app_version", &config.version()),
+            package_name: config.package_name.clone(),
+            java_version: actual("java_version", &config.java_version),
+            boot_version: actual("boot_version", &config.boot_version),
+            projects_dir: config.projects_dir.clone(),
+            app_dir: config.app_dir().display().to_string(),
+            source_dir: config.source_dir().display().to_string(),
+            jar_path: jar_path.display().to_string(),
+            generated,
+            jar_built: jar_built
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|since| since.as_secs()),
+            jar_up_to_date,
+            dependencies,
+            plugins,
+            mismatches,
+        });
+    }
+
+    let show = |label: &str, setting: &str, configured: &String| {
+        let value = actual(setting, configured);
+        if &value == configured {
+            println!("{:>13}: {}", label, value);
+        } else {
+            println!("{:>13}: {} (config: {})", label, value, configured);
+        }
+    };
+    println!("     APP NAME: {}", config.app_name);
+    show("GROUP ID", "group_id", &config.group_id().to_string());
+    show(
+        "ARTIFACT ID",
+        "artifact_id",
+        &config.artifact_id().to_string(),
+    );
+    show("APP VERSION", "app_version", &config.version());
+    println!(" PACKAGE NAME: {}", config.package_name);
+    show("JAVA VERSION", "java_version", &config.java_version);
+    show("BOOT VERSION", "boot_version", &config.boot_version);
+    println!(" PROJECTS DIR: {}", config.projects_dir);
+    println!("      APP DIR: {}", config.app_dir().display());
+    println!("   SOURCE DIR: {}", config.source_dir().display());
+    match jar_built {
+        Some(built) => println!(
+            "     JAR PATH: {} (built {}{})",
+            jar_path.display(),
+            format_age(built),
+            if jar_up_to_date {
+                ""
+            } else {
+                ", older than the sources"
+            }
+        ),
+        None => println!("     JAR PATH: {} (not built)", jar_path.display()),
+    }
+
+    if !generated {
+        println!(
+            "\nNo project at {}, showing configured values, run init to generate it",
+            config.app_dir().display()
+        );
+        return Ok(());
+    }
+    if read {
+        println!(" DEPENDENCIES: {}", short_names(&dependencies));
+        println!("      PLUGINS: {}", short_names(&plugins));
+    }
+    if !mismatches.is_empty() {
+        println!(
+            "\nWarning: the project on disk differs from the config in {}",
+            mismatches
+                .iter()
+                .map(|mismatch| mismatch.setting.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    Ok(())
+}
+
+pub fn clean(config: &ProjectConfig, dry_run: bool) -> Result<()> {
+    let output = if config.build_tool.is_gradle() {
+        "build"
+    } else {
+        "target"
+    };
+    // Modules have their own output, the aggregator may have some too
+    let mut output_dirs = config.module_dirs();
+    if !config.modules.is_empty() {
+        output_dirs.push(config.app_dir());
+    }
+    output_dirs = output_dirs
+        .into_iter()
+        .map(|dir| dir.join(output))
+        .filter(|dir| dir.exists())
+        .collect();
+    if output_dirs.is_empty() {
+        println!("Nothing to clean");
+        return Ok(());
+    }
+    if dry_run {
+        for output_dir in &output_dirs {
+            println!(
+                "Would delete: {} ({} files)",
+                output_dir.display(),
+                count_files(output_dir)?
+            );
+        }
+        return Ok(());
+    }
+
+    for output_dir in &output_dirs {
+        fs::remove_dir_all(output_dir)?;
+        println!("Deleted {}", output_dir.display());
+    }
+    Ok(())
+}
+
+/// Refuse to reset imported projects, their directory wasn't created by
+/// spring-init and may hold work that exists nowhere else
+pub fn check_reset(config: &ProjectConfig) -> Result<()> {
+    match &config.project_dir {
+        Some(dir) => Err(color_eyre::eyre::eyre!(
+            "{} was imported, reset only deletes projects generated by spring-init",
+            dir
+        )),
+        None => Ok(()),
+    }
+}
+
+pub fn reset(config: &ProjectConfig, dry_run: bool) -> Result<()> {
+    check_reset(config)?;
+    if dry_run {
+        if Path::new("spring.zip").exists() {
+            println!("Would delete: spring.zip");
+        }
+        if config.app_dir().exists() {
+            println!(
+                "Would delete: {} ({} files)",
+                config.app_dir().display(),
+                count_files(&config.app_dir())?
+            );
+        }
+        return Ok(());
+    }
+
+    // Remove spring.zip if it exists
+    if Path::new("spring.zip").exists() {
+        fs::remove_file("spring.zip")?;
+    }
+
+    // Remove app directory if it exists
+    if config.app_dir().exists() {
+        fs::remove_dir_all(config.app_dir())?;
+    }
+
+    eprintln!("Project reset complete");
+    Ok(())
+}
+
+pub fn count_files(dir: &Path) -> Result<usize> {
+    let mut count = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            count += count_files(&entry.path())?;
+        } else {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+fn build_args(build_tool: BuildTool, keep_going: bool, strict: bool) -> Result<Vec<&'static str>> {
+    // The quality plugins are only configured for Maven, and Gradle's build
+    // already runs the check task
+    if strict && build_tool.is_gradle() {
+        return Err(color_eyre::eyre::eyre!(
+            "--strict is only supported for Maven projects"
+        ));
+    }
+    let mut args = if build_tool.is_gradle() {
+        vec!["build"]
+    } else if strict {
+        vec!["verify"]
+    } else {
+        vec!["package"]
+    };
+    if keep_going {
+        args.push(if build_tool.is_gradle() {
+            "--continue"
+        } else {
+            "--fail-at-end"
+        });
+    }
+    Ok(args)
+}
+
+/// Build the project, with the Maven profile of `environment` if given.
+/// `strict` runs through verify, where the quality plugins fail on violations.
+pub fn build(
+    config: &ProjectConfig,
+    keep_going: bool,
+    environment: Option<Environment>,
+    strict: bool,
+) -> Result<()> {
+    if let Some(environment) = environment {
+        profiles::check(config, environment)?;
+    }
+    let args = build_args(config.build_tool, keep_going, strict)?;
+    hooks::run(config, Hook::PreBuild, None, false)?;
+    println!("Building project...");
+    let program = wrapper::program(config);
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .args(environment.map(|environment| format!("-P{}", environment.as_str())))
+        .current_dir(config.app_dir());
+    jdk::configure(&mut command, config);
+    repositories::configure(&mut command, config);
+    let status = command.status()?;
+
+    if !status.success() {
+        if keep_going {
+            return Err(color_eyre::eyre::eyre!(
+                "Build finished with failures, see the summary above"
+            ));
+        }
+        return Err(color_eyre::eyre::eyre!("Failed to build project"));
+    }
+
+    println!("Build complete");
+    hooks::run(config, Hook::PostBuild, None, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keep_going_per_build_tool() {
+        assert_eq!(
+            build_args(BuildTool::Maven, true, false).unwrap(),
+            ["package", "--fail-at-end"]
+        );
+        assert_eq!(
+            build_args(BuildTool::Maven, true, true).unwrap(),
+            ["verify", "--fail-at-end"]
+        );
+        for gradle in [BuildTool::Gradle, BuildTool::GradleKotlin] {
+            assert_eq!(
+                build_args(gradle, true, false).unwrap(),
+                ["build", "--continue"]
+            );
+            assert_eq!(build_args(gradle, false, false).unwrap(), ["build"]);
+        }
+    }
+
+    #[test]
+    fn strict_is_rejected_for_gradle() {
+        assert!(build_args(BuildTool::Gradle, false, true).is_err());
+        assert!(build_args(BuildTool::GradleKotlin, true, true).is_err());
+    }
+
+    #[test]
+    fn subdir_nests_the_project() {
+        let config = ProjectConfig {
+            projects_dir: "projects".to_string(),
+            subdir: Some("services".to_string()),
+            app_name: "billing".to_string(),
+            ..ProjectConfig::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(
+            config.scaffold_dir(),
+            Path::new("projects").join("services")
+        );
+        assert_eq!(
+            config.app_dir(),
+            Path::new("projects").join("services").join("billing")
+        );
+
+        let nested = ProjectConfig {
+            subdir: Some("services/payments/".to_string()),
+            ..config
+        };
+        assert_eq!(
+            nested.scaffold_dir(),
+            Path::new("projects").join("services").join("payments")
+        );
+    }
+
+    #[test]
+    fn subdir_outside_projects_dir_is_rejected() {
+        for subdir in ["..", "services/../..", "/tmp"] {
+            let config = ProjectConfig {
+                subdir: Some(subdir.to_string()),
+                ..ProjectConfig::default()
+            };
+            assert!(config.validate().is_err(), "{} was accepted", subdir);
+        }
+    }
+
+    #[test]
+    fn reset_refuses_imported_projects() {
+        let imported = ProjectConfig {
+            project_dir: Some("/home/dev/legacy-service".to_string()),
+            ..ProjectConfig::default()
+        };
+        assert!(check_reset(&imported).is_err());
+        assert!(reset(&imported, false).is_err());
+        assert!(check_reset(&ProjectConfig::default()).is_ok());
+    }
+}
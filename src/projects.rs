@@ -0,0 +1,186 @@
+use crate::{BuildTool, Language, Packaging, ProjectConfig};
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Settings a project was generated with, restored when it becomes active
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProjectState {
+    pub app_version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_qualifier: Option<String>,
+    pub package_name: String,
+    pub java_version: String,
+    pub boot_version: String,
+    #[serde(default)]
+    pub subdir: Option<String>,
+    #[serde(default)]
+    pub build_tool: BuildTool,
+    #[serde(default)]
+    pub language: Language,
+    #[serde(default)]
+    pub packaging: Packaging,
+    /// Directory of an imported project, generated ones live under projects_dir
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modules: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifact_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_dir: Option<String>,
+}
+
+/// Projects generated under projects_dir or imported, keyed by app name
+#[derive(Serialize, Deserialize, Default)]
+pub struct Registry {
+    pub active: Option<String>,
+    pub projects: BTreeMap<String, ProjectState>,
+}
+
+fn registry_path(config: &ProjectConfig) -> PathBuf {
+    PathBuf::from(&config.projects_dir)
+        .join(".spring-init")
+        .join("projects.json")
+}
+
+impl Registry {
+    pub fn load(config: &ProjectConfig) -> Result<Self> {
+        let path = registry_path(config);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, config: &ProjectConfig) -> Result<()> {
+        let path = registry_path(config);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Record the project described by `config` and make it active
+    pub fn register(&mut self, config: &ProjectConfig) {
+        self.projects.insert(
+            config.app_name.clone(),
+            ProjectState {
+                app_version: config.app_version.clone(),
+                version_qualifier: config.version_qualifier.clone(),
+                package_name: config.package_name.clone(),
+                java_version: config.java_version.clone(),
+                boot_version: config.boot_version.clone(),
+                subdir: config.subdir.clone(),
+                build_tool: config.build_tool,
+                language: config.language,
+                packaging: config.packaging,
+                dir: config.project_dir.clone(),
+                modules: config.modules.clone(),
+                group_id: config.group_id.clone(),
+                artifact_id: config.artifact_id.clone(),
+                base_dir: config.base_dir.clone(),
+            },
+        );
+        self.active = Some(config.app_name.clone());
+    }
+
+    /// Point `config` at the active project, if any
+    pub fn apply_active(&self, config: &mut ProjectConfig) {
+        if let Some(project) = self
+            .active
+            .as_ref()
+            .and_then(|name| self.project_config(name, config))
+        {
+            *config = project;
+        }
+    }
+
+    /// `config` with the settings recorded for project `name`
+    pub fn project_config(&self, name: &str, config: &ProjectConfig) -> Option<ProjectConfig> {
+        let state = self.projects.get(name)?;
+        let mut project = config.clone();
+        project.app_name = name.to_string();
+        project.app_version = state.app_version.clone();
+        project.version_qualifier = state.version_qualifier.clone();
+        project.package_name = state.package_name.clone();
+        project.java_version = state.java_version.clone();
+        project.boot_version = state.boot_version.clone();
+        project.subdir = state.subdir.clone();
+        project.build_tool = state.build_tool;
+        project.language = state.language;
+        project.packaging = state.packaging;
+        project.project_dir = state.dir.clone();
+        project.modules = state.modules.clone();
+        project.group_id = state.group_id.clone();
+        project.artifact_id = state.artifact_id.clone();
+        project.base_dir = state.base_dir.clone();
+        Some(project)
+    }
+}
+
+pub fn list(config: &ProjectConfig) -> Result<()> {
+    let registry = Registry::load(config)?;
+    if registry.projects.is_empty() {
+        println!("No projects, run init to create one");
+        return Ok(());
+    }
+
+    for name in registry.projects.keys() {
+        let marker = if registry.active.as_ref() == Some(name) {
+            "*"
+        } else {
+            " "
+        };
+        let project = registry
+            .project_config(name, config)
+            .ok_or_else(|| color_eyre::eyre::eyre!("Unknown project: {}", name))?;
+        let missing = if project.app_dir().exists() {
+            ""
+        } else {
+            " (missing)"
+        };
+        println!(
+            "{} {:<30} {}{}",
+            marker,
+            name,
+            project.app_dir().display(),
+            missing
+        );
+    }
+    Ok(())
+}
+
+pub fn use_project(config: &ProjectConfig, name: &str) -> Result<()> {
+    let mut registry = Registry::load(config)?;
+    if !registry.projects.contains_key(name) {
+        return Err(color_eyre::eyre::eyre!("Unknown project: {}", name));
+    }
+    registry.active = Some(name.to_string());
+    registry.save(config)?;
+    println!("Now using project {}", name);
+    Ok(())
+}
+
+pub fn remove(config: &ProjectConfig, name: &str, delete: bool) -> Result<()> {
+    let mut registry = Registry::load(config)?;
+    let project = registry
+        .project_config(name, config)
+        .ok_or_else(|| color_eyre::eyre::eyre!("Unknown project: {}", name))?;
+
+    if delete && project.app_dir().exists() {
+        fs::remove_dir_all(project.app_dir())?;
+        println!("Deleted {}", project.app_dir().display());
+    }
+
+    registry.projects.remove(name);
+    if registry.active.as_deref() == Some(name) {
+        registry.active = None;
+    }
+    registry.save(config)?;
+    println!("Removed project {}", name);
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use crate::project::Language;
+use crate::{jdk, pom, repositories, wrapper, ProjectConfig};
+use clap::ValueEnum;
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::process::Command;
+
+const SPOTLESS: &str = "com.diffplug.spotless:spotless-maven-plugin:2.44.0";
+const GOOGLE_JAVA_FORMAT_VERSION: &str = "1.24.0";
+const CHECKSTYLE: &str = "org.apache.maven.plugins:maven-checkstyle-plugin:3.6.0";
+/// Ruleset written next to the pom, in line with google-java-format's output
+const CHECKSTYLE_CONFIG: &str = "checkstyle.xml";
+const CHECKSTYLE_RULES: &str = r#"<?xml version="1.0"?>
+<!DOCTYPE module PUBLIC
+    "-//Checkstyle//DTD Checkstyle Configuration 1.3//EN"
+    "https://checkstyle.org/dtds/configuration_1_3.dtd">
+<module name="Checker">
+    <property name="severity" value="warning"/>
+    <module name="LineLength">
+        <property name="max" value="100"/>
+        <property name="ignorePattern" value="^(package|import) "/>
+    </module>
+    <module name="TreeWalker">
+        <module name="AvoidStarImport"/>
+        <module name="RedundantImport"/>
+        <module name="UnusedImports"/>
+        <module name="PackageName"/>
+        <module name="TypeName"/>
+        <module name="MethodName"/>
+        <module name="ConstantName"/>
+        <module name="MemberName"/>
+        <module name="ParameterName"/>
+        <module name="LocalVariableName"/>
+        <module name="NeedBraces"/>
+        <module name="EmptyBlock">
+            <property name="option" value="text"/>
+        </module>
+        <module name="EmptyStatement"/>
+        <module name="EqualsHashCode"/>
+        <module name="MissingSwitchDefault"/>
+        <module name="SimplifyBooleanExpression"/>
+        <module name="OneStatementPerLine"/>
+        <module name="MultipleVariableDeclarations"/>
+        <module name="UpperEll"/>
+        <module name="ArrayTypeStyle"/>
+    </module>
+</module>
+"#;
+
+/// Formatter or static analysis tool wired into generated projects, see `quality`
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum QualityTool {
+    /// Spotless with google-java-format, ktfmt for Kotlin
+    Spotless,
+    /// Checkstyle with a bundled ruleset, Java only
+    Checkstyle,
+}
+
+impl QualityTool {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QualityTool::Spotless => "spotless",
+            QualityTool::Checkstyle => "checkstyle",
+        }
+    }
+
+    fn coordinates(&self) -> &'static str {
+        match self {
+            QualityTool::Spotless => SPOTLESS,
+            QualityTool::Checkstyle => CHECKSTYLE,
+        }
+    }
+
+    fn configuration(&self, language: Language) -> pom::Element {
+        let configuration = pom::Element::new("configuration");
+        match self {
+            QualityTool::Spotless => configuration.child(match language {
+                Language::Kotlin => pom::Element::new("kotlin").child(
+                    pom::Element::new("ktfmt").child(pom::Element::new("style").text("GOOGLE")),
+                ),
+                _ => pom::Element::new("java").child(
+                    pom::Element::new("googleJavaFormat")
+                        .child(pom::Element::new("version").text(GOOGLE_JAVA_FORMAT_VERSION)),
+                ),
+            }),
+            QualityTool::Checkstyle => configuration
+                .child(pom::Element::new("configLocation").text(CHECKSTYLE_CONFIG))
+                .child(pom::Element::new("consoleOutput").text("true"))
+                .child(pom::Element::new("failOnViolation").text("true"))
+                .child(pom::Element::new("violationSeverity").text("warning"))
+                .child(pom::Element::new("includeTestSourceDirectory").text("true")),
+        }
+    }
+}
+
+/// The `check` goal of both plugins binds to verify, so `package` builds
+/// stay lenient while `build --strict` and CI's `verify` fail on violations
+fn executions() -> pom::Element {
+    pom::Element::new("executions").child(
+        pom::Element::new("execution")
+            .child(pom::Element::new("goals").child(pom::Element::new("goal").text("check"))),
+    )
+}
+
+/// Tools among `quality` that apply to the project's language
+fn applicable(config: &ProjectConfig) -> Vec<QualityTool> {
+    config
+        .quality
+        .iter()
+        .copied()
+        .filter(|tool| match tool {
+            QualityTool::Spotless => config.language != Language::Groovy,
+            QualityTool::Checkstyle => config.language == Language::Java,
+        })
+        .collect()
+}
+
+/// Add the configured quality plugins to pom.xml with their configuration,
+/// write the Checkstyle ruleset, and format the sources so a fresh project
+/// passes `build --strict`
+pub fn configure(config: &ProjectConfig, offline: bool) -> Result<()> {
+    let tools = applicable(config);
+    for tool in &config.quality {
+        if !tools.contains(tool) {
+            eprintln!(
+                "Skipping {} for {} project",
+                tool.as_str(),
+                config.language.as_str()
+            );
+        }
+    }
+    if tools.is_empty() {
+        return Ok(());
+    }
+
+    let pom_path = config.build_file();
+    let mut pom = pom::Pom::load(&pom_path)?;
+    let mut changed = false;
+    for tool in &tools {
+        let coordinates = pom::Coordinates::parse(tool.coordinates())?;
+        let added = pom.upsert_plugin(&coordinates)? != pom::Change::Unchanged;
+        let mut updated = false;
+        for element in [tool.configuration(config.language), executions()] {
+            updated |=
+                pom.set_plugin_element(&coordinates.group_id, &coordinates.artifact_id, &element)?;
+        }
+        if added || updated {
+            eprintln!("Adding {}", tool.as_str());
+            changed = true;
+        }
+    }
+    if changed {
+        pom.save(&pom_path)?;
+    }
+
+    if tools.contains(&QualityTool::Checkstyle) {
+        let path = config.module_dir().join(CHECKSTYLE_CONFIG);
+        if !path.exists() {
+            fs::write(&path, CHECKSTYLE_RULES)?;
+        }
+    }
+
+    // Initializr indents with tabs, which google-java-format rejects
+    if tools.contains(&QualityTool::Spotless) {
+        let mut command = Command::new(wrapper::maven(config));
+        command
+            .current_dir(config.app_dir())
+            .arg("-q")
+            .arg("spotless:apply")
+            .args(offline.then_some("-o"));
+        jdk::configure(&mut command, config);
+        repositories::configure(&mut command, config);
+        let formatted = command
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false);
+        if !formatted {
+            eprintln!(
+                "Warning: could not format the sources, run `./mvnw spotless:apply` before `spring-init build --strict`"
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,389 @@
+use crate::{initializr, pom, projects, BuildTool, ProjectConfig};
+use color_eyre::eyre::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Source extensions whose package declarations and imports are rewritten
+const SOURCE_EXTENSIONS: &[&str] = &["java", "kt", "groovy"];
+
+/// Replace `old` with `new` where it's a whole package name or the prefix
+/// of a qualified name, e.g. in `package com.example.demo;` and
+/// `import com.example.demo.web.Controller;`, but not in
+/// `com.example.demo2` or `org.com.example.demo`
+fn replace_package(text: &str, old: &str, new: &str) -> String {
+    let is_name = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find(old) {
+        result.push_str(&rest[..pos]);
+        let before = result.chars().last();
+        let after = rest[pos + old.len()..].chars().next();
+        let starts = before.is_none_or(|c| !is_name(c) && c != '.');
+        let ends = after.is_none_or(|c| !is_name(c));
+        result.push_str(if starts && ends { new } else { old });
+        rest = &rest[pos + old.len()..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Source roots of every module, e.g. src/main/java and src/test/kotlin
+fn source_roots(config: &ProjectConfig) -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+    for dir in config.module_dirs() {
+        for set in ["main", "test"] {
+            for language in ["java", "kotlin", "groovy"] {
+                let root = dir.join("src").join(set).join(language);
+                if root.is_dir() {
+                    roots.push(root);
+                }
+            }
+        }
+    }
+    roots
+}
+
+fn package_dir(root: &Path, package: &str) -> PathBuf {
+    package
+        .split('.')
+        .fold(root.to_path_buf(), |dir, segment| dir.join(segment))
+}
+
+/// Move everything in `from` into `to`, merging directories that exist in both
+fn merge_into(from: &Path, to: &Path) -> Result<()> {
+    if !to.exists() {
+        fs::create_dir_all(to.parent().unwrap())?;
+        fs::rename(from, to)?;
+        return Ok(());
+    }
+    for entry in fs::read_dir(from)? {
+        let path = entry?.path();
+        let target = to.join(path.file_name().unwrap());
+        if path.is_dir() {
+            merge_into(&path, &target)?;
+        } else if target.exists() {
+            return Err(color_eyre::eyre::eyre!(
+                "{} already exists, can't move {} there",
+                target.display(),
+                path.display()
+            ));
+        } else {
+            fs::rename(&path, &target)?;
+        }
+    }
+    fs::remove_dir(from)?;
+    Ok(())
+}
+
+/// Move the sources under `root` from the old package directory to the new
+/// one, going through a temporary directory since either may contain the
+/// other, and remove the emptied parents
+fn move_package(root: &Path, old: &str, new: &str) -> Result<()> {
+    let from = package_dir(root, old);
+    if !from.is_dir() {
+        return Ok(());
+    }
+    let staging = root.join(".spring-init-rename");
+    fs::rename(&from, &staging)?;
+    let mut parent = from.parent();
+    while let Some(dir) = parent.filter(|dir| *dir != root) {
+        if fs::remove_dir(dir).is_err() {
+            break;
+        }
+        parent = dir.parent();
+    }
+    merge_into(&staging, &package_dir(root, new))
+}
+
+fn source_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            source_files(&path, files)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| SOURCE_EXTENSIONS.iter().any(|known| ext == *known))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Set `spring.application.name` in application.properties or the
+/// application.yml written by init
+fn rename_application(config: &ProjectConfig, old: &str, new: &str) -> Result<()> {
+    let resources = config
+        .module_dir()
+        .join("src")
+        .join("main")
+        .join("resources");
+    let properties = resources.join("application.properties");
+    if properties.exists() {
+        let content = fs::read_to_string(&properties)?;
+        let renamed = content.replace(
+            &format!("spring.application.name={}", old),
+            &format!("spring.application.name={}", new),
+        );
+        if renamed != content {
+            fs::write(&properties, renamed)?;
+        }
+    }
+    let yml = resources.join("application.yml");
+    if yml.exists() {
+        let content = fs::read_to_string(&yml)?;
+        let mut in_application = false;
+        let mut changed = false;
+        let lines: Vec<String> = content
+            .lines()
+            .map(|line| {
+                if line.trim_start() == "application:" {
+                    in_application = true;
+                } else if in_application && line.trim() == format!("name: {}", old) {
+                    in_application = false;
+                    changed = true;
+                    return line.replace(old, new);
+                } else if !line.starts_with("    ") {
+                    in_application = false;
+                }
+                line.to_string()
+            })
+            .collect();
+        if changed {
+            fs::write(&yml, lines.join("\n") + "\n")?;
+        }
+    }
+    Ok(())
+}
+
+/// Rewrite groupId, artifactId, and name in the aggregator or only pom, and
+/// the parent and library dependency references in module poms
+fn rename_maven(
+    config: &ProjectConfig,
+    name: Option<&str>,
+    artifact: Option<&str>,
+    group: Option<&str>,
+) -> Result<()> {
+    let root_path = config.app_dir().join("pom.xml");
+    let mut root = pom::Pom::load(&root_path)?;
+    let old_group = root.text(&["groupId"])?;
+    if let Some(group) = group {
+        root.set_text(&["groupId"], group)?;
+    }
+    if let Some(artifact) = artifact {
+        root.set_text(&["artifactId"], artifact)?;
+    }
+    if let Some(name) = name {
+        root.set_text(&["name"], name)?;
+    }
+    root.save(&root_path)?;
+
+    for (i, module) in config.modules.iter().enumerate() {
+        let path = config.app_dir().join(module).join("pom.xml");
+        let mut pom = pom::Pom::load(&path)?;
+        if let Some(group) = group {
+            pom.set_text(&["parent", "groupId"], group)?;
+        }
+        if let Some(artifact) = artifact {
+            pom.set_text(&["parent", "artifactId"], artifact)?;
+        }
+        // The runnable module depends on the libraries by the old groupId
+        if let (0, Some(group), Some(old_group)) = (i, group, &old_group) {
+            for library in &config.modules[1..] {
+                if pom.remove_dependency(old_group, library)? {
+                    pom.upsert_dependency(&pom::Dependency {
+                        coordinates: pom::Coordinates {
+                            group_id: group.to_string(),
+                            artifact_id: library.clone(),
+                            version: Some("${project.version}".to_string()),
+                        },
+                        scope: None,
+                        optional: false,
+                    })?;
+                }
+            }
+        }
+        pom.save(&path)?;
+    }
+    Ok(())
+}
+
+/// Rewrite `group` in build.gradle(.kts) and `rootProject.name` in
+/// settings.gradle(.kts)
+fn rename_gradle(
+    config: &ProjectConfig,
+    artifact: Option<&str>,
+    group: Option<&str>,
+) -> Result<()> {
+    let rewrite = |path: &Path, key: &str, value: &str| -> Result<()> {
+        if !path.exists() {
+            return Ok(());
+        }
+        let content = fs::read_to_string(path)?;
+        let lines: Vec<String> = content
+            .lines()
+            .map(|line| {
+                let trimmed = line.trim_start();
+                let Some(rest) = trimmed.strip_prefix(key) else {
+                    return line.to_string();
+                };
+                if !rest.trim_start().starts_with('=') {
+                    return line.to_string();
+                }
+                let quote = if rest.contains('"') { '"' } else { '\'' };
+                let indent = &line[..line.len() - trimmed.len()];
+                format!("{}{} = {}{}{}", indent, key, quote, value, quote)
+            })
+            .collect();
+        fs::write(path, lines.join("\n") + "\n")?;
+        Ok(())
+    };
+
+    let kts = if config.build_tool == BuildTool::GradleKotlin {
+        ".kts"
+    } else {
+        ""
+    };
+    let app_dir = config.app_dir();
+    if let Some(group) = group {
+        rewrite(
+            &app_dir.join(format!("build.gradle{}", kts)),
+            "group",
+            group,
+        )?;
+    }
+    if let Some(artifact) = artifact {
+        rewrite(
+            &app_dir.join(format!("settings.gradle{}", kts)),
+            "rootProject.name",
+            artifact,
+        )?;
+    }
+    Ok(())
+}
+
+/// Rename the app and/or move it to another base package: rewrite the build
+/// file's coordinates, move the sources and rewrite their package
+/// declarations and imports, rename the project directory, and register the
+/// project under its new name
+pub fn rename(
+    config: &ProjectConfig,
+    app: Option<&str>,
+    package: Option<&str>,
+    dry_run: bool,
+) -> Result<()> {
+    if !config.build_file().exists() {
+        return Err(color_eyre::eyre::eyre!(
+            "No {} found, run init first",
+            config.build_file().display()
+        ));
+    }
+    let app = app.filter(|app| *app != config.app_name);
+    let package = package.filter(|package| *package != config.package_name);
+    if let Some(app) = app {
+        initializr::check_artifact("app name", app)?;
+    }
+    if let Some(package) = package {
+        initializr::check_package(package)?;
+    }
+    if app.is_none() && package.is_none() {
+        println!("Nothing to rename");
+        return Ok(());
+    }
+
+    let mut renamed = config.clone();
+    if let Some(app) = app {
+        renamed.app_name = app.to_string();
+    }
+    if let Some(package) = package {
+        renamed.package_name = package.to_string();
+    }
+    // Coordinates and the directory follow the app name and package unless
+    // configured separately, imported projects keep their directory
+    let group = package.filter(|_| config.group_id.is_none());
+    let artifact = app.filter(|_| config.artifact_id.is_none());
+    let move_dir = app.is_some() && config.project_dir.is_none() && config.base_dir.is_none();
+    if move_dir && renamed.app_dir().exists() {
+        return Err(color_eyre::eyre::eyre!(
+            "{} already exists",
+            renamed.app_dir().display()
+        ));
+    }
+
+    let mut files = Vec::new();
+    if package.is_some() {
+        for root in source_roots(config) {
+            source_files(&root, &mut files)?;
+        }
+    }
+
+    if dry_run {
+        if let Some(group) = group {
+            println!("Would set groupId to {}", group);
+        }
+        if let Some(artifact) = artifact {
+            println!("Would set artifactId to {}", artifact);
+        }
+        if let Some(app) = app {
+            println!("Would set name to {}", app);
+        }
+        if let Some(package) = package {
+            println!(
+                "Would move sources from {} to {} and rewrite {} files",
+                config.package_name,
+                package,
+                files.len()
+            );
+        }
+        if move_dir {
+            println!(
+                "Would move {} to {}",
+                config.app_dir().display(),
+                renamed.app_dir().display()
+            );
+        }
+        return Ok(());
+    }
+
+    match config.build_tool {
+        BuildTool::Maven => rename_maven(config, app, artifact, group)?,
+        BuildTool::Gradle | BuildTool::GradleKotlin => rename_gradle(config, artifact, group)?,
+    }
+    if let Some(app) = app {
+        rename_application(config, &config.app_name, app)?;
+    }
+
+    if let Some(package) = package {
+        for path in &files {
+            let content = fs::read_to_string(path)?;
+            let rewritten = replace_package(&content, &config.package_name, package);
+            if rewritten != content {
+                fs::write(path, rewritten)?;
+            }
+        }
+        for root in source_roots(config) {
+            move_package(&root, &config.package_name, package)?;
+        }
+        eprintln!("Moved sources from {} to {}", config.package_name, package);
+    }
+
+    if move_dir {
+        fs::rename(config.app_dir(), renamed.app_dir())?;
+        eprintln!(
+            "Moved {} to {}",
+            config.app_dir().display(),
+            renamed.app_dir().display()
+        );
+    }
+
+    let mut registry = projects::Registry::load(config)?;
+    registry.projects.remove(&config.app_name);
+    registry.register(&renamed);
+    registry.save(config)?;
+
+    println!(
+        "Renamed {} ({}) to {} ({})",
+        config.app_name, config.package_name, renamed.app_name, renamed.package_name
+    );
+    Ok(())
+}
@@ -0,0 +1,300 @@
+use crate::pom::{self, Element};
+use crate::ProjectConfig;
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, BufRead, IsTerminal};
+use std::process::Command;
+
+/// Keyring service the repository passwords are stored under, next to the API keys
+const SERVICE: &str = "spring-init";
+/// Settings profile declaring maven_repositories
+const PROFILE: &str = "spring-init";
+/// Argument in .mvn/maven.config pointing Maven at the project's settings
+const SETTINGS_ARG: &str = "--settings=.mvn/settings.xml";
+/// Repositories are declared in both lists, so plugins published to them
+/// resolve as well
+const LISTS: [(&str, &str); 2] = [
+    ("repositories", "repository"),
+    ("pluginRepositories", "pluginRepository"),
+];
+
+/// Maven repository added to generated projects, e.g. an internal Nexus
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MavenRepository {
+    pub id: String,
+    pub url: String,
+    /// Also resolve snapshots from the repository
+    #[serde(default)]
+    pub snapshots: bool,
+    /// User to authenticate as, the password comes from `repos login`
+    #[serde(default)]
+    pub username: Option<String>,
+}
+
+/// Mirror replacing the repositories it matches, e.g. Maven Central
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MavenMirror {
+    pub id: String,
+    pub url: String,
+    /// Repository IDs the mirror stands in for, `*` for all of them
+    #[serde(default = "default_mirror_of")]
+    pub mirror_of: String,
+    /// User to authenticate as, the password comes from `repos login`
+    #[serde(default)]
+    pub username: Option<String>,
+}
+
+fn default_mirror_of() -> String {
+    "*".to_string()
+}
+
+fn entry(id: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(SERVICE, &format!("maven:{}", id))
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to open the OS keyring: {}", e))
+}
+
+/// Variable the settings read a server's password from, e.g. MAVEN_NEXUS_PASSWORD
+pub fn password_var(id: &str) -> String {
+    let id: String = id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("MAVEN_{}_PASSWORD", id)
+}
+
+/// Servers needing credentials, as ID and username
+fn servers(config: &ProjectConfig) -> Vec<(&str, &str)> {
+    let repositories = config
+        .maven_repositories
+        .iter()
+        .filter_map(|repository| Some((repository.id.as_str(), repository.username.as_deref()?)));
+    let mirrors = config
+        .maven_mirrors
+        .iter()
+        .filter_map(|mirror| Some((mirror.id.as_str(), mirror.username.as_deref()?)));
+    repositories.chain(mirrors).collect()
+}
+
+/// Hand Maven the passwords of the configured servers from the keyring. A
+/// variable that is already set wins, so CI can provide its own.
+pub fn configure(command: &mut Command, config: &ProjectConfig) {
+    for (id, _) in servers(config) {
+        let var = password_var(id);
+        if std::env::var_os(&var).is_some() {
+            continue;
+        }
+        if let Some(password) = entry(id).ok().and_then(|entry| entry.get_password().ok()) {
+            command.env(var, password);
+        }
+    }
+}
+
+/// Store a repository password in the OS keyring, read without echo or from stdin
+pub fn login(id: &str) -> Result<()> {
+    let password = if io::stdin().is_terminal() {
+        inquire::Password::new(&format!("Password for {}:", id))
+            .without_confirmation()
+            .prompt()?
+    } else {
+        let mut line = String::new();
+        io::stdin().lock().read_line(&mut line)?;
+        line
+    };
+    let password = password.trim();
+    if password.is_empty() {
+        return Err(color_eyre::eyre::eyre!("No password given"));
+    }
+    entry(id)?
+        .set_password(password)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to store the password: {}", e))?;
+    println!("Stored the {} password in the OS keyring", id);
+    Ok(())
+}
+
+/// Remove a repository password from the OS keyring
+pub fn logout(id: &str) -> Result<()> {
+    match entry(id)?.delete_credential() {
+        Ok(()) => println!("Removed the {} password from the OS keyring", id),
+        Err(keyring::Error::NoEntry) => println!("No {} password in the OS keyring", id),
+        Err(e) => {
+            return Err(color_eyre::eyre::eyre!(
+                "Failed to remove the password: {}",
+                e
+            ))
+        }
+    }
+    Ok(())
+}
+
+fn repository_element(name: &str, repository: &MavenRepository) -> Element {
+    Element::new(name)
+        .child(Element::new("id").text(&repository.id))
+        .child(Element::new("url").text(&repository.url))
+        .child(Element::new("releases").child(Element::new("enabled").text("true")))
+        .child(
+            Element::new("snapshots")
+                .child(Element::new("enabled").text(&repository.snapshots.to_string())),
+        )
+}
+
+fn settings(config: &ProjectConfig) -> String {
+    let mut sections = Vec::new();
+    let servers = servers(config);
+    if !servers.is_empty() {
+        sections.push(
+            servers
+                .iter()
+                .fold(Element::new("servers"), |list, (id, username)| {
+                    list.child(
+                        Element::new("server")
+                            .child(Element::new("id").text(id))
+                            .child(Element::new("username").text(username))
+                            .child(
+                                Element::new("password")
+                                    .text(&format!("${{env.{}}}", password_var(id))),
+                            ),
+                    )
+                }),
+        );
+    }
+    if !config.maven_mirrors.is_empty() {
+        sections.push(
+            config
+                .maven_mirrors
+                .iter()
+                .fold(Element::new("mirrors"), |list, mirror| {
+                    list.child(
+                        Element::new("mirror")
+                            .child(Element::new("id").text(&mirror.id))
+                            .child(Element::new("url").text(&mirror.url))
+                            .child(Element::new("mirrorOf").text(&mirror.mirror_of)),
+                    )
+                }),
+        );
+    }
+    if !config.maven_repositories.is_empty() && !config.repositories_in_pom {
+        let profile = LISTS.iter().fold(
+            Element::new("profile").child(Element::new("id").text(PROFILE)),
+            |profile, (list, name)| {
+                profile.child(
+                    config
+                        .maven_repositories
+                        .iter()
+                        .fold(Element::new(list), |list, repository| {
+                            list.child(repository_element(name, repository))
+                        }),
+                )
+            },
+        );
+        sections.push(Element::new("profiles").child(profile));
+        sections.push(
+            Element::new("activeProfiles").child(Element::new("activeProfile").text(PROFILE)),
+        );
+    }
+
+    let body: Vec<String> = sections
+        .iter()
+        .map(|section| section.render("    ", "    "))
+        .collect();
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <settings xmlns=\"http://maven.apache.org/SETTINGS/1.2.0\"\n          \
+        xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\"\n          \
+        xsi:schemaLocation=\"http://maven.apache.org/SETTINGS/1.2.0 https://maven.apache.org/xsd/settings-1.2.0.xsd\">\n\
+        {}\n\
+        </settings>\n",
+        body.join("\n")
+    )
+}
+
+/// Write the configured repositories, mirrors, and credentials into the
+/// project's .mvn/settings.xml, which .mvn/maven.config points Maven at, or
+/// with `repositories_in_pom` declare the repositories in pom.xml. Passwords
+/// stay in the keyring, the settings read them from the environment.
+pub fn sync(config: &ProjectConfig, dry_run: bool) -> Result<()> {
+    if config.maven_repositories.is_empty() && config.maven_mirrors.is_empty() {
+        println!("No maven_repositories or maven_mirrors configured, nothing to do");
+        return Ok(());
+    }
+    if config.build_tool.is_gradle() {
+        return Err(color_eyre::eyre::eyre!(
+            "Repository settings are only supported for Maven projects"
+        ));
+    }
+    let dir = config.app_dir();
+    let settings_path = dir.join(".mvn").join("settings.xml");
+    let in_pom = config.repositories_in_pom && !config.maven_repositories.is_empty();
+    let needs_settings = !servers(config).is_empty()
+        || !config.maven_mirrors.is_empty()
+        || !config.repositories_in_pom;
+    if dry_run {
+        if in_pom {
+            let ids: Vec<&str> = config
+                .maven_repositories
+                .iter()
+                .map(|repository| repository.id.as_str())
+                .collect();
+            println!("Would add repositories to pom.xml: {}", ids.join(", "));
+        }
+        if needs_settings {
+            println!("Would write {}", settings_path.display());
+        }
+        return Ok(());
+    }
+
+    if in_pom {
+        let pom_path = config.build_file();
+        let mut pom = pom::Pom::load(&pom_path)?;
+        let mut changed = false;
+        for (list, name) in LISTS {
+            for repository in &config.maven_repositories {
+                changed |= pom.upsert_by_id(list, &repository_element(name, repository))?
+                    != pom::Change::Unchanged;
+            }
+        }
+        if changed {
+            pom.save(&pom_path)?;
+            eprintln!("Added repositories to {}", pom_path.display());
+        }
+    }
+
+    if needs_settings {
+        fs::create_dir_all(dir.join(".mvn"))?;
+        fs::write(&settings_path, settings(config))?;
+        eprintln!("Wrote {}", settings_path.display());
+
+        let config_path = dir.join(".mvn").join("maven.config");
+        let mut maven_config = fs::read_to_string(&config_path).unwrap_or_default();
+        if !maven_config.contains(SETTINGS_ARG) {
+            if !maven_config.is_empty() && !maven_config.ends_with('\n') {
+                maven_config.push('\n');
+            }
+            maven_config.push_str(SETTINGS_ARG);
+            maven_config.push('\n');
+            fs::write(&config_path, maven_config)?;
+        }
+    }
+
+    for (id, _) in servers(config) {
+        let stored = entry(id)
+            .ok()
+            .and_then(|entry| entry.get_password().ok())
+            .is_some();
+        if !stored && std::env::var_os(password_var(id)).is_none() {
+            eprintln!(
+                "Warning: no password for {}, run `spring-init repos login {}` or set {}",
+                id,
+                id,
+                password_var(id)
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,748 @@
+use crate::logs::{LogFilter, Printer};
+use crate::output::{self, OutputFormat, RunInfo};
+use crate::profiles::Environment;
+use crate::{deps, jdk, native, project, ProjectConfig};
+use clap::{Args, ValueEnum};
+use color_eyre::eyre::Result;
+use std::fs::{self, File};
+use std::io::{ErrorKind, IsTerminal, Read, Seek, SeekFrom, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// How long to wait after SIGTERM before killing the app
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+/// Actuator's health endpoint, relative to the context path
+const HEALTH_PATH: &str = "/actuator/health";
+/// Spring Boot's port when server.port isn't set
+const DEFAULT_PORT: u16 = 8080;
+/// Conventional JDWP port, used by `run --debug` and the IDE attach
+/// configurations
+pub const DEBUG_PORT: u16 = 5005;
+
+#[derive(Args)]
+pub struct RunArgs {
+    /// Spring profiles to activate, e.g. dev or dev,local
+    #[arg(long)]
+    profile: Option<String>,
+    /// JVM option passed before -jar, e.g. --jvm-arg=-Xmx512m (repeatable)
+    #[arg(long = "jvm-arg", allow_hyphen_values = true)]
+    jvm_args: Vec<String>,
+    /// Environment variable for the app as KEY=VALUE, or the environment to
+    /// build and run with, dev or prod (repeatable)
+    #[arg(long = "env", value_parser = parse_env_arg)]
+    env: Vec<EnvArg>,
+    /// File with KEY=VALUE lines to add to the app's environment
+    #[arg(long)]
+    env_file: Option<String>,
+    /// Run in the background, see `status`, `logs`, and `stop`
+    #[arg(long, short)]
+    detach: bool,
+    /// Run the existing jar even if sources changed since it was built
+    #[arg(long)]
+    no_build: bool,
+    /// Run the GraalVM native executable from `build --native` instead of the jar
+    #[arg(long)]
+    native: bool,
+    /// Server port, overriding server.port and SERVER_PORT; 0 picks a free one
+    #[arg(long)]
+    port: Option<u16>,
+    /// Listen for a debugger on localhost with the JDWP agent, on port 5005
+    /// unless given as --debug=PORT
+    #[arg(
+        long,
+        value_name = "PORT",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "5005"
+    )]
+    debug: Option<u16>,
+    /// With --debug, don't start the app until a debugger attaches
+    #[arg(long, requires = "debug")]
+    suspend: bool,
+    /// Run in the background and wait until the actuator health endpoint
+    /// reports UP, adding actuator when it's missing
+    #[arg(long)]
+    wait_healthy: bool,
+    /// Seconds to wait for --wait-healthy before stopping the app and failing
+    #[arg(long, default_value_t = 120, requires = "wait_healthy")]
+    wait_timeout: u64,
+}
+
+#[derive(Clone)]
+enum EnvArg {
+    Variable(String, String),
+    Environment(Environment),
+}
+
+/// `--env` takes variables, and names an environment when there is no `=`
+fn parse_env_arg(value: &str) -> Result<EnvArg, String> {
+    if value.contains('=') {
+        return parse_env_var(value).map(|(key, value)| EnvArg::Variable(key, value));
+    }
+    Environment::from_str(value, true)
+        .map(EnvArg::Environment)
+        .map_err(|_| format!("expected KEY=VALUE, dev, or prod, got {}", value))
+}
+
+fn parse_env_var(value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('=')
+        .filter(|(key, _)| !key.trim().is_empty())
+        .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected KEY=VALUE, got {}", value))
+}
+
+/// Read a .env style file, skipping blank lines and `#` comments and
+/// accepting an `export ` prefix and quoted values
+fn read_env_file(path: &str) -> Result<Vec<(String, String)>> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to read env file {}: {}", path, e))?;
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = parse_env_var(line)
+                .map_err(|e| color_eyre::eyre::eyre!("Invalid line in {}: {}", path, e))?;
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+                .unwrap_or(value);
+            Ok((key, value.to_string()))
+        })
+        .collect()
+}
+
+/// A port nothing listens on right now, from the OS
+fn free_port() -> Result<u16> {
+    Ok(TcpListener::bind(("127.0.0.1", 0))?.local_addr()?.port())
+}
+
+/// server.port from application.properties or application.yml, None when
+/// it can't be told, e.g. for a `${PORT}` placeholder
+fn configured_port(config: &ProjectConfig) -> Option<u16> {
+    let resources = config
+        .module_dir()
+        .join("src")
+        .join("main")
+        .join("resources");
+    if let Ok(content) = fs::read_to_string(resources.join("application.properties")) {
+        let value = content.lines().find_map(|line| {
+            let (key, value) = line.split_once(['=', ':'])?;
+            (key.trim() == "server.port").then(|| value.trim().to_string())
+        });
+        if let Some(value) = value {
+            return value.parse().ok();
+        }
+    }
+    for name in ["application.yml", "application.yaml"] {
+        let Ok(content) = fs::read_to_string(resources.join(name)) else {
+            continue;
+        };
+        let yaml: serde_norway::Value = serde_norway::from_str(&content).ok()?;
+        return match &yaml["server"]["port"] {
+            serde_norway::Value::Null => Some(DEFAULT_PORT),
+            serde_norway::Value::Number(port) => port.as_u64()?.try_into().ok(),
+            serde_norway::Value::String(port) => port.parse().ok(),
+            _ => None,
+        };
+    }
+    Some(DEFAULT_PORT)
+}
+
+/// Fail when something already listens on `port`, before the app would
+/// crash on it
+fn check_port(port: u16, given: bool) -> Result<()> {
+    if !in_use(port) {
+        return Ok(());
+    }
+    Err(color_eyre::eyre::eyre!(
+        "Port {} is already in use, {}pass --port <PORT>, or --port 0 to pick a free one",
+        port,
+        if given {
+            ""
+        } else {
+            "stop whatever listens on it or "
+        }
+    ))
+}
+
+fn in_use(port: u16) -> bool {
+    matches!(
+        TcpListener::bind(("0.0.0.0", port)),
+        Err(e) if e.kind() == ErrorKind::AddrInUse
+    )
+}
+
+/// Latest modification time of the files under `path`
+fn newest_mtime(path: &Path) -> Option<SystemTime> {
+    let metadata = fs::metadata(path).ok()?;
+    if !metadata.is_dir() {
+        return metadata.modified().ok();
+    }
+    fs::read_dir(path)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| newest_mtime(&entry.path()))
+        .max()
+}
+
+/// Whether `artifact` was built after the last change to the sources and build files
+pub fn is_current(config: &ProjectConfig, artifact: &Path) -> bool {
+    let Some(built) = fs::metadata(artifact).and_then(|meta| meta.modified()).ok() else {
+        return false;
+    };
+    let app_dir = config.app_dir();
+    let mut inputs = vec![
+        app_dir.join("pom.xml"),
+        app_dir.join("build.gradle"),
+        app_dir.join("build.gradle.kts"),
+        app_dir.join("settings.gradle"),
+        app_dir.join("settings.gradle.kts"),
+    ];
+    for dir in config.module_dirs() {
+        inputs.push(dir.join("src"));
+        inputs.push(dir.join("pom.xml"));
+    }
+    inputs
+        .iter()
+        .filter_map(|input| newest_mtime(input))
+        .all(|changed| changed <= built)
+}
+
+/// Run the built jar, or native executable, with the requested profiles, JVM
+/// options, and environment, building it first when it is missing or older
+/// than the sources
+pub async fn run(config: &ProjectConfig, args: &RunArgs, output: OutputFormat) -> Result<()> {
+    let mut environments = args.env.iter().filter_map(|arg| match arg {
+        EnvArg::Environment(environment) => Some(*environment),
+        EnvArg::Variable(..) => None,
+    });
+    let environment = environments.next();
+    if environments.next().is_some() {
+        return Err(color_eyre::eyre::eyre!("Only one environment can be given"));
+    }
+    if environment.is_some() && args.native {
+        return Err(color_eyre::eyre::eyre!(
+            "--env dev or prod can't be combined with --native"
+        ));
+    }
+    if args.debug.is_some() && args.native {
+        return Err(color_eyre::eyre::eyre!(
+            "Native executables can't be debugged with JDWP, drop --native"
+        ));
+    }
+
+    if args.detach || args.wait_healthy {
+        if let Some(pid) = running_pid(config) {
+            return Err(color_eyre::eyre::eyre!(
+                "{} is already running (pid {}), use `spring-init stop` first",
+                config.app_name,
+                pid
+            ));
+        }
+    }
+
+    // Variables given on the command line win over the env file
+    let mut variables = match &args.env_file {
+        Some(env_file) => read_env_file(env_file)?,
+        None => Vec::new(),
+    };
+    variables.extend(args.env.iter().filter_map(|arg| match arg {
+        EnvArg::Variable(key, value) => Some((key.clone(), value.clone())),
+        EnvArg::Environment(_) => None,
+    }));
+
+    if let Some(debug) = args.debug.filter(|debug| in_use(*debug)) {
+        return Err(color_eyre::eyre::eyre!(
+            "Debug port {} is already in use, pass --debug=<PORT>",
+            debug
+        ));
+    }
+    // Check the port before spending time on a build
+    let port = match args.port {
+        Some(0) => Some(free_port()?),
+        Some(port) => {
+            check_port(port, true)?;
+            Some(port)
+        }
+        None => {
+            // SERVER_PORT from --env, or inherited, overrides the config files
+            let server_port = variables
+                .iter()
+                .rev()
+                .find(|(key, _)| key == "SERVER_PORT")
+                .map(|(_, value)| value.clone())
+                .or_else(|| std::env::var("SERVER_PORT").ok());
+            let port = match server_port {
+                Some(value) => value.parse().ok(),
+                None => configured_port(config),
+            };
+            if let Some(port) = port.filter(|port| *port != 0) {
+                check_port(port, false)?;
+            }
+            port
+        }
+    };
+
+    if args.wait_healthy && !has_actuator(config)? {
+        if args.no_build {
+            return Err(color_eyre::eyre::eyre!(
+                "--wait-healthy needs actuator, which isn't a dependency, drop --no-build to add it"
+            ));
+        }
+        eprintln!("Adding actuator for the health check");
+        deps::add_dependencies(config, &["actuator".to_string()]).await?;
+    }
+
+    let artifact = if args.native {
+        config.native_path()
+    } else {
+        config.jar_path()
+    };
+    if !args.no_build {
+        if is_current(config, &artifact) {
+            eprintln!("{} is up to date, skipping build", artifact.display());
+        } else if args.native {
+            native::build(config)?;
+        } else {
+            project::build(config, false, environment, false)?;
+        }
+    }
+    if !artifact.exists() {
+        return Err(color_eyre::eyre::eyre!(
+            "No {} found at {}, run `spring-init build{}` first",
+            if args.native {
+                "native executable"
+            } else {
+                config.packaging.as_str()
+            },
+            artifact.display(),
+            if args.native { " --native" } else { "" }
+        ));
+    }
+
+    // Native executables take the same -D and -X options as the JVM
+    let mut command = if args.native {
+        Command::new(&artifact)
+    } else {
+        Command::new(jdk::java(config))
+    };
+    if let Some(debug) = args.debug {
+        command.arg(format!(
+            "-agentlib:jdwp=transport=dt_socket,server=y,suspend={},address=localhost:{}",
+            if args.suspend { "y" } else { "n" },
+            debug
+        ));
+    }
+    command.args(&args.jvm_args);
+    // The environment's profile comes first so --profile can override it
+    let profiles: Vec<&str> = environment
+        .map(|environment| environment.as_str())
+        .into_iter()
+        .chain(args.profile.as_deref())
+        .collect();
+    if !profiles.is_empty() {
+        command.arg(format!("-Dspring.profiles.active={}", profiles.join(",")));
+    }
+    if let (Some(port), Some(_)) = (port, args.port) {
+        command.arg(format!("-Dserver.port={}", port));
+    }
+    if !args.native {
+        command.arg("-jar").arg(&artifact);
+    }
+    command.current_dir(config.app_dir());
+    command.envs(variables);
+
+    let info = |pid: Option<u32>| RunInfo {
+        app_name: config.app_name.clone(),
+        port,
+        pid,
+        health: None,
+        debug_port: args.debug,
+        log: pid.map(|_| log_path(config).display().to_string()),
+    };
+    let debugger = || {
+        if let Some(debug) = args.debug {
+            eprintln!(
+                "Debugger can attach to localhost:{}{}",
+                debug,
+                if args.suspend {
+                    ", the app waits for it"
+                } else {
+                    ""
+                }
+            );
+        }
+    };
+    if args.wait_healthy {
+        let mut child = spawn_detached(config, command)?;
+        let timeout = Duration::from_secs(args.wait_timeout);
+        debugger();
+        return wait_healthy(config, &mut child, timeout, args.debug, output).await;
+    }
+    if args.detach {
+        let child = spawn_detached(config, command)?;
+        debugger();
+        if output.is_json() {
+            return output::print_json(&info(Some(child.id())));
+        }
+        println!(
+            "Started {} in the background (pid {}{}), logs in {}",
+            config.app_name,
+            child.id(),
+            port.map(|port| format!(", port {}", port))
+                .unwrap_or_default(),
+            log_path(config).display()
+        );
+        return Ok(());
+    }
+
+    if output.is_json() {
+        output::print_json(&info(None))?;
+    } else {
+        if let (Some(port), Some(_)) = (port, args.port) {
+            println!("Using port {}", port);
+        }
+        println!("Running {}", artifact.display());
+    }
+    debugger();
+    let status = command.status()?;
+    if !status.success() {
+        return Err(color_eyre::eyre::eyre!(
+            "Application exited with {}",
+            status
+        ));
+    }
+    Ok(())
+}
+
+/// Directory holding the pidfile and log of a detached run
+fn state_dir(config: &ProjectConfig) -> PathBuf {
+    config.app_dir().join(".spring-init")
+}
+
+fn pid_path(config: &ProjectConfig) -> PathBuf {
+    state_dir(config).join("app.pid")
+}
+
+fn log_path(config: &ProjectConfig) -> PathBuf {
+    state_dir(config).join("app.log")
+}
+
+#[cfg(unix)]
+fn is_alive(pid: u32) -> bool {
+    // Signal 0 only checks that the process exists
+    unsafe { libc::kill(pid as libc::pid_t, 0) == 0 }
+}
+
+/// Terminate, or with `force` kill, the process group led by `pid`, so
+/// JVMs forked by a build tool go down with it
+#[cfg(unix)]
+pub(crate) fn signal(pid: u32, force: bool) {
+    let signal = if force { libc::SIGKILL } else { libc::SIGTERM };
+    unsafe {
+        libc::kill(-(pid as libc::pid_t), signal);
+    }
+}
+
+#[cfg(not(unix))]
+fn is_alive(pid: u32) -> bool {
+    Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+        .unwrap_or(false)
+}
+
+/// Console processes ignore taskkill without /F, so a graceful stop only
+/// reaches apps with a window and the rest are killed after STOP_TIMEOUT
+#[cfg(not(unix))]
+pub(crate) fn signal(pid: u32, force: bool) {
+    let mut command = Command::new("taskkill");
+    command.args(["/T", "/PID", &pid.to_string()]);
+    if force {
+        command.arg("/F");
+    }
+    let _ = command.output();
+}
+
+/// Start `command` in a process group of its own, led by the child, so
+/// `signal` reaches everything it forks and Ctrl-C in the terminal doesn't
+pub(crate) fn new_process_group(command: &mut Command) {
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(command, 0);
+    #[cfg(windows)]
+    {
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+        std::os::windows::process::CommandExt::creation_flags(command, CREATE_NEW_PROCESS_GROUP);
+    }
+}
+
+/// Stop `child` and the process group it leads, killing it when it doesn't
+/// exit within STOP_TIMEOUT
+pub(crate) fn stop_child(child: &mut Child) -> Result<()> {
+    if child.try_wait()?.is_some() {
+        return Ok(());
+    }
+    signal(child.id(), false);
+    let start = Instant::now();
+    while child.try_wait()?.is_none() {
+        if start.elapsed() > STOP_TIMEOUT {
+            signal(child.id(), true);
+            child.wait()?;
+            break;
+        }
+        thread::sleep(Duration::from_millis(200));
+    }
+    Ok(())
+}
+
+/// Pid of the detached app if it is still running, removing a stale pidfile
+fn running_pid(config: &ProjectConfig) -> Option<u32> {
+    let path = pid_path(config);
+    let pid = fs::read_to_string(&path).ok()?.trim().parse().ok()?;
+    if is_alive(pid) {
+        Some(pid)
+    } else {
+        let _ = fs::remove_file(&path);
+        None
+    }
+}
+
+fn spawn_detached(config: &ProjectConfig, mut command: Command) -> Result<Child> {
+    if let Some(pid) = running_pid(config) {
+        return Err(color_eyre::eyre::eyre!(
+            "{} is already running (pid {}), use `spring-init stop` first",
+            config.app_name,
+            pid
+        ));
+    }
+
+    fs::create_dir_all(state_dir(config))?;
+    let log = File::create(log_path(config))?;
+    command
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log);
+    // Keep Ctrl-C in the terminal from reaching the app
+    new_process_group(&mut command);
+
+    let child = command.spawn()?;
+    fs::write(pid_path(config), child.id().to_string())?;
+    Ok(child)
+}
+
+/// Port from the embedded server's startup line, e.g.
+/// "Tomcat started on port 8080 (http) with context path '/'"
+fn port_from_log(log: &str) -> Option<u16> {
+    log.lines().rev().find_map(|line| {
+        let rest = &line[line.find("started on port")? + "started on port".len()..];
+        let digits: String = rest
+            .trim_start_matches([' ', '(', 's', ')'])
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        digits.parse().ok()
+    })
+}
+
+/// Context path from the same startup line, without a trailing slash, so
+/// empty for the root
+fn context_path_from_log(log: &str) -> String {
+    const MARKER: &str = "context path '";
+    log.lines()
+        .rev()
+        .filter(|line| line.contains("started on port"))
+        .find_map(|line| {
+            let rest = &line[line.find(MARKER)? + MARKER.len()..];
+            rest.split('\'').next()
+        })
+        .map(|path| path.trim_end_matches('/').to_string())
+        .unwrap_or_default()
+}
+
+fn has_actuator(config: &ProjectConfig) -> Result<bool> {
+    let build_file = config.build_file();
+    let content = fs::read_to_string(&build_file).map_err(|_| {
+        color_eyre::eyre::eyre!("No {} found, run init first", build_file.display())
+    })?;
+    Ok(content.contains("spring-boot-starter-actuator"))
+}
+
+/// Poll the detached app's health endpoint, on the port from its log, until
+/// it reports UP. The app is stopped when it doesn't within `timeout`.
+async fn wait_healthy(
+    config: &ProjectConfig,
+    child: &mut Child,
+    timeout: Duration,
+    debug: Option<u16>,
+    output: OutputFormat,
+) -> Result<()> {
+    // Straight to localhost, never through the configured proxy
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .timeout(Duration::from_secs(2))
+        .build()?;
+    let start = Instant::now();
+    eprintln!(
+        "Started {} in the background (pid {}), waiting for it to become healthy...",
+        config.app_name,
+        child.id()
+    );
+    loop {
+        if child.try_wait()?.is_some() {
+            let _ = fs::remove_file(pid_path(config));
+            return Err(color_eyre::eyre::eyre!(
+                "{} exited before becoming healthy, see {}",
+                config.app_name,
+                log_path(config).display()
+            ));
+        }
+        let log = fs::read_to_string(log_path(config)).unwrap_or_default();
+        if let Some(port) = port_from_log(&log) {
+            let base = format!("http://localhost:{}{}", port, context_path_from_log(&log));
+            let health = format!("{}{}", base, HEALTH_PATH);
+            // DOWN comes with a 503, so only the body tells
+            let status = match client.get(&health).send().await {
+                Ok(response) => response
+                    .json::<serde_json::Value>()
+                    .await
+                    .ok()
+                    .and_then(|body| body["status"].as_str().map(String::from)),
+                Err(_) => None,
+            };
+            if status.as_deref() == Some("UP") {
+                if output.is_json() {
+                    return output::print_json(&RunInfo {
+                        app_name: config.app_name.clone(),
+                        port: Some(port),
+                        pid: Some(child.id()),
+                        health: Some(health),
+                        debug_port: debug,
+                        log: Some(log_path(config).display().to_string()),
+                    });
+                }
+                println!(
+                    "{} is healthy on port {} after {}s",
+                    config.app_name,
+                    port,
+                    start.elapsed().as_secs()
+                );
+                println!("          APP: {}/", base);
+                println!("       HEALTH: {}", health);
+                if fs::read_to_string(config.build_file())
+                    .unwrap_or_default()
+                    .contains("springdoc-openapi")
+                {
+                    println!("   SWAGGER UI: {}/swagger-ui.html", base);
+                }
+                if let Some(debug) = debug {
+                    println!("     DEBUGGER: localhost:{}", debug);
+                }
+                println!("         LOGS: {}", log_path(config).display());
+                return Ok(());
+            }
+        }
+        if start.elapsed() > timeout {
+            eprintln!(
+                "{} not healthy after {}s",
+                config.app_name,
+                timeout.as_secs()
+            );
+            stop_child(child)?;
+            let _ = fs::remove_file(pid_path(config));
+            return Err(color_eyre::eyre::eyre!(
+                "{} did not become healthy within {}s, see {}",
+                config.app_name,
+                timeout.as_secs(),
+                log_path(config).display()
+            ));
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
+
+/// Report whether the detached app is running and on which port
+pub fn status(config: &ProjectConfig) -> Result<()> {
+    let Some(pid) = running_pid(config) else {
+        println!("{} is not running", config.app_name);
+        return Ok(());
+    };
+    let log = fs::read_to_string(log_path(config)).unwrap_or_default();
+    match port_from_log(&log) {
+        Some(port) => println!(
+            "{} is running (pid {}) on port {}",
+            config.app_name, pid, port
+        ),
+        None => println!(
+            "{} is running (pid {}), not listening on a port yet",
+            config.app_name, pid
+        ),
+    }
+    Ok(())
+}
+
+/// Stop the detached app with SIGTERM, then SIGKILL if it doesn't exit
+pub fn stop(config: &ProjectConfig) -> Result<()> {
+    let Some(pid) = running_pid(config) else {
+        println!("{} is not running", config.app_name);
+        return Ok(());
+    };
+
+    println!("Stopping {} (pid {})...", config.app_name, pid);
+    signal(pid, false);
+    let start = Instant::now();
+    while is_alive(pid) && start.elapsed() < STOP_TIMEOUT {
+        thread::sleep(Duration::from_millis(200));
+    }
+    if is_alive(pid) {
+        eprintln!(
+            "Still running after {}s, killing it",
+            STOP_TIMEOUT.as_secs()
+        );
+        signal(pid, true);
+    }
+
+    fs::remove_file(pid_path(config))?;
+    println!("Stopped {}", config.app_name);
+    Ok(())
+}
+
+/// Print the detached app's log, following new output if `follow` is set
+pub fn logs(config: &ProjectConfig, follow: bool, filter: LogFilter) -> Result<()> {
+    let path = log_path(config);
+    let mut file = File::open(&path).map_err(|_| {
+        color_eyre::eyre::eyre!(
+            "No log at {}, start the app with `run --detach`",
+            path.display()
+        )
+    })?;
+
+    let mut stdout = std::io::stdout();
+    let color = stdout.is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    let mut printer = Printer::new(filter, color);
+    let mut buffer = Vec::new();
+    loop {
+        buffer.clear();
+        file.read_to_end(&mut buffer)?;
+        printer.print(&mut stdout, &buffer)?;
+        if !follow {
+            printer.finish(&mut stdout)?;
+            return Ok(());
+        }
+        stdout.flush()?;
+        thread::sleep(Duration::from_millis(500));
+        // Start over when the log was recreated by a new run
+        if fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0) < file.stream_position()? {
+            file.seek(SeekFrom::Start(0))?;
+            printer.reset();
+        }
+    }
+}
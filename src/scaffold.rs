@@ -0,0 +1,612 @@
+use crate::layout::Layout;
+use crate::project::Language;
+use crate::{import, ProjectConfig};
+use color_eyre::eyre::Result;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+
+/// Starters the generated code compiles against, as `group:artifact` and ID
+const REQUIRED: &[(&str, &str)] = &[
+    (
+        "org.springframework.boot:spring-boot-starter-data-jpa",
+        "data-jpa",
+    ),
+    ("org.springframework.boot:spring-boot-starter-web", "web"),
+];
+const VALIDATION: &str = "org.springframework.boot:spring-boot-starter-validation";
+
+/// Field types accepted in `--fields`, with the import they need
+const TYPES: &[(&str, Option<&str>)] = &[
+    ("String", None),
+    ("Integer", None),
+    ("int", None),
+    ("Long", None),
+    ("long", None),
+    ("Boolean", None),
+    ("boolean", None),
+    ("Double", None),
+    ("double", None),
+    ("BigDecimal", Some("java.math.BigDecimal")),
+    ("Instant", Some("java.time.Instant")),
+    ("LocalDate", Some("java.time.LocalDate")),
+    ("LocalDateTime", Some("java.time.LocalDateTime")),
+    ("UUID", Some("java.util.UUID")),
+];
+
+/// Built-in templates as name, package key, file name suffix, and whether
+/// they render into the test sources
+const TEMPLATES: &[(&str, &str, &str, bool)] = &[
+    ("entity", "model", "", false),
+    ("repository", "repository", "Repository", false),
+    ("request", "dto", "Request", false),
+    ("response", "dto", "Response", false),
+    ("service", "service", "Service", false),
+    ("controller", "controller", "Controller", false),
+    ("service_test", "service", "ServiceTest", true),
+    ("controller_test", "controller", "ControllerTest", true),
+];
+
+const ENTITY: &str = r#"package {{ packages.model }};
+
+import jakarta.persistence.Entity;
+import jakarta.persistence.GeneratedValue;
+import jakarta.persistence.GenerationType;
+import jakarta.persistence.Id;
+import jakarta.persistence.Table;
+{% for import in imports -%}
+import {{ import }};
+{% endfor -%}
+
+@Entity
+@Table(name = "{{ table }}")
+public class {{ entity }} {
+
+    @Id
+    @GeneratedValue(strategy = GenerationType.IDENTITY)
+    private Long id;
+{% for field in fields %}
+    private {{ field.type }} {{ field.name }};
+{% endfor %}
+    protected {{ entity }}() {}
+
+    public {{ entity }}({% for field in fields %}{{ field.type }} {{ field.name }}{% if not loop.last %}, {% endif %}{% endfor %}) {
+{%- for field in fields %}
+        this.{{ field.name }} = {{ field.name }};
+{%- endfor %}
+    }
+
+    public Long getId() {
+        return id;
+    }
+{% for field in fields %}
+    public {{ field.type }} get{{ field.capitalized }}() {
+        return {{ field.name }};
+    }
+
+    public void set{{ field.capitalized }}({{ field.type }} {{ field.name }}) {
+        this.{{ field.name }} = {{ field.name }};
+    }
+{% endfor -%}
+}
+"#;
+
+const REPOSITORY: &str = r#"package {{ packages.repository }};
+
+{% if layered -%}
+import {{ packages.model }}.{{ entity }};
+{% endif -%}
+import org.springframework.data.jpa.repository.JpaRepository;
+
+public interface {{ entity }}Repository extends JpaRepository<{{ entity }}, Long> {}
+"#;
+
+const REQUEST: &str = r#"package {{ packages.dto }};
+
+{% if validation -%}
+import jakarta.validation.constraints.NotNull;
+{% endif -%}
+{% for import in imports -%}
+import {{ import }};
+{% endfor %}
+public record {{ entity }}Request(
+{%- for field in fields %}
+        {% if validation %}@NotNull {% endif %}{{ field.type }} {{ field.name }}{% if not loop.last %},{% endif %}
+{%- endfor %}) {}
+"#;
+
+const RESPONSE: &str = r#"package {{ packages.dto }};
+
+{% if layered -%}
+import {{ packages.model }}.{{ entity }};
+{% endif -%}
+{% for import in imports -%}
+import {{ import }};
+{% endfor %}
+public record {{ entity }}Response(
+        Long id{% for field in fields %},
+        {{ field.type }} {{ field.name }}{% endfor %}) {
+
+    public static {{ entity }}Response from({{ entity }} {{ variable }}) {
+        return new {{ entity }}Response(
+                {{ variable }}.getId(){% for field in fields %},
+                {{ variable }}.get{{ field.capitalized }}(){% endfor %});
+    }
+}
+"#;
+
+const SERVICE: &str = r#"package {{ packages.service }};
+
+{% if layered -%}
+import {{ packages.dto }}.{{ entity }}Request;
+import {{ packages.dto }}.{{ entity }}Response;
+import {{ packages.model }}.{{ entity }};
+import {{ packages.repository }}.{{ entity }}Repository;
+{% endif -%}
+import java.util.List;
+import java.util.Optional;
+import org.springframework.stereotype.Service;
+import org.springframework.transaction.annotation.Transactional;
+
+@Service
+@Transactional
+public class {{ entity }}Service {
+
+    private final {{ entity }}Repository repository;
+
+    public {{ entity }}Service({{ entity }}Repository repository) {
+        this.repository = repository;
+    }
+
+    @Transactional(readOnly = true)
+    public List<{{ entity }}Response> findAll() {
+        return repository.findAll().stream().map({{ entity }}Response::from).toList();
+    }
+
+    @Transactional(readOnly = true)
+    public Optional<{{ entity }}Response> findById(Long id) {
+        return repository.findById(id).map({{ entity }}Response::from);
+    }
+
+    public {{ entity }}Response create({{ entity }}Request request) {
+        {{ entity }} {{ variable }} = new {{ entity }}({% for field in fields %}request.{{ field.name }}(){% if not loop.last %}, {% endif %}{% endfor %});
+        return {{ entity }}Response.from(repository.save({{ variable }}));
+    }
+
+    public Optional<{{ entity }}Response> update(Long id, {{ entity }}Request request) {
+        return repository
+                .findById(id)
+                .map(
+                        {{ variable }} -> {
+{%- for field in fields %}
+                            {{ variable }}.set{{ field.capitalized }}(request.{{ field.name }}());
+{%- endfor %}
+                            return {{ entity }}Response.from({{ variable }});
+                        });
+    }
+
+    public boolean delete(Long id) {
+        if (!repository.existsById(id)) {
+            return false;
+        }
+        repository.deleteById(id);
+        return true;
+    }
+}
+"#;
+
+const CONTROLLER: &str = r#"package {{ packages.controller }};
+
+{% if layered -%}
+import {{ packages.dto }}.{{ entity }}Request;
+import {{ packages.dto }}.{{ entity }}Response;
+import {{ packages.service }}.{{ entity }}Service;
+{% endif -%}
+{% if validation -%}
+import jakarta.validation.Valid;
+{% endif -%}
+import java.net.URI;
+import java.util.List;
+import org.springframework.http.ResponseEntity;
+import org.springframework.web.bind.annotation.DeleteMapping;
+import org.springframework.web.bind.annotation.GetMapping;
+import org.springframework.web.bind.annotation.PathVariable;
+import org.springframework.web.bind.annotation.PostMapping;
+import org.springframework.web.bind.annotation.PutMapping;
+import org.springframework.web.bind.annotation.RequestBody;
+import org.springframework.web.bind.annotation.RequestMapping;
+import org.springframework.web.bind.annotation.RestController;
+
+@RestController
+@RequestMapping("/api/{{ path }}")
+public class {{ entity }}Controller {
+
+    private final {{ entity }}Service service;
+
+    public {{ entity }}Controller({{ entity }}Service service) {
+        this.service = service;
+    }
+
+    @GetMapping
+    public List<{{ entity }}Response> findAll() {
+        return service.findAll();
+    }
+
+    @GetMapping("/{id}")
+    public ResponseEntity<{{ entity }}Response> findById(@PathVariable Long id) {
+        return ResponseEntity.of(service.findById(id));
+    }
+
+    @PostMapping
+    public ResponseEntity<{{ entity }}Response> create(
+            {% if validation %}@Valid {% endif %}@RequestBody {{ entity }}Request request) {
+        {{ entity }}Response created = service.create(request);
+        URI location = URI.create("/api/{{ path }}/" + created.id());
+        return ResponseEntity.created(location).body(created);
+    }
+
+    @PutMapping("/{id}")
+    public ResponseEntity<{{ entity }}Response> update(
+            @PathVariable Long id, {% if validation %}@Valid {% endif %}@RequestBody {{ entity }}Request request) {
+        return ResponseEntity.of(service.update(id, request));
+    }
+
+    @DeleteMapping("/{id}")
+    public ResponseEntity<Void> delete(@PathVariable Long id) {
+        if (!service.delete(id)) {
+            return ResponseEntity.notFound().build();
+        }
+        return ResponseEntity.noContent().build();
+    }
+}
+"#;
+
+const SERVICE_TEST: &str = r#"package {{ packages.service }};
+
+import static org.assertj.core.api.Assertions.assertThat;
+import static org.mockito.ArgumentMatchers.any;
+import static org.mockito.Mockito.when;
+
+{% if layered -%}
+import {{ packages.dto }}.{{ entity }}Request;
+import {{ packages.dto }}.{{ entity }}Response;
+import {{ packages.model }}.{{ entity }};
+import {{ packages.repository }}.{{ entity }}Repository;
+{% endif -%}
+{% for import in test_imports -%}
+import {{ import }};
+{% endfor -%}
+import org.junit.jupiter.api.Test;
+import org.junit.jupiter.api.extension.ExtendWith;
+import org.mockito.InjectMocks;
+import org.mockito.Mock;
+import org.mockito.junit.jupiter.MockitoExtension;
+
+@ExtendWith(MockitoExtension.class)
+class {{ entity }}ServiceTest {
+
+    @Mock
+    private {{ entity }}Repository repository;
+
+    @InjectMocks
+    private {{ entity }}Service service;
+
+    @Test
+    void createSavesThe{{ entity }}() {
+        when(repository.save(any({{ entity }}.class)))
+                .thenAnswer(invocation -> invocation.getArgument(0));
+
+        {{ entity }}Request request =
+                new {{ entity }}Request({% for field in fields %}{{ field.sample }}{% if not loop.last %}, {% endif %}{% endfor %});
+
+        {{ entity }}Response response = service.create(request);
+
+        assertThat(response.{{ fields[0].name }}()).isEqualTo({{ fields[0].sample }});
+    }
+
+    @Test
+    void findByIdIsEmptyForAnUnknownId() {
+        when(repository.findById(1L)).thenReturn(Optional.empty());
+
+        assertThat(service.findById(1L)).isEmpty();
+    }
+}
+"#;
+
+const CONTROLLER_TEST: &str = r#"package {{ packages.controller }};
+
+import static org.mockito.Mockito.mock;
+import static org.mockito.Mockito.when;
+import static org.springframework.test.web.servlet.request.MockMvcRequestBuilders.get;
+import static org.springframework.test.web.servlet.result.MockMvcResultMatchers.status;
+
+{% if layered -%}
+import {{ packages.service }}.{{ entity }}Service;
+{% endif -%}
+import java.util.List;
+import java.util.Optional;
+import org.junit.jupiter.api.BeforeEach;
+import org.junit.jupiter.api.Test;
+import org.springframework.test.web.servlet.MockMvc;
+import org.springframework.test.web.servlet.setup.MockMvcBuilders;
+
+class {{ entity }}ControllerTest {
+
+    private final {{ entity }}Service service = mock({{ entity }}Service.class);
+
+    private MockMvc mockMvc;
+
+    @BeforeEach
+    void setUp() {
+        mockMvc = MockMvcBuilders.standaloneSetup(new {{ entity }}Controller(service)).build();
+    }
+
+    @Test
+    void findAllReturnsOk() throws Exception {
+        when(service.findAll()).thenReturn(List.of());
+
+        mockMvc.perform(get("/api/{{ path }}")).andExpect(status().isOk());
+    }
+
+    @Test
+    void findByIdReturnsNotFoundForAnUnknownId() throws Exception {
+        when(service.findById(1L)).thenReturn(Optional.empty());
+
+        mockMvc.perform(get("/api/{{ path }}/1")).andExpect(status().isNotFound());
+    }
+}
+"#;
+
+fn template(name: &str) -> &'static str {
+    match name {
+        "entity" => ENTITY,
+        "repository" => REPOSITORY,
+        "request" => REQUEST,
+        "response" => RESPONSE,
+        "service" => SERVICE,
+        "controller" => CONTROLLER,
+        "service_test" => SERVICE_TEST,
+        "controller_test" => CONTROLLER_TEST,
+        _ => unreachable!("unknown template {}", name),
+    }
+}
+
+#[derive(Serialize)]
+struct Field {
+    name: String,
+    #[serde(rename = "type")]
+    java_type: String,
+    /// For getters and setters, e.g. `CreatedAt`
+    capitalized: String,
+    /// Java expression of a value for the tests
+    sample: String,
+}
+
+fn sample(java_type: &str, name: &str) -> String {
+    match java_type {
+        "String" => format!("\"{}\"", name),
+        "Integer" | "int" => "1".to_string(),
+        "Long" | "long" => "1L".to_string(),
+        "Boolean" | "boolean" => "true".to_string(),
+        "Double" | "double" => "1.0".to_string(),
+        "BigDecimal" => "BigDecimal.ONE".to_string(),
+        "Instant" => "Instant.EPOCH".to_string(),
+        "LocalDate" => "LocalDate.EPOCH".to_string(),
+        "LocalDateTime" => "LocalDateTime.of(2024, 1, 1, 0, 0)".to_string(),
+        "UUID" => "new UUID(0, 1)".to_string(),
+        _ => unreachable!(),
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Parse `name:Type,name:Type`
+fn parse_fields(fields: &str) -> Result<Vec<Field>> {
+    let fields: Vec<Field> = fields
+        .split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .map(|field| {
+            let (name, java_type) = field
+                .split_once(':')
+                .map(|(name, java_type)| (name.trim(), java_type.trim()))
+                .ok_or_else(|| {
+                    color_eyre::eyre::eyre!("Invalid field {:?}, expected name:Type", field)
+                })?;
+            if !is_identifier(name) || name == "id" {
+                return Err(color_eyre::eyre::eyre!("Invalid field name {:?}", name));
+            }
+            if !TYPES.iter().any(|(known, _)| *known == java_type) {
+                let known: Vec<&str> = TYPES.iter().map(|(known, _)| *known).collect();
+                return Err(color_eyre::eyre::eyre!(
+                    "Unsupported type {} for {}, use one of: {}",
+                    java_type,
+                    name,
+                    known.join(", ")
+                ));
+            }
+            let mut chars = name.chars();
+            let capitalized = chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default();
+            Ok(Field {
+                name: name.to_string(),
+                java_type: java_type.to_string(),
+                capitalized,
+                sample: sample(java_type, name),
+            })
+        })
+        .collect::<Result<_>>()?;
+    if fields.is_empty() {
+        return Err(color_eyre::eyre::eyre!("No fields given"));
+    }
+    Ok(fields)
+}
+
+/// `CustomerOrder` as `customer_order`
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+    snake
+}
+
+fn plural(word: &str) -> String {
+    let consonant_y = word
+        .strip_suffix('y')
+        .filter(|stem| !stem.ends_with(['a', 'e', 'i', 'o', 'u']));
+    if let Some(stem) = consonant_y {
+        format!("{}ies", stem)
+    } else if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|suffix| word.ends_with(suffix))
+    {
+        format!("{}es", word)
+    } else {
+        format!("{}s", word)
+    }
+}
+
+/// Render the CRUD stack for `entity` from the built-in templates: a JPA
+/// entity, repository, request and response records, service, REST
+/// controller, and unit tests for the service and controller
+pub fn crud(
+    config: &ProjectConfig,
+    entity: &str,
+    fields: &str,
+    force: bool,
+    dry_run: bool,
+) -> Result<()> {
+    if config.language != Language::Java {
+        return Err(color_eyre::eyre::eyre!(
+            "scaffold crud only generates Java sources"
+        ));
+    }
+    let starts_upper = entity
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_uppercase());
+    if !is_identifier(entity) || !starts_upper {
+        return Err(color_eyre::eyre::eyre!(
+            "Invalid entity name {:?}, use a class name like Customer",
+            entity
+        ));
+    }
+    let fields = parse_fields(fields)?;
+
+    let dependencies =
+        import::read_build_settings(config.build_tool, &config.module_dir())?.dependencies;
+    let missing: Vec<&str> = REQUIRED
+        .iter()
+        .filter(|(coordinates, _)| {
+            !dependencies
+                .iter()
+                .any(|dependency| dependency == coordinates)
+        })
+        .map(|(_, id)| *id)
+        .collect();
+    if !missing.is_empty() {
+        return Err(color_eyre::eyre::eyre!(
+            "scaffold crud needs {}, add it with `spring-init add-dep {}`",
+            missing.join(" and "),
+            missing.join(" ")
+        ));
+    }
+    let validation = dependencies
+        .iter()
+        .any(|dependency| dependency == VALIDATION);
+
+    // Follow the layered layout's packages, otherwise keep the feature together
+    let snake = snake_case(entity);
+    let base = &config.package_name;
+    let layered = config.layout == Some(Layout::Layered);
+    let package = |layer: &str| {
+        if layered {
+            format!("{}.{}", base, layer)
+        } else {
+            format!("{}.{}", base, snake.replace('_', ""))
+        }
+    };
+    let packages: serde_json::Map<String, serde_json::Value> =
+        ["model", "repository", "dto", "service", "controller"]
+            .iter()
+            .map(|layer| (layer.to_string(), package(layer).into()))
+            .collect();
+    let imports: BTreeSet<&str> = fields
+        .iter()
+        .filter_map(|field| {
+            TYPES
+                .iter()
+                .find(|(known, _)| *known == field.java_type)
+                .and_then(|(_, import)| *import)
+        })
+        .collect();
+
+    let mut context = tera::Context::new();
+    context.insert("entity", entity);
+    context.insert(
+        "variable",
+        &(entity[..1].to_ascii_lowercase() + &entity[1..]),
+    );
+    context.insert("path", &plural(&snake.replace('_', "-")));
+    context.insert("table", &plural(&snake));
+    context.insert("packages", &packages);
+    context.insert("fields", &fields);
+    context.insert("imports", &imports);
+    let mut test_imports = imports.clone();
+    test_imports.insert("java.util.Optional");
+    context.insert("test_imports", &test_imports);
+    context.insert("layered", &layered);
+    context.insert("validation", &validation);
+
+    let mut tera = tera::Tera::new();
+    tera.autoescape_on(Vec::<&str>::new());
+    let mut files: Vec<(PathBuf, String)> = Vec::new();
+    for (name, layer, suffix, test) in TEMPLATES {
+        tera.add_raw_template(name, template(name))
+            .map_err(|e| color_eyre::eyre::eyre!("Invalid template {}: {}", name, e))?;
+        let content = tera
+            .render(name, &context)
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to render {}: {}", name, e))?;
+        let root = config
+            .module_dir()
+            .join("src")
+            .join(if *test { "test" } else { "main" })
+            .join("java");
+        let dir = package(layer)
+            .split('.')
+            .fold(root, |dir, segment| dir.join(segment));
+        files.push((dir.join(format!("{}{}.java", entity, suffix)), content));
+    }
+
+    if dry_run {
+        for (path, _) in &files {
+            println!("Would write {}", path.display());
+        }
+        return Ok(());
+    }
+    if let Some((path, _)) = files.iter().find(|(path, _)| path.exists() && !force) {
+        return Err(color_eyre::eyre::eyre!(
+            "{} already exists, use --force to overwrite it",
+            path.display()
+        ));
+    }
+    for (path, content) in &files {
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, content)?;
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
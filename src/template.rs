@@ -0,0 +1,260 @@
+use crate::{plugins, pom, ProjectConfig};
+use color_eyre::eyre::Result;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Manifest at the root of a template directory
+const MANIFEST: &str = "template.toml";
+/// Directory holding the files copied into the project
+const FILES: &str = "files";
+/// Files with this suffix are rendered with Tera, the rest are copied as is
+const SUFFIX: &str = ".tera";
+
+/// A variable the template's files use, prompted for at init time unless
+/// given with `--var`
+#[derive(Deserialize)]
+pub struct Variable {
+    pub name: String,
+    /// Prompt shown for the variable, defaults to its name
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// Value used without prompting when there's no terminal, the variable
+    /// is required without one
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+/// What a template adds to the project besides its files
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct Manifest {
+    pub description: Option<String>,
+    /// Initializr dependency IDs added to the selected ones
+    pub dependencies: Vec<String>,
+    /// Maven plugin coordinates added to maven_plugins
+    pub maven_plugins: Vec<String>,
+    /// Added to maven_dependencies, as `group:artifact[:version][@scope]`
+    pub maven_dependencies: Vec<String>,
+    /// Added to maven_boms, as `group:artifact:version`
+    pub maven_boms: Vec<String>,
+    /// Properties set in pom.xml, values are rendered like the files
+    pub properties: BTreeMap<String, String>,
+    pub variables: Vec<Variable>,
+}
+
+/// A template directory registered under `templates`
+pub struct Template {
+    pub name: String,
+    pub dir: PathBuf,
+    pub manifest: Manifest,
+}
+
+impl Template {
+    /// Load the template registered as `name`
+    pub fn load(config: &ProjectConfig, name: &str) -> Result<Self> {
+        let dir = config.templates.get(name).ok_or_else(|| {
+            let known: Vec<&str> = config.templates.keys().map(String::as_str).collect();
+            color_eyre::eyre::eyre!(
+                "Unknown template {}, registered templates: {}",
+                name,
+                if known.is_empty() {
+                    "none".to_string()
+                } else {
+                    known.join(", ")
+                }
+            )
+        })?;
+        let dir = PathBuf::from(dir);
+        let path = dir.join(MANIFEST);
+        let manifest = match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content)
+                .map_err(|e| color_eyre::eyre::eyre!("Invalid {}: {}", path.display(), e))?,
+            Err(_) if dir.is_dir() => Manifest::default(),
+            Err(_) => {
+                return Err(color_eyre::eyre::eyre!(
+                    "Template directory {} not found",
+                    dir.display()
+                ))
+            }
+        };
+        Ok(Template {
+            name: name.to_string(),
+            dir,
+            manifest,
+        })
+    }
+
+    /// Add the manifest's plugins, dependencies, and BOMs to the config so
+    /// init syncs them along with the configured ones
+    pub fn configure(&self, config: &mut ProjectConfig) {
+        config.maven_plugins.extend(
+            self.manifest
+                .maven_plugins
+                .iter()
+                .cloned()
+                .map(plugins::MavenPlugin::Coordinates),
+        );
+        config
+            .maven_dependencies
+            .extend(self.manifest.maven_dependencies.iter().cloned());
+        config
+            .maven_boms
+            .extend(self.manifest.maven_boms.iter().cloned());
+    }
+
+    /// Values for the template's variables, from `given` first, then a
+    /// prompt when `prompt` is set, then the default
+    pub fn variables(
+        &self,
+        given: &BTreeMap<String, String>,
+        prompt: bool,
+    ) -> Result<BTreeMap<String, String>> {
+        let mut values = given.clone();
+        for variable in &self.manifest.variables {
+            if values.contains_key(&variable.name) {
+                continue;
+            }
+            let value = if prompt {
+                let message = format!("{}:", variable.prompt.as_deref().unwrap_or(&variable.name));
+                let mut text = inquire::Text::new(&message);
+                if let Some(default) = &variable.default {
+                    text = text.with_default(default);
+                }
+                text.prompt()?
+            } else if let Some(default) = &variable.default {
+                default.clone()
+            } else {
+                return Err(color_eyre::eyre::eyre!(
+                    "Template {} needs a value for {}, pass --var {}=...",
+                    self.name,
+                    variable.name,
+                    variable.name
+                ));
+            };
+            values.insert(variable.name.clone(), value);
+        }
+        Ok(values)
+    }
+
+    /// Files under `files/` as their path relative to it
+    fn files(&self) -> Result<Vec<PathBuf>> {
+        fn walk(dir: &Path, root: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+            for entry in fs::read_dir(dir)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    walk(&path, root, files)?;
+                } else {
+                    files.push(path.strip_prefix(root)?.to_path_buf());
+                }
+            }
+            Ok(())
+        }
+
+        let root = self.dir.join(FILES);
+        let mut files = Vec::new();
+        if root.is_dir() {
+            walk(&root, &root, &mut files)?;
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    /// Write the template's files into the project, rendering paths and
+    /// `.tera` files with the project settings and `variables`, and set its
+    /// pom properties
+    pub fn apply(
+        &self,
+        config: &ProjectConfig,
+        variables: &BTreeMap<String, String>,
+        dry_run: bool,
+    ) -> Result<()> {
+        let mut context = tera::Context::new();
+        context.insert("app_name", &config.app_name);
+        context.insert("app_version", &config.version());
+        context.insert("package_name", &config.package_name);
+        context.insert("package_path", &config.package_name.replace('.', "/"));
+        context.insert("java_version", &config.java_version);
+        context.insert("boot_version", &config.boot_version);
+        context.insert("build_tool", config.build_tool.project_type());
+        context.insert("language", config.language.as_str());
+        for (name, value) in variables {
+            context.insert(name.clone(), value);
+        }
+
+        let mut tera = tera::Tera::new();
+        tera.autoescape_on(Vec::<&str>::new());
+        let mut render = |name: &str, source: &str| -> Result<String> {
+            tera.add_raw_template(name, source)
+                .map_err(|e| color_eyre::eyre::eyre!("Invalid template {}: {}", name, e))?;
+            tera.render(name, &context)
+                .map_err(|e| color_eyre::eyre::eyre!("Failed to render {}: {}", name, e))
+        };
+
+        let app_dir = config.app_dir();
+        for file in self.files()? {
+            let name = file.to_string_lossy().to_string();
+            let relative = render(&format!("{} (path)", name), &name)?;
+            let source = self.dir.join(FILES).join(&file);
+            let (relative, content) = match relative.strip_suffix(SUFFIX) {
+                Some(relative) => (
+                    relative.to_string(),
+                    render(&name, &fs::read_to_string(&source)?)?.into_bytes(),
+                ),
+                None => (relative, fs::read(&source)?),
+            };
+            let path = app_dir.join(relative);
+            if dry_run {
+                println!("Would write {}", path.display());
+                continue;
+            }
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(&path, content)?;
+        }
+
+        if self.manifest.properties.is_empty() {
+            return Ok(());
+        }
+        if config.build_tool.is_gradle() {
+            eprintln!(
+                "Skipping properties of template {} for Gradle project",
+                self.name
+            );
+            return Ok(());
+        }
+        if dry_run {
+            for name in self.manifest.properties.keys() {
+                println!("Would set property: {}", name);
+            }
+            return Ok(());
+        }
+        let pom_path = config.build_file();
+        let mut pom = pom::Pom::load(&pom_path)?;
+        let mut changed = false;
+        for (name, value) in &self.manifest.properties {
+            changed |= pom.set_property(name, &render(name, value)?)?;
+        }
+        if changed {
+            pom.save(&pom_path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Print the registered templates with their descriptions
+pub fn list(config: &ProjectConfig) -> Result<()> {
+    if config.templates.is_empty() {
+        println!("No templates, register a directory under templates in the config");
+        return Ok(());
+    }
+
+    for name in config.templates.keys() {
+        let description = match Template::load(config, name) {
+            Ok(template) => template.manifest.description.unwrap_or_default(),
+            Err(e) => format!("({})", e),
+        };
+        println!("{:<30} {}", name, description);
+    }
+    Ok(())
+}
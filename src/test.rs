@@ -0,0 +1,193 @@
+use crate::output::{self, OutputFormat, TestFailure, TestSummary};
+use crate::{jdk, progress, repositories, wrapper, ProjectConfig};
+use color_eyre::eyre::Result;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Lines of build output shown when tests didn't run at all
+const OUTPUT_TAIL: usize = 40;
+
+/// Results of one test class, read from its JUnit XML report
+pub struct SuiteReport {
+    pub class: String,
+    pub tests: usize,
+    pub skipped: usize,
+    pub time: f64,
+    pub failures: Vec<TestFailure>,
+}
+
+/// Report directory of each module
+fn reports_dirs(config: &ProjectConfig) -> Vec<PathBuf> {
+    let reports = if config.build_tool.is_gradle() {
+        "build/test-results/test"
+    } else {
+        "target/surefire-reports"
+    };
+    config
+        .module_dirs()
+        .into_iter()
+        .map(|dir| dir.join(reports))
+        .collect()
+}
+
+fn parse_report(content: &str) -> Option<SuiteReport> {
+    let document = roxmltree::Document::parse(content).ok()?;
+    let suite = document.root_element();
+    let class = suite.attribute("name")?.to_string();
+    let count = |name: &str| -> usize {
+        suite
+            .attribute(name)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0)
+    };
+
+    let failures = suite
+        .children()
+        .filter(|node| node.has_tag_name("testcase"))
+        .filter_map(|case| {
+            let problem = case
+                .children()
+                .find(|node| node.has_tag_name("failure") || node.has_tag_name("error"))?;
+            // The message attribute is empty for some exceptions, fall back to
+            // the first line of the stack trace
+            let message = problem
+                .attribute("message")
+                .filter(|message| !message.trim().is_empty())
+                .or_else(|| problem.text().and_then(|text| text.trim().lines().next()))
+                .or_else(|| problem.attribute("type"))
+                .unwrap_or_default();
+            Some(TestFailure {
+                class: case.attribute("classname").unwrap_or(&class).to_string(),
+                method: case.attribute("name").unwrap_or_default().to_string(),
+                message: message.trim().to_string(),
+            })
+        })
+        .collect();
+
+    Some(SuiteReport {
+        tests: count("tests"),
+        skipped: count("skipped"),
+        time: suite
+            .attribute("time")
+            .and_then(|value| value.replace(',', "").parse().ok())
+            .unwrap_or(0.0),
+        class,
+        failures,
+    })
+}
+
+/// Read every JUnit XML report the last test run left behind
+pub fn suite_reports(config: &ProjectConfig) -> Vec<SuiteReport> {
+    let mut reports: Vec<SuiteReport> = reports_dirs(config)
+        .into_iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with("TEST-") && name.ends_with(".xml")
+        })
+        .filter_map(|entry| parse_report(&fs::read_to_string(entry.path()).ok()?))
+        .collect();
+    reports.sort_by(|a, b| a.class.cmp(&b.class));
+    reports
+}
+
+/// Run the project's tests, or only those matching `filter`, and summarize
+/// the failures from the test reports instead of the raw build output
+pub fn run(
+    config: &ProjectConfig,
+    filter: Option<&str>,
+    quiet: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let program = wrapper::program(config);
+    let mut args = vec!["test".to_string()];
+    if let Some(filter) = filter {
+        if config.build_tool.is_gradle() {
+            // Gradle separates the method with a dot instead of surefire's #
+            args.extend(["--tests".to_string(), filter.replace('#', ".")]);
+        } else {
+            args.push(format!("-Dtest={}", filter));
+            // Report an empty selection below instead of failing the build
+            args.push("-Dsurefire.failIfNoSpecifiedTests=false".to_string());
+        }
+    }
+
+    // Reports of an earlier run would mix with this one's
+    for dir in reports_dirs(config) {
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    let spinner = progress::spinner("Running tests...", quiet || format.is_json())?;
+    let mut command = Command::new(&program);
+    command.args(&args).current_dir(config.app_dir());
+    jdk::configure(&mut command, config);
+    repositories::configure(&mut command, config);
+    let output = command
+        .output()
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to run {}: {}", program, e))?;
+    spinner.finish_and_clear();
+
+    let reports = suite_reports(config);
+    if reports.is_empty() {
+        if output.status.success() {
+            return Err(color_eyre::eyre::eyre!(
+                "No tests ran{}",
+                filter
+                    .map(|filter| format!(" matching {}", filter))
+                    .unwrap_or_default()
+            ));
+        }
+        // Usually a compile error, which only the build output explains
+        let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+        text.push_str(&String::from_utf8_lossy(&output.stderr));
+        let lines: Vec<&str> = text.lines().collect();
+        for line in &lines[lines.len().saturating_sub(OUTPUT_TAIL)..] {
+            eprintln!("{}", line);
+        }
+        return Err(color_eyre::eyre::eyre!(
+            "{} test failed before running tests",
+            program
+        ));
+    }
+
+    let summary = TestSummary {
+        tests: reports.iter().map(|report| report.tests).sum(),
+        failed: reports.iter().map(|report| report.failures.len()).sum(),
+        skipped: reports.iter().map(|report| report.skipped).sum(),
+        time: reports.iter().map(|report| report.time).sum(),
+        failures: reports
+            .into_iter()
+            .flat_map(|report| report.failures)
+            .collect(),
+    };
+    if format.is_json() {
+        output::print_json(&summary)?;
+    } else {
+        for failure in &summary.failures {
+            println!("FAILED {}.{}", failure.class, failure.method);
+            for line in failure.message.lines() {
+                println!("  {}", line);
+            }
+        }
+        if !summary.failures.is_empty() {
+            println!();
+        }
+        println!(
+            "{} tests, {} failed, {} skipped in {:.1}s",
+            summary.tests, summary.failed, summary.skipped, summary.time
+        );
+    }
+
+    if summary.failed > 0 {
+        return Err(color_eyre::eyre::eyre!(
+            "{} of {} tests failed",
+            summary.failed,
+            summary.tests
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use crate::ProjectConfig;
+use color_eyre::eyre::Result;
+use std::env;
+use std::fs;
+
+fn toolchain_entry(java_version: &str, jdk_home: &str) -> String {
+    format!(
+        "    <toolchain>
+        <type>jdk</type>
+        <provides>
+            <version>{}</version>
+        </provides>
+        <configuration>
+            <jdkHome>{}</jdkHome>
+        </configuration>
+    </toolchain>
+",
+        java_version, jdk_home
+    )
+}
+
+/// Remove every `<toolchain>` block that contains `version_tag`
+fn remove_toolchain(content: &str, version_tag: &str) -> String {
+    let mut result = String::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find("<toolchain>") {
+        let Some(len) = rest[start..].find("</toolchain>") else {
+            break;
+        };
+        let end = start + len + "</toolchain>".len();
+
+        result.push_str(&rest[..start]);
+        if rest[start..end].contains(version_tag) {
+            // Drop the block along with its indentation and line break
+            result.truncate(result.trim_end_matches([' ', '\t']).len());
+            rest = rest[end..].strip_prefix('\n').unwrap_or(&rest[end..]);
+        } else {
+            result.push_str(&rest[start..end]);
+            rest = &rest[end..];
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// toolchains.xml with an entry for `java_version`, added to `existing` or
+/// a new file. None when `existing` already has one and `replace` isn't set.
+fn toolchains_xml(
+    existing: Option<&str>,
+    java_version: &str,
+    jdk_home: &str,
+    replace: bool,
+) -> Result<Option<String>> {
+    let entry = toolchain_entry(java_version, jdk_home);
+    let Some(existing) = existing else {
+        return Ok(Some(format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<toolchains>\n{}</toolchains>\n",
+            entry
+        )));
+    };
+
+    let version_tag = format!("<version>{}</version>", java_version);
+    let mut content = existing.to_string();
+    if content.contains(&version_tag) {
+        if !replace {
+            return Ok(None);
+        }
+        content = remove_toolchain(&content, &version_tag);
+    }
+    let insert_pos = content.find("</toolchains>").ok_or_else(|| {
+        color_eyre::eyre::eyre!("Could not find </toolchains> tag in toolchains.xml")
+    })?;
+    content.insert_str(insert_pos, &entry);
+    Ok(Some(content))
+}
+
+/// Register the JDK for the configured Java version in ~/.m2/toolchains.xml,
+/// an existing entry for the version is only replaced when `replace` is set
+pub fn write_toolchains(
+    config: &ProjectConfig,
+    jdk_home: Option<&str>,
+    replace: bool,
+) -> Result<()> {
+    let jdk_home = match jdk_home {
+        Some(jdk_home) => jdk_home.to_string(),
+        None => env::var("JAVA_HOME").map_err(|_| {
+            color_eyre::eyre::eyre!("No JDK path given, pass --jdk-home or set JAVA_HOME")
+        })?,
+    };
+    let home = dirs::home_dir()
+        .ok_or_else(|| color_eyre::eyre::eyre!("Could not determine home directory"))?;
+    let toolchains_path = home.join(".m2").join("toolchains.xml");
+
+    let existing = if toolchains_path.exists() {
+        Some(fs::read_to_string(&toolchains_path)?)
+    } else {
+        None
+    };
+    let has_entry = existing.as_deref().is_some_and(|content| {
+        content.contains(&format!("<version>{}</version>", config.java_version))
+    });
+    let Some(content) = toolchains_xml(
+        existing.as_deref(),
+        &config.java_version,
+        &jdk_home,
+        replace,
+    )?
+    else {
+        eprintln!(
+            "Toolchain for Java {} already exists in {}, skipping (use --replace-toolchain to replace it)",
+            config.java_version,
+            toolchains_path.display()
+        );
+        return Ok(());
+    };
+    if has_entry {
+        eprintln!(
+            "Warning: replacing the Java {} toolchain in {}",
+            config.java_version,
+            toolchains_path.display()
+        );
+    }
+
+    fs::create_dir_all(toolchains_path.parent().unwrap())?;
+    fs::write(&toolchains_path, content)?;
+    eprintln!(
+        "Registered Java {} toolchain in {}",
+        config.java_version,
+        toolchains_path.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_file_has_the_java_version() {
+        let xml = toolchains_xml(None, "21", "/usr/lib/jvm/java-21", false)
+            .unwrap()
+            .unwrap();
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.contains("<type>jdk</type>"));
+        assert!(xml.contains("<version>21</version>"));
+        assert!(xml.contains("<jdkHome>/usr/lib/jvm/java-21</jdkHome>"));
+        assert!(xml.trim_end().ends_with("</toolchains>"));
+    }
+
+    #[test]
+    fn existing_entry_is_kept_unless_replaced() {
+        let existing = toolchains_xml(None, "17", "/opt/jdk-17", false)
+            .unwrap()
+            .unwrap();
+        let existing = toolchains_xml(Some(&existing), "21", "/opt/jdk-21", false)
+            .unwrap()
+            .unwrap();
+        assert!(existing.contains("<version>17</version>"));
+        assert!(existing.contains("<version>21</version>"));
+
+        assert!(
+            toolchains_xml(Some(&existing), "21", "/opt/other-21", false)
+                .unwrap()
+                .is_none()
+        );
+
+        let replaced = toolchains_xml(Some(&existing), "21", "/opt/other-21", true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(replaced.matches("<version>21</version>").count(), 1);
+        assert!(replaced.contains("<jdkHome>/opt/other-21</jdkHome>"));
+        assert!(!replaced.contains("/opt/jdk-21"));
+        assert!(replaced.contains("<jdkHome>/opt/jdk-17</jdkHome>"));
+    }
+}
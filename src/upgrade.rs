@@ -0,0 +1,240 @@
+use crate::pom::{self, Pom};
+use crate::projects::Registry;
+use crate::{
+    cache, gradle, import, lock, project, versions, wrapper, IncompatiblePolicy, ProjectConfig,
+};
+use color_eyre::eyre::Result;
+use std::fs;
+use std::process::Command;
+
+/// OpenRewrite plugin and recipe artifact wired in by `upgrade --openrewrite`
+const REWRITE_PLUGIN: &str = "org.openrewrite.maven:rewrite-maven-plugin:5.46.0";
+const REWRITE_SPRING: &str = "org.openrewrite.recipe:rewrite-spring:5.24.0";
+
+/// OpenRewrite recipe migrating code and configuration to `version`'s
+/// major.minor line, e.g. UpgradeSpringBoot_3_4
+fn recipe(version: &str) -> Result<String> {
+    let mut parts = version.split('.');
+    match (parts.next(), parts.next()) {
+        (Some(major), Some(minor)) => Ok(format!(
+            "org.openrewrite.java.spring.boot{major}.UpgradeSpringBoot_{major}_{minor}"
+        )),
+        _ => Err(color_eyre::eyre::eyre!(
+            "Can't pick an OpenRewrite recipe for Spring Boot {}",
+            version
+        )),
+    }
+}
+
+/// Declare the OpenRewrite plugin with the recipe for `version` in the root pom
+fn configure_openrewrite(config: &ProjectConfig, version: &str) -> Result<()> {
+    let path = config.app_dir().join("pom.xml");
+    let mut pom = Pom::load(&path)?;
+    let plugin = pom::Coordinates::parse(REWRITE_PLUGIN)?;
+    let recipes = pom::Coordinates::parse(REWRITE_SPRING)?;
+    pom.upsert_plugin(&plugin)?;
+    let mut set = |path: &[&str], value: &str| {
+        pom.set_plugin_value(&plugin.group_id, &plugin.artifact_id, path, value)
+    };
+    set(
+        &["configuration", "activeRecipes", "recipe"],
+        &recipe(version)?,
+    )?;
+    set(
+        &["dependencies", "dependency", "groupId"],
+        &recipes.group_id,
+    )?;
+    set(
+        &["dependencies", "dependency", "artifactId"],
+        &recipes.artifact_id,
+    )?;
+    set(
+        &["dependencies", "dependency", "version"],
+        recipes.version.as_deref().unwrap_or_default(),
+    )?;
+    pom.save(&path)?;
+    eprintln!("Configured {} with {}", REWRITE_PLUGIN, recipe(version)?);
+    Ok(())
+}
+
+fn run_openrewrite(config: &ProjectConfig) -> Result<()> {
+    println!("Running OpenRewrite...");
+    let program = wrapper::maven(config);
+    let status = Command::new(&program)
+        .arg("rewrite:run")
+        .current_dir(config.app_dir())
+        .status()
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to run {}: {}", program, e))?;
+    if !status.success() {
+        return Err(color_eyre::eyre::eyre!(
+            "OpenRewrite failed, see the output above"
+        ));
+    }
+    Ok(())
+}
+
+/// Point the Boot parent, BOM, or Gradle plugin at `version`
+fn set_boot_version(config: &ProjectConfig, version: &str) -> Result<()> {
+    if config.build_tool.is_gradle() {
+        let path = config.build_file();
+        let content = fs::read_to_string(&path)?;
+        let updated = gradle::set_plugin_version(&content, "org.springframework.boot", version)
+            .ok_or_else(|| {
+                color_eyre::eyre::eyre!(
+                    "Could not find the org.springframework.boot plugin version in {}",
+                    path.display()
+                )
+            })?;
+        fs::write(&path, updated)?;
+        return Ok(());
+    }
+
+    // Modules inherit the version from the aggregator
+    let path = config.app_dir().join("pom.xml");
+    let mut pom = Pom::load(&path)?;
+    if pom.text(&["parent", "artifactId"])?.as_deref() == Some("spring-boot-starter-parent") {
+        pom.set_text(&["parent", "version"], version)?;
+    } else {
+        let bom = pom
+            .coordinates(&["dependencyManagement", "dependencies"], "dependency")?
+            .into_iter()
+            .find(|bom| bom.artifact_id == "spring-boot-dependencies")
+            .ok_or_else(|| {
+                color_eyre::eyre::eyre!(
+                    "{} has neither the Spring Boot parent nor the spring-boot-dependencies BOM",
+                    path.display()
+                )
+            })?;
+        let property = bom.version.as_deref().and_then(|version| {
+            version
+                .strip_prefix("${")
+                .and_then(|rest| rest.strip_suffix('}'))
+        });
+        match property {
+            Some(property) => {
+                pom.set_property(property, version)?;
+            }
+            None => {
+                pom.set_dependency_version(
+                    &["dependencyManagement", "dependencies"],
+                    &bom.group_id,
+                    &bom.artifact_id,
+                    version,
+                )?;
+            }
+        }
+    }
+    pom.save(&path)?;
+    Ok(())
+}
+
+/// Initializr IDs of the project's dependencies, from its lockfile when it
+/// has one
+async fn dependency_ids(config: &ProjectConfig) -> Result<Vec<String>> {
+    let lock_path = config.app_dir().join(lock::FILE_NAME);
+    if lock_path.exists() {
+        return Ok(lock::read(&lock_path)?.dependencies);
+    }
+    let settings = import::read_build_settings(config.build_tool, &config.module_dir())?;
+    Ok(import::dependency_ids(config, &settings.dependencies).await)
+}
+
+/// Move the project to another Spring Boot version, checking that its
+/// dependencies support it, then build to confirm
+pub async fn upgrade(
+    config: &ProjectConfig,
+    spec: &str,
+    openrewrite: bool,
+    build: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let build_file = config.build_file();
+    if !build_file.exists() {
+        return Err(color_eyre::eyre::eyre!(
+            "No build file found at {}, run init first",
+            build_file.display()
+        ));
+    }
+    if openrewrite && config.build_tool.is_gradle() {
+        return Err(color_eyre::eyre::eyre!(
+            "--openrewrite is only supported for Maven projects"
+        ));
+    }
+
+    let metadata = cache::metadata(config, false).await?;
+    let target = versions::resolve(&metadata, spec).ok_or_else(|| {
+        color_eyre::eyre::eyre!("start.spring.io offers no Spring Boot {} release", spec)
+    })?;
+    if let Some(warning) = versions::check(&metadata, &target) {
+        eprintln!("Warning: {}", warning);
+    }
+
+    let current = import::read_build_settings(config.build_tool, &config.app_dir())?
+        .boot_version
+        .unwrap_or_else(|| config.boot_version.clone());
+    if current == target {
+        println!("{} is already on Spring Boot {}", config.app_name, target);
+        return Ok(());
+    }
+
+    let ids = dependency_ids(config).await?;
+    let incompatible = versions::incompatible_dependencies(&metadata, &ids, &target);
+    if !incompatible.is_empty() {
+        let details = incompatible
+            .iter()
+            .map(|(id, range)| format!("{} requires {}", id, range))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if config.incompatible_deps == IncompatiblePolicy::Warn {
+            eprintln!(
+                "Warning: dependencies not compatible with Spring Boot {}: {}",
+                target, details
+            );
+        } else {
+            return Err(color_eyre::eyre::eyre!(
+                "Dependencies not compatible with Spring Boot {}: {} (set incompatible_deps to warn to upgrade anyway)",
+                target,
+                details
+            ));
+        }
+    }
+
+    println!(
+        "Upgrading {} from Spring Boot {} to {}",
+        config.app_name, current, target
+    );
+    if dry_run {
+        if openrewrite {
+            println!("Would run OpenRewrite recipe {}", recipe(&target)?);
+        }
+        println!(
+            "Would set the Spring Boot version in {}",
+            build_file.display()
+        );
+        if build {
+            println!("Would build the project");
+        }
+        return Ok(());
+    }
+
+    // The recipe migrates code and properties, the exact version is set after
+    if openrewrite {
+        configure_openrewrite(config, &target)?;
+        run_openrewrite(config)?;
+    }
+    set_boot_version(config, &target)?;
+
+    let upgraded = ProjectConfig {
+        boot_version: target.clone(),
+        ..config.clone()
+    };
+    let mut registry = Registry::load(config)?;
+    registry.register(&upgraded);
+    registry.save(config)?;
+
+    if build {
+        project::build(&upgraded, false, None, false)?;
+    }
+    println!("Upgraded {} to Spring Boot {}", config.app_name, target);
+    Ok(())
+}
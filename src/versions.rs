@@ -0,0 +1,233 @@
+use crate::metadata::InitializrMetadata;
+use crate::output::{self, OutputFormat};
+use serde::Serialize;
+use std::cmp::Ordering;
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Channel {
+    Stable,
+    Milestone,
+    Snapshot,
+}
+
+impl Channel {
+    fn of(id: &str) -> Self {
+        let id = id.to_uppercase();
+        if id.contains("SNAPSHOT") {
+            Channel::Snapshot
+        } else if id.contains("-M") || id.contains(".M") || id.contains("RC") {
+            Channel::Milestone
+        } else {
+            Channel::Stable
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Channel::Stable => "stable",
+            Channel::Milestone => "milestone",
+            Channel::Snapshot => "snapshot",
+        }
+    }
+}
+
+/// A Spring Boot version offered by start.spring.io
+#[derive(Serialize)]
+pub struct BootVersion {
+    pub id: String,
+    pub name: String,
+    pub channel: Channel,
+    pub default: bool,
+}
+
+/// Boot versions listed in the client metadata, newest first
+pub fn boot_versions(metadata: &InitializrMetadata) -> Vec<BootVersion> {
+    let default = metadata.boot_version.default.as_deref();
+    metadata
+        .boot_version
+        .values
+        .iter()
+        .map(|value| BootVersion {
+            id: value.id.clone(),
+            name: value.name.clone(),
+            channel: Channel::of(&value.id),
+            default: Some(value.id.as_str()) == default,
+        })
+        .collect()
+}
+
+/// Numeric release of a version, e.g. 3.4.3 for both "3.4.3" and "3.4.3.RELEASE"
+fn release(version: &str) -> Vec<u64> {
+    version
+        .split(['.', '-'])
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+/// Sort key for a version, ordering qualifiers the way initializr does:
+/// milestones, then release candidates, then snapshots, then the release
+fn version_key(version: &str) -> (Vec<u64>, u8, u64) {
+    let mut release = release(version);
+    // 3.4 and 3.4.0 are the same release
+    while release.len() < 3 {
+        release.push(0);
+    }
+
+    let qualifier = version
+        .trim_start_matches(|c: char| c.is_ascii_digit() || c == '.')
+        .trim_start_matches(['.', '-'])
+        .to_uppercase();
+    let number = |prefix: &str| qualifier[prefix.len()..].parse().unwrap_or(0);
+    let (rank, number) = if qualifier.starts_with("RC") {
+        (1, number("RC"))
+    } else if qualifier.starts_with('M') {
+        (0, number("M"))
+    } else if qualifier.contains("SNAPSHOT") {
+        (2, 0)
+    } else {
+        (3, 0)
+    };
+    (release, rank, number)
+}
+
+/// Order two versions the way initializr does
+pub fn compare(a: &str, b: &str) -> Ordering {
+    version_key(a).cmp(&version_key(b))
+}
+
+/// Whether `version` falls in an initializr version range such as
+/// "[3.0.0,3.5.0-M1)", or is at least the version given on its own
+pub fn in_range(range: &str, version: &str) -> bool {
+    let version = version_key(version);
+    let range = range.trim();
+    let Some((lower, upper)) = range
+        .strip_prefix(['[', '('])
+        .and_then(|rest| rest.strip_suffix([']', ')']))
+        .and_then(|bounds| bounds.split_once(','))
+    else {
+        return version >= version_key(range);
+    };
+
+    let lower_ok = match version.cmp(&version_key(lower.trim())) {
+        Ordering::Greater => true,
+        Ordering::Equal => range.starts_with('['),
+        Ordering::Less => false,
+    };
+    let upper_ok = upper.trim().is_empty()
+        || match version.cmp(&version_key(upper.trim())) {
+            Ordering::Less => true,
+            Ordering::Equal => range.ends_with(']'),
+            Ordering::Greater => false,
+        };
+    lower_ok && upper_ok
+}
+
+/// Selected dependencies whose version range excludes `boot_version`, as
+/// `(id, range)` pairs
+pub fn incompatible_dependencies(
+    metadata: &InitializrMetadata,
+    deps: &[String],
+    boot_version: &str,
+) -> Vec<(String, String)> {
+    metadata
+        .dependencies()
+        .filter_map(|(_, dep)| Some((dep.id.as_str(), dep.version_range.as_deref()?)))
+        .filter(|(id, range)| deps.iter().any(|dep| dep == id) && !in_range(range, boot_version))
+        .map(|(id, range)| (id.to_string(), range.to_string()))
+        .collect()
+}
+
+/// Newest offered boot version every selected dependency supports,
+/// preferring stable releases
+pub fn compatible_version(metadata: &InitializrMetadata, deps: &[String]) -> Option<String> {
+    let mut versions = boot_versions(metadata);
+    versions.sort_by(|a, b| {
+        (b.channel == Channel::Stable)
+            .cmp(&(a.channel == Channel::Stable))
+            .then_with(|| version_key(&b.id).cmp(&version_key(&a.id)))
+    });
+    versions
+        .into_iter()
+        .find(|version| incompatible_dependencies(metadata, deps, &version.id).is_empty())
+        .map(|version| version.id)
+}
+
+/// Version `spec` stands for: with a trailing `.x`, the newest offered
+/// version of that line, preferring stable releases over milestones over
+/// snapshots, e.g. 3.4.x; otherwise `spec` itself
+pub fn resolve(metadata: &InitializrMetadata, spec: &str) -> Option<String> {
+    let Some(line) = spec.strip_suffix(".x") else {
+        return Some(spec.to_string());
+    };
+    let prefix = format!("{}.", line);
+    boot_versions(metadata)
+        .into_iter()
+        .filter(|version| version.id.starts_with(&prefix))
+        .max_by_key(|version| {
+            let preference = match version.channel {
+                Channel::Stable => 2,
+                Channel::Milestone => 1,
+                Channel::Snapshot => 0,
+            };
+            (preference, version_key(&version.id))
+        })
+        .map(|version| version.id)
+}
+
+fn same_version(a: &str, b: &str) -> bool {
+    let strip = |version: &str| version.trim_end_matches(".RELEASE").to_string();
+    strip(a) == strip(b)
+}
+
+/// Warning for a boot version start.spring.io doesn't offer, if any
+pub fn check(metadata: &InitializrMetadata, boot_version: &str) -> Option<String> {
+    let versions = boot_versions(metadata);
+    if versions.is_empty() || versions.iter().any(|v| same_version(&v.id, boot_version)) {
+        return None;
+    }
+
+    let stable: Vec<&BootVersion> = versions
+        .iter()
+        .filter(|v| v.channel == Channel::Stable)
+        .collect();
+    let oldest = stable
+        .iter()
+        .min_by(|a, b| version_key(&a.id).cmp(&version_key(&b.id)));
+    let supported = stable
+        .iter()
+        .map(|v| v.id.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Some(match oldest {
+        Some(oldest) if version_key(boot_version) < version_key(&oldest.id) => {
+            format!(
+                "Spring Boot {} is end of life and no longer offered by start.spring.io, supported versions: {}",
+                boot_version, supported
+            )
+        }
+        _ => format!(
+            "Spring Boot {} is not a known version, supported versions: {}",
+            boot_version, supported
+        ),
+    })
+}
+
+pub fn list(metadata: &InitializrMetadata, output: OutputFormat) -> color_eyre::eyre::Result<()> {
+    let versions = boot_versions(metadata);
+    if output.is_json() {
+        return output::print_json(&versions);
+    }
+
+    println!("{:<20} CHANNEL", "VERSION");
+    for version in versions {
+        println!(
+            "{:<20} {}{}",
+            version.id,
+            version.channel.as_str(),
+            if version.default { " (default)" } else { "" }
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use crate::{jdk, repositories, run, wrapper, ProjectConfig};
+use color_eyre::eyre::Result;
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::process::{Child, Command, ExitStatus};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Start the app from compiled classes, in its own process group so it can
+/// be stopped together with the JVM the build tool forks
+fn start_app(config: &ProjectConfig, profile: Option<&str>) -> Result<Child> {
+    let program = wrapper::program(config);
+    let task = if config.build_tool.is_gradle() {
+        "bootRun"
+    } else {
+        "spring-boot:run"
+    };
+    let mut command = Command::new(&program);
+    command.args(["-q", task]).current_dir(config.app_dir());
+    jdk::configure(&mut command, config);
+    repositories::configure(&mut command, config);
+    if let Some(profile) = profile {
+        command.env("SPRING_PROFILES_ACTIVE", profile);
+    }
+    run::new_process_group(&mut command);
+    command
+        .spawn()
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to run {}: {}", program, e))
+}
+
+fn compile(config: &ProjectConfig) -> Result<ExitStatus> {
+    let goal = if config.build_tool.is_gradle() {
+        "classes"
+    } else {
+        "compile"
+    };
+    let mut command = Command::new(wrapper::program(config));
+    command.args(["-q", goal]).current_dir(config.app_dir());
+    jdk::configure(&mut command, config);
+    repositories::configure(&mut command, config);
+    Ok(command.status()?)
+}
+
+/// Run the app and recompile whenever something under src/ changes.
+/// Without spring-boot-devtools the app is restarted after each successful
+/// compile, with it devtools picks up the new classes itself.
+pub async fn watch(
+    config: &ProjectConfig,
+    profile: Option<&str>,
+    debounce: Duration,
+) -> Result<()> {
+    let build_file = std::fs::read_to_string(config.build_file()).map_err(|_| {
+        color_eyre::eyre::eyre!("No {} found, run init first", config.build_file().display())
+    })?;
+    let devtools = build_file.contains("spring-boot-devtools");
+
+    let (sender, mut events) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            if matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            ) {
+                let _ = sender.send(event);
+            }
+        }
+    })?;
+    let sources: Vec<_> = config
+        .module_dirs()
+        .into_iter()
+        .map(|dir| dir.join("src"))
+        .collect();
+    for src in &sources {
+        watcher.watch(src, RecursiveMode::Recursive)?;
+    }
+    let src = sources
+        .iter()
+        .map(|src| src.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut app = start_app(config, profile)?;
+    if devtools {
+        println!(
+            "Watching {}, devtools restarts the app after each compile",
+            src
+        );
+    } else {
+        println!("Watching {}, the app restarts after each compile", src);
+    }
+
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    loop {
+        tokio::select! {
+            _ = &mut ctrl_c => break,
+            event = events.recv() => {
+                if event.is_none() {
+                    break;
+                }
+            }
+        }
+
+        // Wait for the burst of events an editor save or git checkout causes to settle
+        let mut changes = 1;
+        while let Ok(Some(_)) = tokio::time::timeout(debounce, events.recv()).await {
+            changes += 1;
+        }
+
+        let start = Instant::now();
+        println!("{} change(s) detected, compiling...", changes);
+        if !compile(config)?.success() {
+            println!(
+                "Compile failed after {:.1}s, waiting for changes",
+                start.elapsed().as_secs_f64()
+            );
+            continue;
+        }
+
+        let exited = app.try_wait()?.is_some();
+        if devtools && !exited {
+            println!(
+                "Compiled in {:.1}s, devtools is restarting the app",
+                start.elapsed().as_secs_f64()
+            );
+        } else {
+            run::stop_child(&mut app)?;
+            app = start_app(config, profile)?;
+            println!(
+                "Compiled and restarted in {:.1}s",
+                start.elapsed().as_secs_f64()
+            );
+        }
+    }
+
+    println!("Stopping {}...", config.app_name);
+    run::stop_child(&mut app)
+}
@@ -0,0 +1,110 @@
+use crate::metadata::InitializrMetadata;
+use crate::ProjectConfig;
+use color_eyre::eyre::Result;
+use inquire::{Confirm, MultiSelect, Select, Text};
+use std::fmt;
+
+struct DependencyOption {
+    id: String,
+    name: String,
+}
+
+impl fmt::Display for DependencyOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} - {}", self.id, self.name)
+    }
+}
+
+/// Prompt for project settings, using the current config values as defaults
+pub fn prompt_project(
+    config: &ProjectConfig,
+    metadata: &InitializrMetadata,
+) -> Result<ProjectConfig> {
+    let mut config = config.clone();
+
+    config.app_name = Text::new("App name:")
+        .with_default(&config.app_name)
+        .prompt()?;
+    config.package_name = Text::new("Package name:")
+        .with_default(&config.package_name)
+        .prompt()?;
+    config.java_version = select_option(
+        "Java version:",
+        metadata
+            .java_version
+            .values
+            .iter()
+            .map(|value| value.id.clone())
+            .collect(),
+        metadata.java_version.default.as_deref(),
+        &config.java_version,
+    )?;
+    config.boot_version = select_option(
+        "Spring Boot version:",
+        metadata
+            .boot_version
+            .values
+            .iter()
+            .map(|value| value.id.clone())
+            .collect(),
+        metadata.boot_version.default.as_deref(),
+        &config.boot_version,
+    )?;
+
+    Ok(config)
+}
+
+/// Select one of the values of a single-select metadata field, starting at
+/// `current` if it is available and the metadata default otherwise
+fn select_option(
+    message: &str,
+    ids: Vec<String>,
+    default: Option<&str>,
+    current: &str,
+) -> Result<String> {
+    if ids.is_empty() {
+        return Ok(Text::new(message).with_default(current).prompt()?);
+    }
+
+    let start = ids
+        .iter()
+        .position(|id| id == current)
+        .or_else(|| ids.iter().position(|id| Some(id.as_str()) == default))
+        .unwrap_or(0);
+
+    Ok(Select::new(message, ids)
+        .with_starting_cursor(start)
+        .prompt()?)
+}
+
+/// Searchable multi-select over all dependencies, with `selected` pre-checked
+pub fn prompt_dependencies(
+    metadata: &InitializrMetadata,
+    selected: &[String],
+) -> Result<Vec<String>> {
+    let options: Vec<DependencyOption> = metadata
+        .dependencies()
+        .map(|(_, dep)| DependencyOption {
+            id: dep.id.clone(),
+            name: dep.name.clone(),
+        })
+        .collect();
+
+    let defaults: Vec<usize> = options
+        .iter()
+        .enumerate()
+        .filter(|(_, option)| selected.contains(&option.id))
+        .map(|(i, _)| i)
+        .collect();
+
+    let chosen = MultiSelect::new("Dependencies (type to filter):", options)
+        .with_default(&defaults)
+        .with_page_size(15)
+        .prompt()?;
+
+    Ok(chosen.into_iter().map(|option| option.id).collect())
+}
+
+pub fn confirm(message: &str, default: bool) -> Result<bool> {
+    Ok(Confirm::new(message).with_default(default).prompt()?)
+}
@@ -0,0 +1,288 @@
+use crate::output::OutputFormat;
+use crate::projects::Registry;
+use crate::{compose, docker, init, project, InitRequest, ProjectConfig};
+use color_eyre::eyre::Result;
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Services generated together, read from workspace.yaml
+#[derive(Deserialize)]
+pub struct Workspace {
+    /// Name of the combined compose project and the database the services share
+    #[serde(default = "default_name")]
+    pub name: String,
+    /// PRD the services' sections are taken from, relative to workspace.yaml
+    #[serde(default)]
+    pub prd: Option<String>,
+    pub services: Vec<ServiceSpec>,
+}
+
+#[derive(Deserialize)]
+pub struct ServiceSpec {
+    /// App name, also the service's directory under projects_dir
+    pub name: String,
+    /// Base package, defaults to package_name with the last segment replaced by the name
+    #[serde(default)]
+    pub package: Option<String>,
+    /// Dependency IDs, added to the suggestions when there is a PRD section
+    #[serde(default)]
+    pub deps: Vec<String>,
+    /// Heading of the PRD section describing the service
+    #[serde(default)]
+    pub prd_section: Option<String>,
+}
+
+fn default_name() -> String {
+    "workspace".to_string()
+}
+
+impl Workspace {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to read {}: {}", path.display(), e))?;
+        let workspace: Workspace = serde_norway::from_str(&content)
+            .map_err(|e| color_eyre::eyre::eyre!("Invalid {}: {}", path.display(), e))?;
+        workspace.validate()?;
+        Ok(workspace)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.services.is_empty() {
+            return Err(color_eyre::eyre::eyre!("The workspace has no services"));
+        }
+        for (i, service) in self.services.iter().enumerate() {
+            if self.services[..i]
+                .iter()
+                .any(|other| other.name == service.name)
+            {
+                return Err(color_eyre::eyre::eyre!(
+                    "Service {} is listed twice",
+                    service.name
+                ));
+            }
+            if service.prd_section.is_some() && self.prd.is_none() {
+                return Err(color_eyre::eyre::eyre!(
+                    "Service {} has a prd_section but the workspace has no prd",
+                    service.name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ServiceSpec {
+    fn package(&self, config: &ProjectConfig) -> String {
+        if let Some(package) = &self.package {
+            return package.clone();
+        }
+        let segment: String = self
+            .name
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect::<String>()
+            .to_lowercase();
+        match config.package_name.rsplit_once('.') {
+            Some((parent, _)) => format!("{}.{}", parent, segment),
+            None => segment,
+        }
+    }
+}
+
+/// Lines under the markdown heading `heading`, up to the next heading of the
+/// same or a higher level
+fn prd_section(prd: &str, heading: &str) -> Option<String> {
+    let level = |line: &str| line.chars().take_while(|&c| c == '#').count();
+    let mut lines = prd.lines();
+    let depth = lines.by_ref().find_map(|line| {
+        let depth = level(line);
+        let title = line[depth..].trim();
+        (depth > 0 && title.eq_ignore_ascii_case(heading.trim())).then_some(depth)
+    })?;
+    let section: Vec<&str> = lines
+        .take_while(|line| !(1..=depth).contains(&level(line)))
+        .collect();
+    Some(section.join("\n").trim().to_string())
+}
+
+/// The service's settings, as recorded when it was generated
+fn service_config(
+    config: &ProjectConfig,
+    registry: &Registry,
+    spec: &ServiceSpec,
+) -> ProjectConfig {
+    registry
+        .project_config(&spec.name, config)
+        .unwrap_or_else(|| ProjectConfig {
+            app_name: spec.name.clone(),
+            package_name: spec.package(config),
+            ..service_defaults(config)
+        })
+}
+
+/// `config` without the settings naming a single project, so each service
+/// gets its artifactId and directory from its own name
+fn service_defaults(config: &ProjectConfig) -> ProjectConfig {
+    ProjectConfig {
+        artifact_id: None,
+        base_dir: None,
+        description: None,
+        ..config.clone()
+    }
+}
+
+/// Generate every service in the workspace, stopping at the first failure
+pub async fn init(
+    config: &ProjectConfig,
+    path: &Path,
+    yes: bool,
+    force: bool,
+    quiet: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let workspace = Workspace::load(path)?;
+    let prd = match &workspace.prd {
+        Some(prd) => {
+            let prd_path = path.parent().unwrap_or(Path::new(".")).join(prd);
+            Some(fs::read_to_string(&prd_path).map_err(|e| {
+                color_eyre::eyre::eyre!("Failed to read {}: {}", prd_path.display(), e)
+            })?)
+        }
+        None => None,
+    };
+
+    let count = workspace.services.len();
+    for (i, spec) in workspace.services.iter().enumerate() {
+        println!("==> {} ({}/{})", spec.name, i + 1, count);
+        let mut request = InitRequest::new()
+            .app_name(&spec.name)
+            .package_name(spec.package(config))
+            .yes(yes)
+            .force(force);
+
+        // init reads the PRD from a file, so the section gets one of its own
+        let mut section_path: Option<PathBuf> = None;
+        if let (Some(prd), Some(heading)) = (&prd, &spec.prd_section) {
+            let section = prd_section(prd, heading).ok_or_else(|| {
+                color_eyre::eyre::eyre!(
+                    "No section {:?} in {} for service {}",
+                    heading,
+                    workspace.prd.as_deref().unwrap_or_default(),
+                    spec.name
+                )
+            })?;
+            let file = env::temp_dir().join(format!("spring-init-{}-prd.md", spec.name));
+            fs::write(&file, section)?;
+            request = request
+                .prd(file.display().to_string())
+                .include(spec.deps.iter().cloned());
+            section_path = Some(file);
+        } else if !spec.deps.is_empty() {
+            request = request.dependencies(spec.deps.iter().cloned());
+        }
+
+        let result = init::init_project(
+            &service_defaults(config),
+            &request,
+            quiet,
+            dry_run,
+            OutputFormat::Text,
+        )
+        .await;
+        if let Some(file) = section_path {
+            let _ = fs::remove_file(file);
+        }
+        result.map_err(|e| color_eyre::eyre::eyre!("Failed to generate {}: {}", spec.name, e))?;
+    }
+
+    println!("Generated {} service(s)", count);
+    Ok(())
+}
+
+/// Build every service, reporting all failures at the end
+pub fn build(config: &ProjectConfig, path: &Path) -> Result<()> {
+    let workspace = Workspace::load(path)?;
+    let registry = Registry::load(config)?;
+    let mut failed = Vec::new();
+    for spec in &workspace.services {
+        println!("==> {}", spec.name);
+        if let Err(e) = project::build(&service_config(config, &registry, spec), false, None, false)
+        {
+            eprintln!("{}: {}", spec.name, e);
+            failed.push(spec.name.as_str());
+        }
+    }
+
+    if !failed.is_empty() {
+        return Err(color_eyre::eyre::eyre!(
+            "{} of {} service(s) failed to build: {}",
+            failed.len(),
+            workspace.services.len(),
+            failed.join(", ")
+        ));
+    }
+    println!("Built {} service(s)", workspace.services.len());
+    Ok(())
+}
+
+/// Write a docker-compose.yml running every service with the backing
+/// services they depend on, adding Dockerfiles the services are missing
+pub fn compose(config: &ProjectConfig, path: &Path, force: bool, dry_run: bool) -> Result<()> {
+    let workspace = Workspace::load(path)?;
+    let registry = Registry::load(config)?;
+    let projects: Vec<ProjectConfig> = workspace
+        .services
+        .iter()
+        .map(|spec| service_config(config, &registry, spec))
+        .collect();
+
+    if !dry_run {
+        for project in &projects {
+            if project.build_file().exists() {
+                docker::write_dockerfile(project)?;
+            }
+        }
+    }
+    compose::write_workspace(
+        &config.scaffold_dir(),
+        &workspace.name,
+        &projects,
+        force,
+        dry_run,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_workspace_yaml() {
+        let path =
+            env::temp_dir().join(format!("spring-init-workspace-{}.yaml", std::process::id()));
+        fs::write(
+            &path,
+            "name: shop\nprd: prd.md\nservices:\n  - name: orders\n    prd_section: Order Service\n    deps: [postgresql]\n  - name: catalog\n    package: com.example.catalog\n    deps: [web, data-jpa]\n",
+        )
+        .unwrap();
+
+        let workspace = Workspace::load(&path);
+        fs::remove_file(&path).unwrap();
+        let workspace = workspace.unwrap();
+
+        assert_eq!(workspace.name, "shop");
+        assert_eq!(workspace.prd.as_deref(), Some("prd.md"));
+        assert_eq!(workspace.services.len(), 2);
+        assert_eq!(
+            workspace.services[0].prd_section.as_deref(),
+            Some("Order Service")
+        );
+        assert_eq!(
+            workspace.services[1].package.as_deref(),
+            Some("com.example.catalog")
+        );
+        assert_eq!(workspace.services[1].deps, ["web", "data-jpa"]);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::ProjectConfig;
+use color_eyre::eyre::Result;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Maven version `wrapper update` pins by default
+pub const MAVEN_VERSION: &str = "3.9.9";
+/// maven-wrapper-plugin version installing the wrapper
+const WRAPPER_VERSION: &str = "3.3.2";
+const REPOSITORY_URL: &str = "https://repo.maven.apache.org/maven2";
+
+/// Maven from PATH, a batch file on Windows where only .exe files are found
+/// without their extension
+pub const MVN: &str = if cfg!(windows) { "mvn.cmd" } else { "mvn" };
+/// Maven wrapper script of the current platform
+const MVNW: &str = if cfg!(windows) { "mvnw.cmd" } else { "mvnw" };
+/// Gradle wrapper script of the current platform
+const GRADLEW: &str = if cfg!(windows) {
+    "gradlew.bat"
+} else {
+    "gradlew"
+};
+
+/// Program running the wrapper `script` of the project in `dir`. Unix runs
+/// `./script` from the project dir, Windows doesn't look programs up in the
+/// child's working directory so it gets the script's path.
+fn script(dir: &Path, script: &str) -> String {
+    if cfg!(windows) {
+        let path = dir.join(script);
+        std::path::absolute(&path)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    } else {
+        format!("./{}", script)
+    }
+}
+
+/// Whether the project in `dir` has a Maven wrapper usable on this platform
+pub fn has_maven_wrapper(dir: &Path) -> bool {
+    dir.join(MVNW).exists()
+}
+
+fn properties_path(dir: &Path) -> std::path::PathBuf {
+    dir.join(".mvn")
+        .join("wrapper")
+        .join("maven-wrapper.properties")
+}
+
+/// Properties for a wrapper running `maven_version`, `wrapperUrl` is only
+/// read by scripts that bootstrap the wrapper jar
+fn properties(maven_version: &str) -> String {
+    format!(
+        "wrapperVersion={wrapper}\n\
+        distributionUrl={repo}/org/apache/maven/apache-maven/{maven}/apache-maven-{maven}-bin.zip\n\
+        wrapperUrl={repo}/org/apache/maven/wrapper/maven-wrapper/{wrapper}/maven-wrapper-{wrapper}.jar\n",
+        wrapper = WRAPPER_VERSION,
+        repo = REPOSITORY_URL,
+        maven = maven_version,
+    )
+}
+
+/// Restore the executable bit and properties a wrapper can lose, e.g. when
+/// the project was copied without them
+fn repair(dir: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let script = dir.join("mvnw");
+        let mut permissions = fs::metadata(&script)?.permissions();
+        if permissions.mode() & 0o111 == 0 {
+            permissions.set_mode(permissions.mode() | 0o755);
+            fs::set_permissions(&script, permissions)?;
+            eprintln!("Made {} executable", script.display());
+        }
+    }
+
+    let path = properties_path(dir);
+    if !path.exists() {
+        fs::create_dir_all(path.parent().unwrap_or(dir))?;
+        fs::write(&path, properties(MAVEN_VERSION))?;
+        eprintln!("Restored {} for Maven {}", path.display(), MAVEN_VERSION);
+    }
+    Ok(())
+}
+
+/// Program running Maven in the project: the wrapper, repaired if needed,
+/// or `mvn` from PATH with a warning when the project has no wrapper
+pub fn maven(config: &ProjectConfig) -> String {
+    let dir = config.app_dir();
+    if !has_maven_wrapper(&dir) {
+        eprintln!(
+            "Warning: no Maven wrapper in {}, using mvn from PATH (run `spring-init wrapper update` to add one)",
+            dir.display()
+        );
+        return MVN.to_string();
+    }
+    if let Err(e) = repair(&dir) {
+        eprintln!("Warning: could not repair the Maven wrapper: {}", e);
+    }
+    script(&dir, MVNW)
+}
+
+/// Program running the project's build tool
+pub fn program(config: &ProjectConfig) -> String {
+    if config.build_tool.is_gradle() {
+        script(&config.app_dir(), GRADLEW)
+    } else {
+        maven(config)
+    }
+}
+
+/// Install or update the project's Maven wrapper with maven-wrapper-plugin,
+/// pinning `maven_version`
+pub fn update(config: &ProjectConfig, maven_version: &str, dry_run: bool) -> Result<()> {
+    if config.build_tool.is_gradle() {
+        return Err(color_eyre::eyre::eyre!(
+            "wrapper update is only supported for Maven projects"
+        ));
+    }
+    let dir = config.app_dir();
+    if !config.build_file().exists() {
+        return Err(color_eyre::eyre::eyre!(
+            "No {} found, run init first",
+            config.build_file().display()
+        ));
+    }
+    if dry_run {
+        println!(
+            "Would install Maven wrapper {} for Maven {} in {}",
+            WRAPPER_VERSION,
+            maven_version,
+            dir.display()
+        );
+        return Ok(());
+    }
+
+    // A working wrapper can replace itself, otherwise Maven has to be installed once
+    let program = if has_maven_wrapper(&dir) {
+        maven(config)
+    } else {
+        MVN.to_string()
+    };
+    let status = Command::new(&program)
+        .args([
+            "-N".to_string(),
+            "-q".to_string(),
+            format!(
+                "org.apache.maven.plugins:maven-wrapper-plugin:{}:wrapper",
+                WRAPPER_VERSION
+            ),
+            format!("-Dmaven={}", maven_version),
+        ])
+        .current_dir(&dir)
+        .status()
+        .map_err(|e| {
+            color_eyre::eyre::eyre!(
+                "Failed to run {}: {}, install Maven to bootstrap the wrapper",
+                program,
+                e
+            )
+        })?;
+    if !status.success() {
+        return Err(color_eyre::eyre::eyre!(
+            "Failed to install the Maven wrapper"
+        ));
+    }
+
+    // Keep the pinned version even when the plugin left existing properties alone
+    let path = properties_path(&dir);
+    let current = fs::read_to_string(&path).unwrap_or_default();
+    if !current.contains(&format!("apache-maven-{}-bin.zip", maven_version)) {
+        fs::write(&path, properties(maven_version))?;
+    }
+    println!(
+        "Installed Maven wrapper for Maven {} in {}",
+        maven_version,
+        dir.display()
+    );
+    Ok(())
+}